@@ -0,0 +1,40 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use mhwiss::data::{db::DB, db_storage::Storage, db_types::ID};
+
+// Needs the database, found as the binaries do (MHWISS_DB, ./MHWorldData...)
+fn storage(c: &mut Criterion) {
+	let db = match DB::new() {
+		Ok(db) => db,
+		Err(e) => {
+			eprintln!("{}", e);
+			return;
+		}
+	};
+	c.bench_function("load_all", |b| b.iter(|| {
+		let mut storage = Storage::new();
+		storage.load_all(&db).unwrap();
+		black_box(storage)
+	}));
+	// Written by the first iteration, read by the others
+	c.bench_function("load_cached", |b| b.iter(|| {
+		let mut storage = Storage::new();
+		storage.load_cached(&db).unwrap();
+		black_box(storage)
+	}));
+
+	// Every armor looked up once, as the sets loader does
+	let mut storage = Storage::new();
+	storage.load_all(&db).unwrap();
+	let ids: Vec<ID> = storage.armors.iter().map(|a| a.id).collect();
+	let mut group = c.benchmark_group("armor by id");
+	group.bench_function("index", |b| b.iter(|| {
+		ids.iter().filter_map(|id| storage.armors.get(*id)).count()
+	}));
+	group.bench_function("scan", |b| b.iter(|| {
+		ids.iter().filter_map(|id| storage.armors.iter().find(|a| a.id == *id)).count()
+	}));
+	group.finish();
+}
+
+criterion_group!(benches, storage);
+criterion_main!(benches);
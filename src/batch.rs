@@ -0,0 +1,120 @@
+use std::{
+	io::Write,
+	sync::Arc,
+	time::{Duration, Instant},
+};
+use itertools::Itertools;
+use rand::Rng;
+use crate::data::{
+	db_storage::Storage,
+	db_types::{
+		Skills,
+		localized::Localized,
+		skill::{SkillLevel, SkillsLevel},
+	},
+	mutable::equipment::Equipment,
+};
+use crate::engines::{Engines, progress::SearchContext};
+use crate::search::Search;
+
+// One searched constraint set of a batch
+pub struct BatchRow {
+	pub constraints: SkillsLevel,
+	pub best: Option<Equipment>,  // None when the engine found nothing
+	pub satisfied: bool,
+	pub runtime: Duration,
+}
+
+/*
+len different skills, each at a random level between 1 and its max level.
+None if there are less than len skills.
+*/
+pub fn generate_random_constraints<R: Rng>(skills: &Skills, len: usize, rng: &mut R) -> Option<SkillsLevel> {
+	let mut constraints = SkillsLevel::new();
+	let mut len = len;
+	if skills.len() < len {
+		return None;
+	}
+	// Sorted so the same seed gives the same constraints
+	let skills: Vec<_> = skills.iter().sorted_by_key(|s| s.id).collect();
+	while len > 0 {
+		let skill = skills[rng.gen_range(0..skills.len())];
+		if constraints.contains_id(skill.id) {
+			continue;
+		}
+		let level = rng.gen_range(1..=skill.max_level);
+		constraints.insert(SkillLevel::new(Arc::clone(skill), level));
+		len -= 1;
+	}
+	Some(constraints)
+}
+
+// Run the engine on every constraint set, one after the other so the runtimes are comparable
+pub fn run_batch(engine: Engines, storage: &Storage, sets: &[SkillsLevel], threads: usize) -> Vec<BatchRow> {
+	sets.iter().map(|constraints| {
+		let search = Search::new(engine, constraints.clone()).with_threads(threads);
+		let started = Instant::now();
+		let best = search.run(storage, &SearchContext::new(constraints)).ok()
+			.and_then(|results| results.into_iter().next());
+		let runtime = started.elapsed();
+		let satisfied = best.as_ref().is_some_and(|best| best.satisfies(constraints));
+		BatchRow { constraints: constraints.clone(), best, satisfied, runtime }
+	}).collect()
+}
+
+const HEADER: [&str; 21] = [
+	"constraints", "satisfied", "reached_levels", "requested_levels",
+	"weapon", "head", "chest", "arms", "waist", "legs", "charm", "tool_1", "tool_2",
+	"decorations", "defence", "fire", "water", "thunder", "ice", "dragon",
+	"runtime_ms",
+];
+
+// Items as "name [id]", decorations as "name [id] xN", lists separated by "; "
+pub fn write_csv<W: Write>(rows: &[BatchRow], writer: W) -> csv::Result<()> {
+	let mut csv = csv::Writer::from_writer(writer);
+	csv.write_record(HEADER.iter())?;
+	for row in rows {
+		let constraints = row.constraints.to_record().iter()
+			.map(|s| format!("{} {}", s.name, s.level))
+			.join("; ");
+		let requested: u32 = row.constraints.iter().map(|s| s.get_level() as u32).sum();
+		let mut record = vec![
+			constraints,
+			String::from(if row.satisfied { "yes" } else { "no" }),
+		];
+		match &row.best {
+			Some(best) => {
+				let item = |item: Option<(u16, &Localized)>| item.map_or(String::new(), |(id, name)| format!("{} [{}]", name, id));
+				record.push(best.reached_levels(&row.constraints).to_string());
+				record.push(requested.to_string());
+				record.push(item(best.weapon.as_ref().map(|w| (w.item.id, &w.item.name))));
+				for piece in best.set.iter() {
+					record.push(item(piece.as_ref().map(|a| (a.item.id, &a.item.name))));
+				}
+				record.push(item(best.charm.as_ref().map(|c| (c.id, &c.name))));
+				for tool in best.tools.iter() {
+					record.push(item(tool.as_ref().map(|t| (t.item.id, &t.item.name))));
+				}
+				record.push(best.get_used_decorations().iter()
+					.sorted_by_key(|(d, _)| d.id)
+					.map(|(d, n)| format!("{} [{}] x{}", d.name, d.id, n))
+					.join("; "));
+				record.push(best.get_defence().to_string());
+				record.push(best.get_fire_defence().to_string());
+				record.push(best.get_water_defence().to_string());
+				record.push(best.get_thunder_defence().to_string());
+				record.push(best.get_ice_defence().to_string());
+				record.push(best.get_dragon_defence().to_string());
+			}
+			None => {
+				record.push(String::from("0"));
+				record.push(requested.to_string());
+				record.resize(HEADER.len() - 1, String::new());
+			}
+		}
+		record.push(row.runtime.as_millis().to_string());
+		csv.write_record(&record)?;
+	}
+	csv.flush()?;
+	Ok(())
+}
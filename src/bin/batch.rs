@@ -0,0 +1,160 @@
+use std::{
+	env,
+	fs::{self, File},
+	io,
+	path::PathBuf,
+	process,
+	str::FromStr,
+};
+use rand::{Rng, SeedableRng, rngs::StdRng};
+use mhwiss::batch::{generate_random_constraints, run_batch, write_csv};
+use mhwiss::data::{
+	db::DB,
+	db_storage::Storage,
+	db_types::{ArmorRank, skill::SkillsLevel},
+	records::ConstraintsRecord,
+};
+use mhwiss::engines::{Engines, parallel::available_threads};
+use mhwiss::search::Filters;
+
+const USAGE: &str = r#"Usage: mhwiss-batch (--constraints <FILE> | --random <N>) [OPTIONS]
+
+Options:
+	--constraints <FILE>   JSON list of constraint sets [[{"id": 1, "level": 3}, ...], ...]
+	--random <N>           N random constraint sets
+	--max-skills <N>       Random sets have from 1 to N skills, default 5
+	--seed <N>             Seed of the random sets, default random
+	--engine <ENGINE>      Greedy or HillClimbing, default Greedy
+	--rank <LR|HR|MR>      Use only armors of this rank. Repeatable, default all the ranks
+	--threads <N>          Worker threads of each search, default one for each core
+	--lang <LANG>          Language of the names, default en
+	--db <FILE>            mhw.db to use, default $MHWISS_DB or the first one found
+	--output <FILE>        CSV report, default stdout
+	-h, --help             Print this message"#;
+
+struct Args {
+	constraints_file: Option<String>,
+	random: usize,
+	max_skills: usize,
+	seed: Option<u64>,
+	engine: Engines,
+	filters: Filters,
+	threads: usize,
+	lang: String,
+	db: Option<PathBuf>,
+	output: Option<String>,
+}
+
+fn parse_args() -> Result<Args, String> {
+	let mut args = Args {
+		constraints_file: None,
+		random: 0,
+		max_skills: 5,
+		seed: None,
+		engine: Engines::Greedy,
+		filters: Default::default(),
+		threads: available_threads(),
+		lang: String::from("en"),
+		db: None,
+		output: None,
+	};
+	let mut it = env::args().skip(1);
+	while let Some(arg) = it.next() {
+		if arg == "-h" || arg == "--help" {
+			println!("{}", USAGE);
+			process::exit(0);
+		}
+		let value = it.next().ok_or(format!("Missing value for {}", arg))?;
+		let number = |value: &str| value.parse::<usize>().map_err(|_| format!("Invalid number {}", value));
+		match arg.as_str() {
+			"--constraints" => args.constraints_file = Some(value),
+			"--random" => args.random = number(&value)?,
+			"--max-skills" => args.max_skills = number(&value)?.max(1),
+			"--seed" => args.seed = Some(value.parse().map_err(|_| format!("Invalid seed {}", value))?),
+			"--engine" => args.engine = Engines::from_str(&value).map_err(|_| format!("Unknown engine {}", value))?,
+			"--rank" => args.filters.ranks.push(ArmorRank::from_str(&value.to_uppercase()).map_err(|_| format!("Unknown rank {}", value))?),
+			"--threads" => args.threads = number(&value)?,
+			"--lang" => args.lang = value,
+			"--db" => args.db = Some(PathBuf::from(value)),
+			"--output" => args.output = Some(value),
+			_ => return Err(format!("Unknown option {}", arg)),
+		}
+	}
+	if args.constraints_file.is_none() && args.random == 0 {
+		return Err(String::from("--constraints or --random is required"));
+	}
+	Ok(args)
+}
+
+fn constraint_sets(storage: &Storage, args: &Args) -> Result<Vec<SkillsLevel>, String> {
+	let mut sets = Vec::new();
+	if let Some(file) = &args.constraints_file {
+		let json = fs::read_to_string(file).map_err(|e| format!("Cannot read {}: {}", file, e))?;
+		let records: Vec<ConstraintsRecord> = serde_json::from_str(&json).map_err(|e| format!("{}: {}", file, e))?;
+		for record in records.iter() {
+			sets.push(SkillsLevel::from_record(record, storage).map_err(|e| format!("{}: {}", file, e))?);
+		}
+	}
+	let mut rng = match args.seed {
+		Some(seed) => StdRng::seed_from_u64(seed),
+		None => StdRng::from_entropy(),
+	};
+	let total = sets.len() + args.random;
+	while sets.len() < total {
+		let len = rng.gen_range(1..=args.max_skills);
+		match generate_random_constraints(&storage.skills, len, &mut rng) {
+			Some(constraints) => sets.push(constraints),
+			None => return Err(format!("Less than {} skills in the database", len)),
+		}
+	}
+	Ok(sets)
+}
+
+fn main() {
+	let args = match parse_args() {
+		Ok(args) => args,
+		Err(e) => {
+			eprintln!("{}\n\n{}", e, USAGE);
+			process::exit(1);
+		}
+	};
+
+	let mut db = match args.db.as_deref().map_or_else(DB::new, DB::open) {
+		Ok(db) => db,
+		Err(e) => {
+			eprintln!("{}", e);
+			process::exit(1);
+		}
+	};
+	db.set_language(args.lang.clone());
+	let mut storage = Storage::new();
+	match storage.load_cached(&db) {
+		Ok(report) if report.is_empty() => {}
+		Ok(report) => eprintln!("{}", report),
+		Err(e) => {
+			eprintln!("{}", e);
+			process::exit(1);
+		}
+	}
+
+	let sets = match constraint_sets(&storage, &args) {
+		Ok(sets) => sets,
+		Err(e) => {
+			eprintln!("{}", e);
+			process::exit(1);
+		}
+	};
+	args.filters.apply(&mut storage);
+
+	let rows = run_batch(args.engine, &storage, &sets, args.threads);
+	let written = match &args.output {
+		Some(file) => File::create(file).map_err(csv::Error::from).and_then(|f| write_csv(&rows, f)),
+		None => write_csv(&rows, io::stdout()),
+	};
+	if let Err(e) = written {
+		eprintln!("Cannot write the report: {}", e);
+		process::exit(1);
+	}
+	let satisfied = rows.iter().filter(|r| r.satisfied).count();
+	eprintln!("{}/{} constraint sets satisfied", satisfied, rows.len());
+}
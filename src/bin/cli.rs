@@ -0,0 +1,332 @@
+use std::{
+	env,
+	fs,
+	ops::Not,
+	path::PathBuf,
+	process,
+	str::FromStr,
+	sync::Arc,
+};
+use mhwiss::data::{
+	crafting::{Crafted, ShoppingList},
+	csv_source::CsvSource,
+	damage::Target,
+	db::DB,
+	db_storage::Storage,
+	db_types::{
+		ArmorRank, Gender, Item, Level, WeaponClass,
+		decoration::Decoration,
+		monster::Monster,
+		skill::{SkillLevel, SkillsLevel},
+		weapon::{ClassData, WeaponFeature},
+	},
+	mutable::equipment::Equipment,
+	records::{ConstraintsRecord, SearchRecord},
+};
+use mhwiss::engines::{EngineError, Engines, Objective, parallel::available_threads, progress::SearchContext};
+use mhwiss::search::{Filters, Search};
+
+// Exit codes
+const EXIT_ERROR: i32 = 1;
+const EXIT_IMPOSSIBLE: i32 = 2;
+
+const USAGE: &str = r#"Usage: mhwiss-cli (--skill <NAME|ID>=<LEVEL> | --constraints <FILE> | --monster <NAME|ID>)... [OPTIONS]
+
+Options:
+	--skill <NAME|ID>=<LEVEL>  Requested skill, the name is the localized one. Repeatable
+	--constraints <FILE>       JSON skills list [{"id": 1, "level": 3}, ...] or a saved json search
+	--monster <NAME|ID>        Target monster: add the skills against its ailments, text shows the best weapons
+	--hitzone <NAME|ID>        Body part of the --monster: also pick a weapon and maximize the expected damage on it
+	--rank <LR|HR|MR>          Use only armors of this rank. Repeatable, default all the ranks
+	--gender <male|female>     Use only armors wearable by this gender, default all the armors
+	--weapon-class <CLASS>     Also pick a weapon of this class, e.g. great-sword or heavy-bowgun. Repeatable
+	--weapon <KIND>:<VALUE>    Also pick a weapon with this phial, shelling[:<min level>], kinsect, notes, coating
+	                           or ammo, e.g. shelling:wide:3 or coating:poison. Repeatable, all are required
+	--crafted <KIND>:<ID>      Already crafted weapon, armor or charm, e.g. weapon:12. The upgrades start from it. Repeatable
+	--shopping-list <FILE|->   Write the materials to craft the best equipment as CSV, - prints the list with --format text
+	--engine <ENGINE>          Greedy or HillClimbing, default Greedy
+	--threads <N>              Worker threads, default one for each core
+	--lang <LANG>              Language of the names, default en
+	--lang2 <LANG>             Show the names also in this language, --skill accepts both
+	--db <FILE>                mhw.db to use, default $MHWISS_DB or the first one found
+	--source-data <DIR>        Load from the MHWorldData source_data CSVs instead of mhw.db, without --lang2
+	--format <text|tsv|json>   text prints the equipment, tsv one item for each line, json every result
+	-h, --help                 Print this message
+
+Exit status: 0 found, 1 error, 2 impossible to satisfy the skills"#;
+
+#[derive(Clone, Copy, PartialEq)]
+enum Format {
+	Text,
+	Tsv,
+	Json,
+}
+
+struct Args {
+	skills: Vec<(String, Level)>,
+	constraints_files: Vec<String>,
+	monster: Option<String>,
+	hitzone: Option<String>,
+	filters: Filters,
+	crafted: Crafted,
+	shopping_list: Option<String>,
+	engine: Engines,
+	threads: usize,
+	lang: String,
+	lang2: Option<String>,
+	db: Option<PathBuf>,
+	source_data: Option<PathBuf>,
+	format: Format,
+}
+
+fn parse_args() -> Result<Args, String> {
+	let mut args = Args {
+		skills: Vec::new(),
+		constraints_files: Vec::new(),
+		monster: None,
+		hitzone: None,
+		filters: Default::default(),
+		crafted: Default::default(),
+		shopping_list: None,
+		engine: Engines::Greedy,
+		threads: available_threads(),
+		lang: String::from("en"),
+		lang2: None,
+		db: None,
+		source_data: None,
+		format: Format::Text,
+	};
+	let mut it = env::args().skip(1);
+	while let Some(arg) = it.next() {
+		if arg == "-h" || arg == "--help" {
+			println!("{}", USAGE);
+			process::exit(0);
+		}
+		let value = it.next().ok_or(format!("Missing value for {}", arg))?;
+		match arg.as_str() {
+			"--skill" => {
+				let (name, level) = value.rsplit_once('=').ok_or(format!("Expected <NAME|ID>=<LEVEL>, found {}", value))?;
+				let level = level.trim().parse::<Level>().map_err(|_| format!("Invalid level in {}", value))?;
+				args.skills.push((name.to_string(), level));
+			}
+			"--constraints" => args.constraints_files.push(value),
+			"--monster" => args.monster = Some(value),
+			"--hitzone" => {
+				args.hitzone = Some(value);
+				args.filters.weapons = true;
+			}
+			"--rank" => args.filters.ranks.push(ArmorRank::from_str(&value.to_uppercase()).map_err(|_| format!("Unknown rank {}", value))?),
+			"--gender" => args.filters.gender = Some(Gender::from_str(&value.to_lowercase()).map_err(|_| format!("Unknown gender {}", value))?),
+			"--weapon-class" => args.filters.weapon_classes.push(WeaponClass::from_str(&value.to_lowercase()).map_err(|_| format!("Unknown weapon class {}", value))?),
+			"--weapon" => args.filters.weapon_features.push(WeaponFeature::from_str(&value)?),
+			"--crafted" => {
+				let (kind, id) = value.split_once(':').ok_or(format!("Expected <KIND>:<ID>, found {}", value))?;
+				let id = id.trim().parse().map_err(|_| format!("Invalid id in {}", value))?;
+				match kind {
+					"weapon" => args.crafted.weapons.insert(id),
+					"armor" => args.crafted.armors.insert(id),
+					"charm" => args.crafted.charms.insert(id),
+					_ => return Err(format!("Unknown crafted kind {}", kind)),
+				};
+			}
+			"--shopping-list" => args.shopping_list = Some(value),
+			"--engine" => args.engine = Engines::from_str(&value).map_err(|_| format!("Unknown engine {}", value))?,
+			"--threads" => args.threads = value.parse().map_err(|_| format!("Invalid threads number {}", value))?,
+			"--lang" => args.lang = value,
+			"--lang2" => args.lang2 = Some(value),
+			"--db" => args.db = Some(PathBuf::from(value)),
+			"--source-data" => args.source_data = Some(PathBuf::from(value)),
+			"--format" => args.format = match value.as_str() {
+				"text" => Format::Text,
+				"tsv" => Format::Tsv,
+				"json" => Format::Json,
+				_ => return Err(format!("Unknown format {}", value)),
+			},
+			_ => return Err(format!("Unknown option {}", arg)),
+		}
+	}
+	if args.skills.is_empty() && args.constraints_files.is_empty() && args.monster.is_none() {
+		return Err(String::from("At least one --skill, --constraints or --monster is required"));
+	}
+	if args.hitzone.is_some() && args.monster.is_none() {
+		return Err(String::from("--hitzone requires --monster"));
+	}
+	if args.source_data.is_some() && (args.lang2.is_some() || args.db.is_some()) {
+		return Err(String::from("--source-data excludes --db and --lang2"));
+	}
+	// The printed list would break the tsv and json output
+	if args.shopping_list.as_deref() == Some("-") && args.format != Format::Text {
+		return Err(String::from("--shopping-list - requires --format text, write the list to a file instead"));
+	}
+	Ok(args)
+}
+
+fn constraints(storage: &Storage, args: &Args) -> Result<SkillsLevel, String> {
+	let mut constraints = SkillsLevel::new();
+	for file in &args.constraints_files {
+		let json = fs::read_to_string(file).map_err(|e| format!("Cannot read {}: {}", file, e))?;
+		let record = match serde_json::from_str::<ConstraintsRecord>(&json) {
+			Ok(record) => record,
+			Err(_) => SearchRecord::from_json(&json).map_err(|e| format!("{}: {}", file, e))?.constraints,
+		};
+		let skills = SkillsLevel::from_record(&record, storage).map_err(|e| format!("{}: {}", file, e))?;
+		for skill in skills.iter() {
+			constraints.set(skill.clone());
+		}
+	}
+	for (name, level) in &args.skills {
+		let skill = storage.find_skill(name).ok_or(format!("Unknown skill {}", name))?;
+		if *level == 0 || *level > skill.max_level {
+			return Err(format!("{} level must be between 1 and {}", skill.name, skill.max_level));
+		}
+		constraints.set(SkillLevel::new(skill, *level));
+	}
+	if let Some(monster) = monster(storage, args)? {
+		monster.apply_suggested(&mut constraints);
+	}
+	Ok(constraints)
+}
+
+fn monster(storage: &Storage, args: &Args) -> Result<Option<Arc<Monster>>, String> {
+	args.monster.as_ref()
+		.map(|name| storage.find_monster(name).ok_or(format!("Unknown monster {}", name)))
+		.transpose()
+}
+
+fn objective(storage: &Storage, args: &Args) -> Result<Objective, String> {
+	let (monster, name) = match (monster(storage, args)?, &args.hitzone) {
+		(Some(monster), Some(name)) => (monster, name),
+		_ => return Ok(Objective::Defence),
+	};
+	let hitzone = monster.find_hitzone(name).ok_or(format!("Unknown hitzone {} of {}", name, monster.name))?.id;
+	Ok(Objective::Damage(Target::new(storage, monster, hitzone).expect("Hitzone of the monster")))
+}
+
+fn load(storage: &mut Storage, args: &Args) -> Result<(), String> {
+	let report = match &args.source_data {
+		Some(dir) => storage.load_all(&CsvSource::new(dir, Some(args.lang.clone()))).map_err(|e| e.to_string())?,
+		None => {
+			let mut db = args.db.as_deref().map_or_else(DB::new, DB::open).map_err(|e| e.to_string())?;
+			db.set_language(args.lang.clone());
+			let report = storage.load_cached(&db).map_err(|e| e.to_string())?;
+			storage.set_secondary_language(&db, args.lang2.as_deref()).map_err(|e| e.to_string())?;
+			report
+		}
+	};
+	if !report.is_empty() {
+		eprintln!("{}", report);
+	}
+	Ok(())
+}
+
+fn print_tsv(equipment: &Equipment) {
+	if let Some(weapon) = &equipment.weapon {
+		println!("weapon\t{}\t{}\t{}", weapon.item.id, weapon.item.name, decorations_ids(&weapon.decorations));
+	}
+	for armor in equipment.set.iter().flatten() {
+		println!("{}\t{}\t{}\t{}", armor.item.class, armor.item.id, armor.item.name, decorations_ids(&armor.decorations));
+	}
+	if let Some(charm) = &equipment.charm {
+		println!("charm\t{}\t{}\t", charm.id, charm.name);
+	}
+	for tool in equipment.tools.iter().flatten() {
+		println!("tool\t{}\t{}\t{}", tool.item.id, tool.item.name, decorations_ids(&tool.decorations));
+	}
+}
+
+// Decorations ids in slot order, 0 for the empty slots
+fn decorations_ids(decorations: &[Option<Arc<Decoration>>]) -> String {
+	decorations.iter()
+		.map(|d| d.as_ref().map_or(0, |d| d.id).to_string())
+		.collect::<Vec<_>>()
+		.join(",")
+}
+
+fn main() {
+	let args = match parse_args() {
+		Ok(args) => args,
+		Err(e) => {
+			eprintln!("{}\n\n{}", e, USAGE);
+			process::exit(EXIT_ERROR);
+		}
+	};
+
+	let mut storage = Storage::new();
+	if let Err(e) = load(&mut storage, &args) {
+		eprintln!("{}", e);
+		process::exit(EXIT_ERROR);
+	}
+
+	let (constraints, objective) = match constraints(&storage, &args).and_then(|c| Ok((c, objective(&storage, &args)?))) {
+		Ok(c) => c,
+		Err(e) => {
+			eprintln!("{}", e);
+			process::exit(EXIT_ERROR);
+		}
+	};
+	// Ranked on every weapon, also when the search does not pick one
+	let against = match monster(&storage, &args) {
+		Ok(Some(monster)) => {
+			let best = monster.best_weapons(&storage.weapons, 3).into_iter()
+				.map(|w| w.name.to_string())
+				.collect::<Vec<_>>();
+			Some(format!("{}, best weapons: {}", monster, if best.is_empty() { String::from("-") } else { best.join(", ") }))
+		}
+		_ => None,
+	};
+	// The upgrade chains need the weapons and charms left out by the filters
+	let full = args.shopping_list.is_some().then(|| storage.clone());
+	args.filters.apply(&mut storage);
+
+	let search = Search::new(args.engine, constraints.clone())
+		.with_threads(args.threads)
+		.with_objective(objective.clone())
+		.with_weapon_required(args.filters.requires_weapon());
+	let (pool, pruned) = search.prune(&storage);
+	eprintln!("Pruned {}", pruned);  // stderr, the stdout is for the results
+	match search.run_pruned(pool, &SearchContext::new(&constraints)) {
+		Ok(bests) => {
+			let best = bests.first().expect("Engine returned no equipment");
+			match args.format {
+				Format::Text => {
+					println!("{}", best);
+					if let Some(weapon) = best.weapon.as_ref().filter(|w| w.item.class_data != ClassData::None) {
+						println!("{}: {}", weapon.item.name, weapon.item.class_data);
+					}
+					if let Some(against) = &against {
+						println!("{}", against);
+					}
+					if let Objective::Damage(target) = &objective {
+						match target.damage(best) {
+							Some(damage) => println!("Damage on {}: {}", target, damage),
+							None => println!("Damage on {}: no weapon picked", target),
+						}
+					}
+				}
+				Format::Tsv => print_tsv(best),
+				Format::Json => println!("{}", SearchRecord::new(&args.engine.to_string(), &constraints, &bests).to_json()),
+			}
+			if let (Some(file), Some(full)) = (&args.shopping_list, &full) {
+				let list = ShoppingList::new(best, full, &args.crafted);
+				if file == "-" {
+					println!("{}", list);
+				} else if let Err(e) = fs::write(file, list.to_csv()) {
+					eprintln!("Cannot write {}: {}", file, e);
+					process::exit(EXIT_ERROR);
+				}
+			}
+			if best.satisfies(&constraints).not() {
+				eprintln!("Impossible to reach all the skills, best found: {}", best.get_skills());
+				process::exit(EXIT_IMPOSSIBLE);
+			}
+		}
+		Err(EngineError::Impossible) => {
+			eprintln!("Impossible to reach all the skills");
+			process::exit(EXIT_IMPOSSIBLE);
+		}
+		Err(EngineError::Cancelled) => {
+			eprintln!("Search cancelled");
+			process::exit(EXIT_ERROR);
+		}
+	}
+}
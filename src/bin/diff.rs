@@ -0,0 +1,94 @@
+use std::{
+	env,
+	path::{Path, PathBuf},
+	process,
+};
+use mhwiss::data::{
+	db::DB,
+	db_storage::Storage,
+	diff::StorageDiff,
+};
+
+const USAGE: &str = r#"Usage: mhwiss-diff <OLD> <NEW> [OPTIONS]
+
+Added, removed and changed skills, armors, decorations, charms, weapons and monsters from the OLD mhw.db to the NEW one.
+
+Options:
+	--lang <LANG>          Language of the names, default en
+	--format <text|json>   Output format, default text
+	-h, --help             Print this message"#;
+
+struct Args {
+	old: PathBuf,
+	new: PathBuf,
+	lang: String,
+	json: bool,
+}
+
+fn parse_args() -> Result<Args, String> {
+	let mut files = Vec::new();
+	let mut lang = String::from("en");
+	let mut json = false;
+	let mut it = env::args().skip(1);
+	while let Some(arg) = it.next() {
+		if arg == "-h" || arg == "--help" {
+			println!("{}", USAGE);
+			process::exit(0);
+		}
+		if !arg.starts_with("--") {
+			files.push(PathBuf::from(arg));
+			continue;
+		}
+		let value = it.next().ok_or(format!("Missing value for {}", arg))?;
+		match arg.as_str() {
+			"--lang" => lang = value,
+			"--format" => json = match value.as_str() {
+				"text" => false,
+				"json" => true,
+				_ => return Err(format!("Unknown format {}", value)),
+			},
+			_ => return Err(format!("Unknown option {}", arg)),
+		}
+	}
+	let mut files = files.into_iter();
+	match (files.next(), files.next(), files.next()) {
+		(Some(old), Some(new), None) => Ok(Args { old, new, lang, json }),
+		_ => Err(String::from("Expected the OLD and the NEW database")),
+	}
+}
+
+// Not cached, the cache of a language holds one database at a time
+fn load(path: &Path, lang: &str) -> Result<Storage, String> {
+	let mut db = DB::open(path).map_err(|e| e.to_string())?;
+	db.set_language(lang.to_string());
+	let mut storage = Storage::new();
+	let report = storage.load_all(&db).map_err(|e| format!("{}: {}", path.display(), e))?;
+	if !report.is_empty() {
+		eprintln!("{}: {}", path.display(), report);
+	}
+	Ok(storage)
+}
+
+fn main() {
+	let args = match parse_args() {
+		Ok(args) => args,
+		Err(e) => {
+			eprintln!("{}\n\n{}", e, USAGE);
+			process::exit(1);
+		}
+	};
+	let storages = load(&args.old, &args.lang).and_then(|old| Ok((old, load(&args.new, &args.lang)?)));
+	let (old, new) = match storages {
+		Ok(storages) => storages,
+		Err(e) => {
+			eprintln!("{}", e);
+			process::exit(1);
+		}
+	};
+	let diff = StorageDiff::new(&old, &new);
+	if args.json {
+		println!("{}", serde_json::to_string_pretty(&diff).expect("The diff is plain data"));
+	} else {
+		println!("{}", diff);
+	}
+}
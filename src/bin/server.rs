@@ -0,0 +1,77 @@
+use std::{
+	env,
+	path::PathBuf,
+	process,
+};
+use mhwiss::data::{db::DB, db_storage::Storage};
+use mhwiss::engines::parallel::available_threads;
+use mhwiss::server::Server;
+
+const USAGE: &str = "Usage: mhwiss-server [OPTIONS]
+
+Options:
+	--addr <ADDRESS>   Listening address, default 127.0.0.1:8080
+	--workers <N>      Requests served at the same time, default one for each core
+	--lang <LANG>      Language of the names, default en
+	--db <FILE>        mhw.db to use, default $MHWISS_DB or the first one found
+	-h, --help         Print this message";
+
+fn main() {
+	let mut addr = String::from("127.0.0.1:8080");
+	let mut workers = available_threads();
+	let mut lang = String::from("en");
+	let mut db_path: Option<PathBuf> = None;
+
+	let mut it = env::args().skip(1);
+	while let Some(arg) = it.next() {
+		if arg == "-h" || arg == "--help" {
+			println!("{}", USAGE);
+			return;
+		}
+		let value = it.next().unwrap_or_else(|| {
+			eprintln!("Missing value for {}\n\n{}", arg, USAGE);
+			process::exit(1);
+		});
+		match arg.as_str() {
+			"--addr" => addr = value,
+			"--workers" => workers = value.parse().unwrap_or_else(|_| {
+				eprintln!("Invalid workers number {}", value);
+				process::exit(1);
+			}),
+			"--lang" => lang = value,
+			"--db" => db_path = Some(PathBuf::from(value)),
+			_ => {
+				eprintln!("Unknown option {}\n\n{}", arg, USAGE);
+				process::exit(1);
+			}
+		}
+	}
+
+	let mut db = match db_path.as_deref().map_or_else(DB::new, DB::open) {
+		Ok(db) => db,
+		Err(e) => {
+			eprintln!("{}", e);
+			process::exit(1);
+		}
+	};
+	db.set_language(lang);
+	let mut storage = Storage::new();
+	match storage.load_cached(&db) {
+		Ok(report) if report.is_empty() => {}
+		Ok(report) => eprintln!("{}", report),
+		Err(e) => {
+			eprintln!("{}", e);
+			process::exit(1);
+		}
+	}
+
+	let server = match Server::bind(&addr, storage, workers) {
+		Ok(server) => server,
+		Err(e) => {
+			eprintln!("Cannot listen on {}: {}", addr, e);
+			process::exit(1);
+		}
+	};
+	eprintln!("Listening on http://{}", server.local_addr());
+	server.run();
+}
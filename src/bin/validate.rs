@@ -0,0 +1,60 @@
+use std::{
+	env,
+	path::PathBuf,
+	process,
+};
+use mhwiss::data::db::DB;
+
+// Exit codes
+const EXIT_ERROR: i32 = 1;
+const EXIT_INVALID: i32 = 2;
+
+const USAGE: &str = r#"Usage: mhwiss-validate [OPTIONS]
+
+Checks a mhw.db before it is loaded: enum values, skill and set bonus references, sharpness and armor set slots.
+
+Options:
+	--db <FILE>   mhw.db to check, default $MHWISS_DB or the first one found
+	-h, --help    Print this message
+
+Exit status: 0 valid, 1 error, 2 issues found"#;
+
+fn parse_args() -> Result<Option<PathBuf>, String> {
+	let mut db = None;
+	let mut it = env::args().skip(1);
+	while let Some(arg) = it.next() {
+		if arg == "-h" || arg == "--help" {
+			println!("{}", USAGE);
+			process::exit(0);
+		}
+		let value = it.next().ok_or(format!("Missing value for {}", arg))?;
+		match arg.as_str() {
+			"--db" => db = Some(PathBuf::from(value)),
+			_ => return Err(format!("Unknown option {}", arg)),
+		}
+	}
+	Ok(db)
+}
+
+fn main() {
+	let db = match parse_args() {
+		Ok(db) => db,
+		Err(e) => {
+			eprintln!("{}\n\n{}", e, USAGE);
+			process::exit(EXIT_ERROR);
+		}
+	};
+	let db = match db.as_deref().map_or_else(DB::new, DB::open) {
+		Ok(db) => db,
+		Err(e) => {
+			eprintln!("{}", e);
+			process::exit(EXIT_ERROR);
+		}
+	};
+	println!("{}", db.path().display());
+	let validation = db.validate();
+	println!("{}", validation);
+	if !validation.is_valid() {
+		process::exit(EXIT_INVALID);
+	}
+}
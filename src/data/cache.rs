@@ -0,0 +1,143 @@
+use std::{
+	collections::hash_map::DefaultHasher,
+	fmt,
+	fs::{self, File},
+	hash::Hasher,
+	io::{self, BufReader, BufWriter, Read, Write},
+	ops::Not,
+	path::{Path, PathBuf},
+};
+use directories::ProjectDirs;
+use serde::{Deserialize, Serialize};
+use crate::data::{
+	db::{DataError, DB, LoadReport},
+	db_storage::Storage,
+	entries::Entries,
+};
+
+// Bump on every change of data::entries or of what the loaders put in it, the caches of other versions are ignored
+const CACHE_VERSION: u32 = 7;
+
+pub enum CacheError {
+	Io(io::Error),
+	Encode(rmp_serde::encode::Error),
+	Decode(rmp_serde::decode::Error),
+	Stale,  // Another version or another database
+	Invalid(DataError),  // The cache refers to an entity it does not contain
+}
+
+impl fmt::Display for CacheError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			CacheError::Io(e) => e.fmt(f),
+			CacheError::Encode(e) => e.fmt(f),
+			CacheError::Decode(e) => e.fmt(f),
+			CacheError::Stale => write!(f, "written by another version or from another database"),
+			CacheError::Invalid(e) => e.fmt(f),
+		}
+	}
+}
+
+impl From<io::Error> for CacheError {
+	fn from(e: io::Error) -> Self {
+		CacheError::Io(e)
+	}
+}
+
+impl From<rmp_serde::encode::Error> for CacheError {
+	fn from(e: rmp_serde::encode::Error) -> Self {
+		CacheError::Encode(e)
+	}
+}
+
+impl From<rmp_serde::decode::Error> for CacheError {
+	fn from(e: rmp_serde::decode::Error) -> Self {
+		CacheError::Decode(e)
+	}
+}
+
+#[derive(Serialize, Deserialize)]
+struct Header {
+	version: u32,
+	db_hash: u64,
+}
+
+// Cache of the storage in lang, None without a home directory
+pub fn cache_path(lang: Option<&str>) -> Option<PathBuf> {
+	let proj_dirs = ProjectDirs::from("org", "SilverCorp", "mhwiss")?;
+	Some(proj_dirs.cache_dir().join(format!("storage-{}.cache", lang.unwrap_or("none"))))
+}
+
+// Only tells if the database changed. DefaultHasher may change between Rust releases, that only costs a rebuild of the cache
+pub fn hash_file(path: &Path) -> io::Result<u64> {
+	let mut file = File::open(path)?;
+	let mut hasher = DefaultHasher::new();
+	let mut buffer = vec![0; 1 << 16];
+	loop {
+		match file.read(&mut buffer)? {
+			0 => return Ok(hasher.finish()),
+			n => hasher.write(&buffer[..n]),
+		}
+	}
+}
+
+// The header is read first, a stale cache is not decoded at all
+pub(crate) fn read(path: &Path, db_hash: u64) -> Result<Storage, CacheError> {
+	let mut reader = BufReader::new(File::open(path)?);
+	let header: Header = rmp_serde::decode::from_read(&mut reader)?;
+	if header.version != CACHE_VERSION || header.db_hash != db_hash {
+		return Err(CacheError::Stale);
+	}
+	let entries: Entries = rmp_serde::decode::from_read(&mut reader)?;
+	let mut storage = Storage::new();
+	let report = storage.load_all(&entries).map_err(CacheError::Invalid)?;
+	// The empty tables are already so in the database
+	match report.issues.into_iter().find(|issue| matches!(issue, DataError::Empty(_)).not()) {
+		Some(issue) => Err(CacheError::Invalid(issue)),
+		None => Ok(storage),
+	}
+}
+
+// Written aside and renamed, another launch never reads half a cache
+pub(crate) fn write(storage: &Storage, path: &Path, db_hash: u64) -> Result<(), CacheError> {
+	if let Some(dir) = path.parent() {
+		fs::create_dir_all(dir)?;
+	}
+	let partial = path.with_extension("partial");
+	let mut writer = BufWriter::new(File::create(&partial)?);
+	rmp_serde::encode::write(&mut writer, &Header { version: CACHE_VERSION, db_hash })?;
+	rmp_serde::encode::write(&mut writer, &Entries::new(storage))?;
+	writer.flush()?;
+	drop(writer);
+	fs::rename(&partial, path)?;
+	Ok(())
+}
+
+impl Storage {
+	/*
+	Same as load_all() but from the cache of the language of db when it was written from the same database file.
+	Otherwise the storage is loaded from db and cached for the next launch, so only that launch reports the
+	skipped data. A cache that cannot be read or written only costs the SQL load.
+	*/
+	pub fn load_cached(&mut self, db: &DB) -> Result<LoadReport, DataError> {
+		let path = cache_path(db.get_language());
+		let db_hash = hash_file(db.path()).ok();
+		if let (Some(path), Some(db_hash)) = (&path, db_hash) {
+			match read(path, db_hash) {
+				Ok(storage) => {
+					*self = storage;
+					return Ok(LoadReport::default());
+				}
+				Err(CacheError::Io(e)) if e.kind() == io::ErrorKind::NotFound => {}
+				Err(e) => eprintln!("Cache {} not used: {}", path.display(), e),
+			}
+		}
+		let report = self.load_all(db)?;
+		if let (Some(path), Some(db_hash)) = (path, db_hash) {
+			if let Err(e) = write(self, &path, db_hash) {
+				eprintln!("Cache {} not written: {}", path.display(), e);
+			}
+		}
+		Ok(report)
+	}
+}
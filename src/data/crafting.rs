@@ -0,0 +1,134 @@
+use std::{
+	collections::{HashMap, HashSet},
+	fmt,
+	sync::Arc,
+};
+use serde::{Deserialize, Serialize};
+use crate::data::{
+	db_storage::Storage,
+	db_types::{
+		ID,
+		material::{Material, RecipeKind},
+	},
+	mutable::equipment::Equipment,
+};
+
+/*
+The materials to farm before crafting an equipment, from the recipes of the storage.
+The decorations and the tools are never crafted, they are left out.
+*/
+
+// What the player already has, by id. A crafted weapon or charm is where its upgrades start from
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Crafted {
+	pub weapons: HashSet<ID>,
+	pub armors: HashSet<ID>,
+	pub charms: HashSet<ID>,
+}
+
+pub struct ShoppingList {
+	pub materials: Vec<(Arc<Material>, u32)>,  // By name
+	pub steps: Vec<(RecipeKind, String)>,  // What to craft, the upgrades of a chain in order
+	pub missing: Vec<String>,  // Steps without a known recipe, their materials are not counted
+}
+
+// id and its ancestors by previous until a crafted one, the last first. True if a crafted one was found
+fn chain(id: ID, crafted: &HashSet<ID>, previous: impl Fn(ID) -> Option<ID>) -> (Vec<ID>, bool) {
+	let mut ret = Vec::new();
+	let mut current = Some(id);
+	while let Some(id) = current {
+		if crafted.contains(&id) {
+			ret.reverse();
+			return (ret, true);
+		}
+		if ret.contains(&id) {
+			break;  // A loop in the data
+		}
+		ret.push(id);
+		current = previous(id);
+	}
+	ret.reverse();
+	(ret, false)
+}
+
+impl ShoppingList {
+	pub fn new(equipment: &Equipment, storage: &Storage, crafted: &Crafted) -> Self {
+		let mut steps: Vec<(RecipeKind, ID)> = Vec::new();
+		if let Some(weapon) = &equipment.weapon {
+			let (ids, upgrade) = chain(weapon.item.id, &crafted.weapons, |id| storage.weapons.get(id).and_then(|w| w.previous_id));
+			// Without a crafted one the chain starts from the nearest weapon that can be forged
+			let start = if upgrade {
+				0
+			} else {
+				ids.iter().rposition(|id| storage.recipes.contains_key(&(RecipeKind::WeaponCreate, *id))).unwrap_or(0)
+			};
+			for (i, id) in ids.iter().enumerate().skip(start) {
+				let kind = if i == start && !upgrade { RecipeKind::WeaponCreate } else { RecipeKind::WeaponUpgrade };
+				steps.push((kind, *id));
+			}
+		}
+		for armor in equipment.set.iter().flatten() {
+			if !crafted.armors.contains(&armor.item.id) {
+				steps.push((RecipeKind::Armor, armor.item.id));
+			}
+		}
+		if let Some(charm) = &equipment.charm {
+			let (ids, _) = chain(charm.id, &crafted.charms, |id| storage.charms.get(id).and_then(|c| c.previous_id));
+			steps.extend(ids.into_iter().map(|id| (RecipeKind::Charm, id)));
+		}
+
+		let mut totals: HashMap<ID, (Arc<Material>, u32)> = HashMap::new();
+		let mut ret = ShoppingList { materials: Vec::new(), steps: Vec::new(), missing: Vec::new() };
+		for (kind, id) in steps {
+			let name = match kind {
+				RecipeKind::Armor => storage.armors.get(id).map(|a| a.name.to_string()),
+				RecipeKind::Charm => storage.charms.get(id).map(|c| c.name.to_string()),
+				RecipeKind::WeaponCreate | RecipeKind::WeaponUpgrade => storage.weapons.get(id).map(|w| w.name.to_string()),
+			}.unwrap_or_else(|| format!("{} {}", kind, id));
+			match storage.recipes.get(&(kind, id)) {
+				Some(recipe) => for (material, quantity) in recipe.iter() {
+					totals.entry(material.id).or_insert_with(|| (Arc::clone(material), 0)).1 += *quantity as u32;
+				},
+				None => ret.missing.push(name.clone()),
+			}
+			ret.steps.push((kind, name));
+		}
+		ret.materials = totals.into_values().collect();
+		ret.materials.sort_by_key(|(material, _)| (material.name.get(), material.id));
+		ret
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.steps.is_empty()
+	}
+
+	// One line for each material, to import in a spreadsheet
+	pub fn to_csv(&self) -> String {
+		let mut ret = String::from("id,name,rarity,quantity\n");
+		for (material, quantity) in self.materials.iter() {
+			ret += &format!("{},\"{}\",{},{}\n", material.id, material.name.get().replace('"', "\"\""), material.rarity, quantity);
+		}
+		ret
+	}
+}
+
+impl fmt::Display for ShoppingList {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		if self.is_empty() {
+			return write!(f, "Nothing to craft");
+		}
+		write!(f, "Craft:")?;
+		for (kind, name) in self.steps.iter() {
+			write!(f, "\n\t{} ({})", name, kind)?;
+		}
+		write!(f, "\nMaterials:")?;
+		for (material, quantity) in self.materials.iter() {
+			write!(f, "\n\t{:>3} x {}", quantity, material.name)?;
+		}
+		if !self.missing.is_empty() {
+			write!(f, "\nNo recipe for: {}", self.missing.join(", "))?;
+		}
+		Ok(())
+	}
+}
@@ -0,0 +1,604 @@
+use std::{
+	collections::HashMap,
+	path::{Path, PathBuf},
+	str::FromStr,
+	sync::Arc,
+};
+use csv::StringRecord;
+use itertools::Itertools;
+use crate::data::{
+	damage::{DamageSkills, resolve_damage_skills},
+	db::{self, DataError, LoadReport},
+	db_types::{
+		ArmorClass, ArmorRank, Armors, ArmorSets, Charms, Decorations, ElderSeal, Element, Gender, ID, Level,
+		Materials, Monsters, Recipes, SHARPNESS_LEVELS, SetSkills, Skills, Tools, WeaponClass, Weapons,
+		armor::{Armor, ArmorSet},
+		charm::Charm,
+		decoration::Decoration,
+		id_map::IdMap,
+		material::{Material, RecipeKind},
+		monster::Monster,
+		skill::{SetSkill, Skill, SkillLevel, SkillsLevel},
+		tool::Tool,
+		weapon::Weapon,
+	},
+	source::DataSource,
+};
+
+/*
+The source files of MHWorldData (MHWorldData/source_data), read without building mhw.db.
+Every table is a CSV with a header row, the rows refer to each other by English name:
+	skills/skill_base.csv              name_en, description_en, max_level, secret, unlocks
+	skills/skill_levels.csv            base_name_en, level (the max level when skill_base has no max_level)
+	armors/armorset_bonus_base.csv     name_en, skill1_name, skill1_required, skill2_name, skill2_required
+	armors/armor_base.csv              name_en, rank, type, armorset, male, female, slot_1..3,
+	                                   defense_base, defense_max, defense_augment_max, defense_fire..defense_dragon,
+	                                   skill1_name, skill1_level...
+	armors/armorset_base.csv           name_en, rank, head, chest, arms, waist, legs, bonus
+	charms/charm_base.csv              name_en, previous_en, skill1_name, skill1_level...
+	decorations/decoration_base.csv    name_en, slot, skill1_name, skill1_level, skill2_name, skill2_level
+	weapons/weapon_base.csv            name_en, weapon_type, previous_en, attack, affinity, defense, slot_1..3,
+	                                   element1, element1_attack, element2, element2_attack, element_hidden,
+	                                   elderseal, skill, armorset_bonus, ammo_config and the class columns of mhw.db
+	weapons/weapon_sharpness.csv       name_en, red, orange, yellow, green, blue, white, purple
+	weapons/weapon_ammo.csv            key, deviation, special_ammo, <ammo>_clip, <ammo>_rapid, <ammo>_recoil, <ammo>_reload
+	monsters/monster_base.csv          name_en, size, ailment_roar..ailment_effluvia
+	monsters/monster_weaknesses.csv    name_en, form, fire..stun (only the normal form is used)
+	items/item_base.csv                name_en, rarity
+	armors/armor_craft_ext.csv         base_name_en, item1_name, item1_qty..item4_name, item4_qty
+	charms/charm_craft.csv             base_name_en, item1_name, item1_qty..item4_name, item4_qty
+	weapons/weapon_craft.csv           base_name_en, type (Create or Upgrade), item1_name, item1_qty..item4_name, item4_qty
+The hitzones are in a YAML file, the monsters have none.
+The names in another language come from a name_<lang> column or from <table>_translations.csv next to the table.
+Without an id column the ids are the row numbers, they differ from the ones of mhw.db.
+*/
+const SKILLS: TableFile = ("skills/skill_base.csv", "skilltree");
+const SKILL_LEVELS: TableFile = ("skills/skill_levels.csv", "skilltree");
+const SET_SKILLS: TableFile = ("armors/armorset_bonus_base.csv", "armorset_bonus");
+const ARMORS: TableFile = ("armors/armor_base.csv", "armor");
+const SETS: TableFile = ("armors/armorset_base.csv", "armorset");
+const CHARMS: TableFile = ("charms/charm_base.csv", "charm");
+const DECORATIONS: TableFile = ("decorations/decoration_base.csv", "decoration");
+const WEAPONS: TableFile = ("weapons/weapon_base.csv", "weapon");
+const SHARPNESS: TableFile = ("weapons/weapon_sharpness.csv", "weapon");
+const AMMO: TableFile = ("weapons/weapon_ammo.csv", "weapon_ammo");
+const MONSTERS: TableFile = ("monsters/monster_base.csv", "monster");
+const WEAKNESSES: TableFile = ("monsters/monster_weaknesses.csv", "monster");
+const ITEMS: TableFile = ("items/item_base.csv", "item");
+const ARMOR_RECIPES: TableFile = ("armors/armor_craft_ext.csv", "recipe_item");
+const CHARM_RECIPES: TableFile = ("charms/charm_craft.csv", "recipe_item");
+const WEAPON_RECIPES: TableFile = ("weapons/weapon_craft.csv", "recipe_item");
+
+const SKILL_COLUMNS: [(&str, &str); 4] = [
+	("skill1_name", "skill1_level"),
+	("skill2_name", "skill2_level"),
+	("skill3_name", "skill3_level"),
+	("skill4_name", "skill4_level"),
+];
+const SET_SKILL_COLUMNS: [(&str, &str); 2] = [("skill1_name", "skill1_required"), ("skill2_name", "skill2_required")];
+const MATERIAL_COLUMNS: [(&str, &str); 4] = [
+	("item1_name", "item1_qty"),
+	("item2_name", "item2_qty"),
+	("item3_name", "item3_qty"),
+	("item4_name", "item4_qty"),
+];
+const SHARPNESS_COLUMNS: [&str; SHARPNESS_LEVELS] = ["red", "orange", "yellow", "green", "blue", "white", "purple"];
+
+// The source files have the attack shown in game, mhw.db the true one
+fn bloat(class: WeaponClass) -> f32 {
+	match class {
+		WeaponClass::GreatSword => 4.8,
+		WeaponClass::Longsword => 3.3,
+		WeaponClass::SwordAndShield | WeaponClass::DualBlade => 1.4,
+		WeaponClass::Hammer => 5.2,
+		WeaponClass::HuntingHorn => 4.2,
+		WeaponClass::Lance | WeaponClass::Gunlance => 2.3,
+		WeaponClass::SwitchAxe => 3.5,
+		WeaponClass::ChargeBlade => 3.6,
+		WeaponClass::InsectGlaive => 3.1,
+		WeaponClass::LightBowgun => 1.3,
+		WeaponClass::HeavyBowgun => 1.5,
+		WeaponClass::Bow => 1.2,
+	}
+}
+
+// A file and the table of mhw.db named in the report
+type TableFile = (&'static str, &'static str);
+
+struct Table {
+	name: &'static str,
+	path: PathBuf,
+	columns: HashMap<String, usize>,
+	records: Vec<StringRecord>,
+	translations: HashMap<String, String>,  // English name -> name in the language of the source
+}
+
+struct Row<'a> {
+	table: &'a Table,
+	record: &'a StringRecord,
+	id: ID,
+}
+
+impl Table {
+	fn rows(&self) -> Result<Vec<Row<'_>>, DataError> {
+		self.records.iter().enumerate().map(|(i, record)| {
+			let mut row = Row { table: self, record, id: i as ID + 1 };
+			if let Some(id) = row.get("id") {
+				row.id = id.parse().map_err(|_| DataError::File(self.path.clone(), format!("row {}: invalid id \"{}\"", i + 1, id)))?;
+			}
+			Ok(row)
+		}).collect()
+	}
+
+	fn ids(&self) -> Result<HashMap<String, ID>, DataError> {
+		Ok(self.rows()?.iter().map(|row| (row.name_en().to_string(), row.id)).collect())
+	}
+}
+
+impl Row<'_> {
+	// None for a missing column and for an empty value
+	fn get(&self, column: &str) -> Option<&str> {
+		self.table.columns.get(column)
+			.and_then(|i| self.record.get(*i))
+			.map(str::trim)
+			.filter(|value| !value.is_empty())
+	}
+
+	fn name_en(&self) -> &str {
+		self.get("name_en").unwrap_or_default()
+	}
+
+	// An empty value is the default, an invalid one is reported
+	fn number<T: FromStr + Default>(&self, column: &'static str, report: &mut LoadReport) -> T {
+		self.get(column).and_then(|value| report.parse(self.table.name, self.id, column, value)).unwrap_or_default()
+	}
+
+	fn flag(&self, column: &'static str, default: bool, report: &mut LoadReport) -> bool {
+		match self.get(column).map(str::to_lowercase).as_deref() {
+			None => default,
+			Some("true" | "1") => true,
+			Some("false" | "0") => false,
+			Some(value) => {
+				report.push(DataError::BadValue { table: self.table.name, id: self.id, column, value: value.to_string() });
+				default
+			}
+		}
+	}
+
+	// The entity named name in the ids of its table, column is where the name comes from
+	fn lookup<'a, T>(&self, items: &'a IdMap<T>, ids: &HashMap<String, ID>, name: &str,
+			column: &'static str, target: &'static str, report: &mut LoadReport) -> Option<&'a Arc<T>> {
+		match ids.get(name) {
+			Some(target_id) => report.reference(items.get(*target_id), self.table.name, self.id, target, *target_id),
+			None => {
+				report.push(DataError::BadValue { table: self.table.name, id: self.id, column, value: name.to_string() });
+				None
+			}
+		}
+	}
+}
+
+fn skills_level(row: &Row, columns: &[(&'static str, &'static str)], skills: &Skills, skill_ids: &HashMap<String, ID>, report: &mut LoadReport) -> SkillsLevel {
+	let mut ret = SkillsLevel::new();
+	for (name_column, level_column) in columns {
+		if let Some(name) = row.get(name_column) {
+			let level: Level = row.number(level_column, report);
+			if let Some(skill) = row.lookup(skills, skill_ids, name, name_column, "skilltree", report) {
+				ret.insert(SkillLevel::new(Arc::clone(skill), level));
+			}
+		}
+	}
+	ret
+}
+
+pub struct CsvSource {
+	dir: PathBuf,
+	lang: Option<String>,
+}
+
+impl CsvSource {
+	// dir is the source_data directory, the names are in lang or in English
+	pub fn new(dir: &Path, lang: Option<String>) -> Self {
+		CsvSource { dir: dir.to_path_buf(), lang: lang.filter(|l| l != "en") }
+	}
+
+	fn table(&self, file: TableFile) -> Result<Table, DataError> {
+		self.optional_table(file)?.ok_or_else(|| DataError::MissingTable(file.0.to_string()))
+	}
+
+	fn optional_table(&self, (file, name): TableFile) -> Result<Option<Table>, DataError> {
+		let path = self.dir.join(file);
+		if !path.exists() {
+			return Ok(None);
+		}
+		let (columns, records) = read_csv(&path)?;
+		let mut translations = HashMap::new();
+		if let Some(lang) = &self.lang {
+			let path = path.with_file_name(format!("{}_translations.csv", path.file_stem().unwrap_or_default().to_string_lossy()));
+			if path.exists() {
+				let (t_columns, t_records) = read_csv(&path)?;
+				if let (Some(en), Some(name)) = (t_columns.get("name_en"), t_columns.get(&format!("name_{}", lang))) {
+					translations = t_records.iter()
+						.filter_map(|r| Some((r.get(*en)?.trim().to_string(), r.get(*name)?.trim().to_string())))
+						.filter(|(_, name)| !name.is_empty())
+						.collect();
+				}
+			}
+		}
+		Ok(Some(Table { name, path, columns, records, translations }))
+	}
+
+	// The name in the language of the source, else the English one, else a placeholder. Every fallback is reported
+	fn name(&self, row: &Row, report: &mut LoadReport) -> String {
+		let table = row.table.name;
+		let lang = match &self.lang {
+			Some(lang) => lang,
+			None => return row.get("name_en").map_or_else(|| format!("{} {}", table, row.id), str::to_string),
+		};
+		let name = row.get(&format!("name_{}", lang)).or_else(|| row.table.translations.get(row.name_en()).map(String::as_str));
+		if let Some(name) = name {
+			return name.to_string();
+		}
+		let english = row.get("name_en");
+		report.push(DataError::MissingTranslation { table, id: row.id, lang: lang.clone(), english: english.is_some() });
+		english.map_or_else(|| format!("{} {}", table, row.id), str::to_string)
+	}
+
+	fn description(&self, row: &Row) -> String {
+		self.lang.as_ref().and_then(|lang| row.get(&format!("description_{}", lang)))
+			.or_else(|| row.get("description_en"))
+			.unwrap_or_default()
+			.to_string()
+	}
+}
+
+fn read_csv(path: &Path) -> Result<(HashMap<String, usize>, Vec<StringRecord>), DataError> {
+	let error = |e: csv::Error| DataError::File(path.to_path_buf(), e.to_string());
+	let mut reader = csv::Reader::from_path(path).map_err(error)?;
+	let columns = reader.headers().map_err(error)?.iter()
+		.enumerate()
+		.map(|(i, column)| (column.trim().to_string(), i))
+		.collect();
+	let records = reader.records().collect::<Result<_, _>>().map_err(error)?;
+	Ok((columns, records))
+}
+
+impl DataSource for CsvSource {
+	fn load_skills(&self, skills: &mut Skills, report: &mut LoadReport) -> Result<(), DataError> {
+		let table = self.table(SKILLS)?;
+		let ids = table.ids()?;
+		let mut max_levels: HashMap<String, Level> = HashMap::new();
+		if let Some(levels) = self.optional_table(SKILL_LEVELS)? {
+			for row in levels.rows()? {
+				if let (Some(name), Some(level)) = (row.get("base_name_en"), row.get("level").and_then(|l| l.parse::<Level>().ok())) {
+					let max = max_levels.entry(name.to_string()).or_default();
+					*max = level.max(*max);
+				}
+			}
+		}
+		// Like the SQL loader the unlocked skills come first, the skills that unlock them refer to them
+		let mut rows = table.rows()?;
+		rows.sort_by_key(|row| row.get("unlocks").is_some());
+		for row in rows {
+			let max_level = match row.get("max_level") {
+				Some(value) => report.parse("skilltree", row.id, "max_level", value),
+				None => max_levels.get(row.name_en()).copied(),
+			};
+			let max_level = match max_level {
+				Some(max_level) => max_level,
+				None => {
+					report.push(DataError::BadValue { table: "skilltree", id: row.id, column: "max_level", value: String::new() });
+					continue;
+				}
+			};
+			let unlock = row.get("unlocks")
+				.and_then(|name| row.lookup(skills, &ids, name, "unlocks", "skilltree", report))
+				.map(Arc::clone);
+			let secret = row.number("secret", report);
+			skills.insert(Arc::new(Skill::new(row.id, self.name(&row, report), self.description(&row), max_level, secret, unlock)));
+		}
+		report.expect_rows("skilltree", skills.len());
+		Ok(())
+	}
+
+	fn load_set_skills(&self, set_skills: &mut SetSkills, skills: &Skills, report: &mut LoadReport) -> Result<(), DataError> {
+		let skill_ids = self.table(SKILLS)?.ids()?;
+		let table = self.table(SET_SKILLS)?;
+		for row in table.rows()? {
+			let mut set_skill = SetSkill::new(row.id, self.name(&row, report));
+			set_skill.skills = skills_level(&row, &SET_SKILL_COLUMNS, skills, &skill_ids, report);
+			set_skills.insert(Arc::new(set_skill));
+		}
+		report.expect_rows("armorset_bonus_skill", set_skills.len());
+		Ok(())
+	}
+
+	fn load_armors(&self, armors: &mut Armors, skills: &Skills, set_skills: &SetSkills, report: &mut LoadReport) -> Result<(), DataError> {
+		let skill_ids = self.table(SKILLS)?.ids()?;
+		let set_skill_ids = self.table(SET_SKILLS)?.ids()?;
+		// The set bonus of an armor is the one of its set
+		let mut bonuses: HashMap<String, String> = HashMap::new();
+		if let Some(sets) = self.optional_table(SETS)? {
+			for row in sets.rows()? {
+				if let Some(bonus) = row.get("bonus") {
+					bonuses.insert(row.name_en().to_string(), bonus.to_string());
+				}
+			}
+		}
+		let table = self.table(ARMORS)?;
+		for row in table.rows()? {
+			let id = row.id;
+			let gender = Gender::new(row.flag("male", true, report), row.flag("female", true, report));
+			if gender.is_none() {
+				report.push(DataError::BadValue { table: "armor", id, column: "male, female", value: String::from("0, 0") });
+			}
+			let class = report.parse::<ArmorClass>("armor", id, "type", &row.get("type").unwrap_or_default().to_lowercase());
+			let rank = report.parse::<ArmorRank>("armor", id, "rank", &row.get("rank").unwrap_or_default().to_uppercase());
+			let (gender, class, rank) = match (gender, class, rank) {
+				(Some(gender), Some(class), Some(rank)) => (gender, class, rank),
+				_ => continue,
+			};
+			let slots = ["slot_1", "slot_2", "slot_3"].map(|column| row.number(column, report));
+			let defence = ["defense_base", "defense_max", "defense_augment_max"].map(|column| row.number(column, report));
+			let elements = ["defense_fire", "defense_water", "defense_thunder", "defense_ice", "defense_dragon"]
+				.map(|column| row.number(column, report));
+			let mut armor = Armor::new(id, self.name(&row, report), class, rank, gender, slots, defence, elements);
+			armor.skills = skills_level(&row, &SKILL_COLUMNS, skills, &skill_ids, report);
+			if let Some(bonus) = row.get("armorset").and_then(|set| bonuses.get(set)) {
+				if let Some(set_skill) = row.lookup(set_skills, &set_skill_ids, bonus, "armorset", "armorset_bonus", report) {
+					armor.add_setskill(set_skill);
+				}
+			}
+			armors.insert(Arc::new(armor));
+		}
+		report.expect_rows("armor", armors.len());
+		Ok(())
+	}
+
+	fn load_sets(&self, sets: &mut ArmorSets, armors: &Armors, set_skills: &SetSkills, report: &mut LoadReport) -> Result<(), DataError> {
+		let armor_ids = self.table(ARMORS)?.ids()?;
+		let set_skill_ids = self.table(SET_SKILLS)?.ids()?;
+		let table = self.table(SETS)?;
+		for row in table.rows()? {
+			let rank = match report.parse::<ArmorRank>("armorset", row.id, "rank", &row.get("rank").unwrap_or_default().to_uppercase()) {
+				Some(rank) => rank,
+				None => continue,
+			};
+			let set_skill = row.get("bonus")
+				.and_then(|bonus| row.lookup(set_skills, &set_skill_ids, bonus, "bonus", "armorset_bonus", report))
+				.map(Arc::clone);
+			let mut set = ArmorSet::new(row.id, self.name(&row, report), rank, set_skill);
+			for column in ["head", "chest", "arms", "waist", "legs"] {
+				if let Some(armor) = row.get(column).and_then(|name| row.lookup(armors, &armor_ids, name, column, "armor", report)) {
+					if let Err(taken) = set.add_armor(armor) {
+						report.push(DataError::SlotTaken { set: row.id, slot: armor.class.to_string(), armors: vec![taken, armor.id] });
+					}
+				}
+			}
+			sets.insert(Arc::new(set));
+		}
+		report.expect_rows("armorset", sets.len());
+		Ok(())
+	}
+
+	fn load_charms(&self, charms: &mut Charms, skills: &Skills, report: &mut LoadReport) -> Result<(), DataError> {
+		let skill_ids = self.table(SKILLS)?.ids()?;
+		let table = self.table(CHARMS)?;
+		let ids = table.ids()?;
+		for row in table.rows()? {
+			let mut charm = Charm::new(row.id, self.name(&row, report));
+			charm.skills = skills_level(&row, &SKILL_COLUMNS, skills, &skill_ids, report);
+			charm.previous_id = row.get("previous_en").and_then(|name| match ids.get(name) {
+				Some(previous_id) => Some(*previous_id),
+				None => {
+					report.push(DataError::BadValue { table: "charm", id: row.id, column: "previous_en", value: name.to_string() });
+					None
+				}
+			});
+			charms.insert(Arc::new(charm));
+		}
+		report.expect_rows("charm", charms.len());
+		Ok(())
+	}
+
+	fn load_decorations(&self, decorations: &mut Decorations, skills: &Skills, report: &mut LoadReport) -> Result<(), DataError> {
+		let skill_ids = self.table(SKILLS)?.ids()?;
+		let table = self.table(DECORATIONS)?;
+		for row in table.rows()? {
+			let size = row.number("slot", report);
+			let deco_skills = skills_level(&row, &SKILL_COLUMNS, skills, &skill_ids, report);
+			decorations.insert(Arc::new(Decoration::new(row.id, self.name(&row, report), size, deco_skills)));
+		}
+		report.expect_rows("decoration", decorations.len());
+		Ok(())
+	}
+
+	fn load_weapons(&self, weapons: &mut Weapons, skills: &Skills, set_skills: &SetSkills, report: &mut LoadReport) -> Result<(), DataError> {
+		let skill_ids = self.table(SKILLS)?.ids()?;
+		let set_skill_ids = self.table(SET_SKILLS)?.ids()?;
+		let mut sharpnesses = HashMap::new();
+		if let Some(table) = self.optional_table(SHARPNESS)? {
+			for row in table.rows()? {
+				let sharpness = SHARPNESS_COLUMNS.map(|column| row.number(column, report));
+				sharpnesses.insert(row.name_en().to_string(), sharpness);
+			}
+		}
+		// The bowguns refer to their ammo by key
+		let mut ammo = HashMap::new();
+		if let Some(table) = self.optional_table(AMMO)? {
+			let columns: Vec<String> = table.columns.iter().sorted_by_key(|(_, i)| **i).map(|(c, _)| c.clone()).collect();
+			for row in table.rows()? {
+				if let Some(key) = row.get("key") {
+					ammo.insert(key.to_string(), db::ammo_table(&columns, &|c| row.get(c).map(String::from)));
+				}
+			}
+		}
+		let table = self.table(WEAPONS)?;
+		let ids = table.ids()?;
+		for row in table.rows()? {
+			let id = row.id;
+			let class = match report.parse::<WeaponClass>("weapon", id, "weapon_type", row.get("weapon_type").unwrap_or_default()) {
+				Some(class) => class,
+				None => continue,
+			};
+			let previous_id = row.get("previous_en").and_then(|name| match ids.get(name) {
+				Some(previous_id) => Some(*previous_id),
+				None => {
+					report.push(DataError::BadValue { table: "weapon", id, column: "previous_en", value: name.to_string() });
+					None
+				}
+			});
+			let attack_true = match row.get("attack_true") {
+				Some(_) => row.number("attack_true", report),
+				None => (row.number::<f32>("attack", report) / bloat(class)).round() as u16,
+			};
+			let slots = ["slot_1", "slot_2", "slot_3"].map(|column| row.number(column, report));
+
+			// An unknown element is dropped, the weapon is still usable
+			let mut elements = Vec::new();
+			for (element_column, attack_column) in [("element1", "element1_attack"), ("element2", "element2_attack")] {
+				if let Some(e) = row.get(element_column) {
+					if let Some(element) = report.parse::<Element>("weapon", id, element_column, &e.to_lowercase()) {
+						elements.push((element, row.number(attack_column, report)));
+					}
+				}
+			}
+			elements.shrink_to_fit();
+			let elderseal = row.get("elderseal").map(str::to_lowercase);
+			let elderseal = match ElderSeal::new(elderseal.as_deref()) {
+				Some(elderseal) => elderseal,
+				None => {
+					report.push(DataError::BadValue { table: "weapon", id, column: "elderseal", value: elderseal.unwrap_or_default() });
+					ElderSeal::Empty
+				}
+			};
+			let set_skill = row.get("armorset_bonus")
+				.and_then(|bonus| row.lookup(set_skills, &set_skill_ids, bonus, "armorset_bonus", "armorset_bonus", report))
+				.map(Arc::clone);
+			let mut skill = SkillsLevel::new();
+			if let Some(s) = row.get("skill").and_then(|name| row.lookup(skills, &skill_ids, name, "skill", "skilltree", report)) {
+				skill.insert(SkillLevel::new(Arc::clone(s), 1));
+			}
+			let weapon_ammo = match row.get("ammo_config") {
+				Some(key) if class.has_ammo() => match ammo.get(key) {
+					Some(table) => Some(table.clone()),
+					None => {
+						report.push(DataError::BadValue { table: "weapon", id, column: "ammo_config", value: key.to_string() });
+						None
+					}
+				},
+				_ => None,
+			};
+
+			let mut weapon = Weapon::new(id, previous_id, class, self.name(&row, report),
+				attack_true, row.number("affinity", report), sharpnesses.get(row.name_en()).copied(), row.number("defense", report),
+				slots, elements, row.flag("element_hidden", false, report), elderseal,
+				set_skill, skill,
+			);
+			weapon.class_data = db::class_data(class, id, &|c| row.get(c).map(String::from), weapon_ammo, report);
+			weapons.insert(Arc::new(weapon));
+		}
+		weapons.shrink_to_fit();
+		report.expect_rows("weapon", weapons.len());
+		Ok(())
+	}
+
+	// Without the monster files there are no monsters, like a FileSource without monsters
+	fn load_monsters(&self, monsters: &mut Monsters, skills: &Skills, report: &mut LoadReport) -> Result<(), DataError> {
+		let table = match self.optional_table(MONSTERS)? {
+			Some(table) => table,
+			None => return Ok(()),
+		};
+		let english_ids = self.table(SKILLS)?.ids()?;
+		let weaknesses = self.optional_table(WEAKNESSES)?;
+		let weakness_rows = match &weaknesses {
+			Some(table) => table.rows()?,
+			None => Vec::new(),
+		};
+		let weakness_rows: HashMap<&str, &Row> = weakness_rows.iter()
+			.filter(|row| row.get("form").is_none_or(|form| form == "normal"))
+			.map(|row| (row.name_en(), row))
+			.collect();
+		for row in table.rows()? {
+			let mut monster = Monster::new(row.id, self.name(&row, report), row.get("size") != Some("small"));
+			let weakness = weakness_rows.get(row.name_en());
+			db::monster_data(&mut monster, &|column| match column.strip_prefix("weakness_") {
+				Some(element) => weakness.and_then(|w| w.get(element)).map(String::from),
+				None => row.get(column).map(String::from),
+			}, report);
+			monster.suggest_skills(skills, &english_ids);
+			monsters.insert(Arc::new(monster));
+		}
+		monsters.shrink_to_fit();
+		report.expect_rows("monster", monsters.len());
+		Ok(())
+	}
+
+	// Same placeholder as the SQL loader, the source files have no tools
+	fn load_tools(&self, tools: &mut Tools, _report: &mut LoadReport) -> Result<(), DataError> {
+		tools.insert(Arc::new(Tool::new(1, String::from("ToDo"), [2,2,1])));
+		Ok(())
+	}
+
+	fn load_damage_skills(&self, damage_skills: &mut DamageSkills, skills: &Skills, _report: &mut LoadReport) -> Result<(), DataError> {
+		resolve_damage_skills(damage_skills, skills, &self.table(SKILLS)?.ids()?);
+		Ok(())
+	}
+
+	// Without the item file there are no recipes. Like the SQL loader only the items used in a recipe are loaded
+	fn load_recipes(&self, materials: &mut Materials, recipes: &mut Recipes, report: &mut LoadReport) -> Result<(), DataError> {
+		let items = match self.optional_table(ITEMS)? {
+			Some(table) => table,
+			None => return Ok(()),
+		};
+		let item_rows = items.rows()?;
+		let item_rows: HashMap<&str, &Row> = item_rows.iter().map(|row| (row.name_en(), row)).collect();
+		for (file, targets) in [(ARMOR_RECIPES, ARMORS), (CHARM_RECIPES, CHARMS), (WEAPON_RECIPES, WEAPONS)] {
+			let table = match self.optional_table(file)? {
+				Some(table) => table,
+				None => continue,
+			};
+			let ids = self.table(targets)?.ids()?;
+			for row in table.rows()? {
+				let base = row.get("base_name_en").unwrap_or_default();
+				let id = match ids.get(base) {
+					Some(id) => *id,
+					None => {
+						report.push(DataError::BadValue { table: file.1, id: row.id, column: "base_name_en", value: base.to_string() });
+						continue;
+					}
+				};
+				let kind = match (targets.1, row.get("type").map(str::to_lowercase).as_deref()) {
+					("armor", _) => RecipeKind::Armor,
+					("charm", _) => RecipeKind::Charm,
+					(_, Some("create")) => RecipeKind::WeaponCreate,
+					(_, Some("upgrade")) => RecipeKind::WeaponUpgrade,
+					(_, kind) => {
+						report.push(DataError::BadValue { table: file.1, id: row.id, column: "type", value: kind.unwrap_or_default().to_string() });
+						continue;
+					}
+				};
+				let mut recipe = Vec::new();
+				for (name_column, quantity_column) in MATERIAL_COLUMNS {
+					let name = match row.get(name_column) {
+						Some(name) => name,
+						None => continue,
+					};
+					let item = match item_rows.get(name) {
+						Some(item) => item,
+						None => {
+							report.push(DataError::BadValue { table: file.1, id: row.id, column: name_column, value: name.to_string() });
+							continue;
+						}
+					};
+					if !materials.contains(item.id) {
+						materials.insert(Arc::new(Material::new(item.id, self.name(item, report), item.number("rarity", report))));
+					}
+					recipe.push((Arc::clone(materials.get(item.id).unwrap()), row.number(quantity_column, report)));
+				}
+				recipes.insert((kind, id), recipe);
+			}
+		}
+		Ok(())
+	}
+}
@@ -0,0 +1,190 @@
+use std::{
+	collections::HashMap,
+	fmt,
+	sync::Arc,
+};
+use serde::{Deserialize, Serialize};
+use strum::{Display, EnumIter, IntoEnumIterator};
+use crate::data::{
+	db_storage::Storage,
+	db_types::{
+		Element, ID, Item,
+		monster::{Hitzone, Monster},
+		skill::{Skill, SkillsLevel},
+		weapon::Weapon,
+		Skills,
+	},
+	mutable::equipment::Equipment,
+};
+
+/*
+Expected damage of a hit of motion value 100 on a body part, the average over the critical hits.
+Only the weapon and the skills below count: no item, food, song, status or element cap.
+*/
+
+// The skills the damage depends on, by English name
+#[derive(EnumIter, Display, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum DamageSkill {
+	#[strum(serialize = "Attack Boost")]
+	AttackBoost,
+	#[strum(serialize = "Critical Eye")]
+	CriticalEye,
+	#[strum(serialize = "Critical Boost")]
+	CriticalBoost,
+	#[strum(serialize = "Weakness Exploit")]
+	WeaknessExploit,
+	#[strum(serialize = "Fire Attack")]
+	FireAttack,
+	#[strum(serialize = "Water Attack")]
+	WaterAttack,
+	#[strum(serialize = "Thunder Attack")]
+	ThunderAttack,
+	#[strum(serialize = "Ice Attack")]
+	IceAttack,
+	#[strum(serialize = "Dragon Attack")]
+	DragonAttack,
+}
+
+// The skills of the storage found by English name, the missing ones never count
+pub type DamageSkills = HashMap<DamageSkill, Arc<Skill>>;
+
+pub(crate) fn resolve_damage_skills(damage_skills: &mut DamageSkills, skills: &Skills, english_ids: &HashMap<String, ID>) {
+	for which in DamageSkill::iter() {
+		if let Some(skill) = english_ids.get(&which.to_string()).and_then(|id| skills.get(*id)) {
+			damage_skills.insert(which, Arc::clone(skill));
+		}
+	}
+}
+
+// By level, as in Iceborne: (attack, attack percent, affinity)
+const ATTACK_BOOST: [(f32, f32, i16); 7] = [(3., 0., 0), (6., 0., 0), (9., 0., 0), (7., 5., 5), (8., 6., 5), (9., 8., 5), (10., 10., 5)];
+const CRITICAL_EYE: [i16; 7] = [5, 10, 15, 20, 25, 30, 40];
+const CRITICAL_BOOST: [f32; 4] = [1.25, 1.30, 1.35, 1.40];  // From level 0
+const WEAKNESS_EXPLOIT: [i16; 3] = [15, 30, 50];
+const WEAK_SPOT: u8 = 45;  // Weakness Exploit needs a raw hitzone at least this
+const ELEMENT_ATTACK: [(f32, f32); 6] = [(30., 0.), (60., 0.), (100., 0.), (100., 5.), (100., 10.), (100., 20.)];  // Displayed element
+const BAD_CRITICAL: f32 = 0.75;
+// By sharpness level, from red to purple
+const RAW_SHARPNESS: [f32; 7] = [0.5, 0.75, 1.0, 1.05, 1.2, 1.32, 1.39];
+const ELEMENT_SHARPNESS: [f32; 7] = [0.25, 0.5, 0.75, 1.0, 1.0625, 1.15, 1.25];
+
+#[derive(Clone, Copy, Default, Serialize)]
+pub struct Damage {
+	pub raw: f32,
+	pub element: f32,
+	pub affinity: i16,  // Capped to -100..=100
+}
+
+impl Damage {
+	pub fn total(&self) -> f32 {
+		self.raw + self.element
+	}
+}
+
+impl fmt::Display for Damage {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{:.1} per hit ({:.1} raw, {:.1} element, affinity {}%)", self.total(), self.raw, self.element, self.affinity)
+	}
+}
+
+fn level(skills: &SkillsLevel, damage_skills: &DamageSkills, which: DamageSkill) -> usize {
+	damage_skills.get(&which)
+		.and_then(|skill| skills.get_level(Arc::clone(skill)))
+		.unwrap_or(0) as usize
+}
+
+// The value of level in a table from level 1, the last one beyond it
+fn at<T: Copy + Default>(table: &[T], level: usize) -> T {
+	match level {
+		0 => T::default(),
+		_ => table[level.min(table.len()) - 1],
+	}
+}
+
+fn element_attack(element: Element) -> Option<DamageSkill> {
+	match element {
+		Element::Fire => Some(DamageSkill::FireAttack),
+		Element::Water => Some(DamageSkill::WaterAttack),
+		Element::Thunder => Some(DamageSkill::ThunderAttack),
+		Element::Ice => Some(DamageSkill::IceAttack),
+		Element::Dragon => Some(DamageSkill::DragonAttack),
+		_ => None,
+	}
+}
+
+// The modifiers of the best sharpness level, 1 for the ranged weapons
+fn sharpness(weapon: &Weapon) -> (f32, f32) {
+	match weapon.sharpness.and_then(|hits| hits.iter().rposition(|h| *h > 0)) {
+		Some(i) => (RAW_SHARPNESS[i], ELEMENT_SHARPNESS[i]),
+		None => (1., 1.),
+	}
+}
+
+// A hit of weapon with skills on hitzone. The hidden elements need Free Element, they do not count
+pub fn hit(weapon: &Weapon, skills: &SkillsLevel, damage_skills: &DamageSkills, hitzone: &Hitzone) -> Damage {
+	let level = |which| level(skills, damage_skills, which);
+	let raw_hitzone = hitzone.raw(weapon.class);
+	let (attack, percent, boost_affinity) = at(&ATTACK_BOOST, level(DamageSkill::AttackBoost));
+	let mut affinity = weapon.affinity as i16 + boost_affinity + at(&CRITICAL_EYE, level(DamageSkill::CriticalEye));
+	if raw_hitzone >= WEAK_SPOT {
+		affinity += at(&WEAKNESS_EXPLOIT, level(DamageSkill::WeaknessExploit));
+	}
+	let affinity = affinity.clamp(-100, 100);
+	let critical = if affinity >= 0 {
+		1. + affinity as f32 / 100. * (CRITICAL_BOOST[level(DamageSkill::CriticalBoost).min(CRITICAL_BOOST.len() - 1)] - 1.)
+	} else {
+		1. - (-affinity) as f32 / 100. * (1. - BAD_CRITICAL)
+	};
+	let (raw_sharpness, element_sharpness) = sharpness(weapon);
+	let true_attack = weapon.attack_true as f32 * (1. + percent / 100.) + attack;
+	let raw = true_attack * raw_sharpness * critical * raw_hitzone as f32 / 100.;
+
+	let element = if weapon.element_hidden {
+		0.
+	} else {
+		weapon.elements.iter()
+			.filter_map(|(element, value)| {
+				let (add, percent) = at(&ELEMENT_ATTACK, level(element_attack(*element)?));
+				let displayed = *value as f32 * (1. + percent / 100.) + add;
+				Some(displayed / 10. * element_sharpness * hitzone.element(*element) as f32 / 100.)
+			})
+			.fold(0., |sum, damage| sum + damage)  // The sum of nothing is -0
+	};
+	Damage { raw, element, affinity }
+}
+
+// A body part of a monster to hit, with the damage skills of the storage the equipments come from
+#[derive(Clone)]
+pub struct Target {
+	pub monster: Arc<Monster>,
+	hitzone: usize,  // Index in monster.hitzones
+	skills: DamageSkills,
+}
+
+impl Target {
+	// None when the monster has no such hitzone
+	pub fn new(storage: &Storage, monster: Arc<Monster>, hitzone: ID) -> Option<Self> {
+		let hitzone = monster.hitzones.iter().position(|h| h.id == hitzone)?;
+		Some(Target { monster, hitzone, skills: storage.damage_skills.clone() })
+	}
+
+	pub fn hitzone(&self) -> &Hitzone {
+		&self.monster.hitzones[self.hitzone]
+	}
+
+	pub fn skills(&self) -> impl Iterator<Item = &Arc<Skill>> {
+		self.skills.values()
+	}
+
+	// None without a weapon
+	pub fn damage(&self, equipment: &Equipment) -> Option<Damage> {
+		let weapon = equipment.weapon.as_ref()?;
+		Some(hit(&weapon.item, &equipment.get_skills(), &self.skills, self.hitzone()))
+	}
+}
+
+impl fmt::Display for Target {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{} {}", self.monster.name, self.hitzone().name)
+	}
+}
@@ -1,132 +1,405 @@
 use std::{
+	collections::HashMap,
+	env,
+	fmt,
+	path::{Path, PathBuf},
 	str::FromStr,
 	sync::Arc,
 };
-use rusqlite::{Connection, params, Row};
+use directories::ProjectDirs;
+use rusqlite::{Connection, params, Row, types::Value};
+use strum::EnumCount;
+use crate::data::{
+	damage::{DamageSkills, resolve_damage_skills},
+	source::DataSource,
+};
 use crate::data::db_types::{
 	*,
 	charm::Charm,
 	decoration::Decoration,
+	material::{Material, RecipeKind},
+	monster::{AILMENT_COLUMNS, Ailment, Hitzone, Monster},
 	skill::{SetSkill, Skill},
 	ArmorClass, ArmorRank, ElderSeal, Element, Gender, WeaponClass,
-	weapon::Weapon,
+	weapon::{Ammo, AmmoTable, ClassData, Weapon},
 	armor::{Armor, ArmorSet},
 	skill::{SkillLevel, SkillsLevel},
 	tool::Tool,
 };
 
+// Environment variable with the path of the database
+pub const DB_ENV: &str = "MHWISS_DB";
+const DB_FILE: &str = "mhw.db";
+// Names missing in the chosen language are taken from this one
+const FALLBACK_LANG: &str = "en";
+
+pub enum DbError {
+	NotFound(Vec<PathBuf>),  // Every path tried
+	Open(PathBuf, rusqlite::Error),
+}
+
+impl fmt::Display for DbError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			DbError::NotFound(paths) => {
+				write!(f, "Database {} not found, set {} to its path. Searched in:", DB_FILE, DB_ENV)?;
+				for path in paths {
+					write!(f, "\n\t{}", path.display())?;
+				}
+				Ok(())
+			}
+			DbError::Open(path, e) => write!(f, "{} is not a valid database: {}", path.display(), e),
+		}
+	}
+}
+
+/*
+Problems of the database content, or of any other DataSource.
+A loader returns the error when it can not go on (missing table or column, wrong column type),
+the other problems go in the LoadReport and the broken entity, or only the broken reference, is skipped.
+*/
+#[derive(Debug)]
+pub enum DataError {
+	Sql(rusqlite::Error),
+	MissingTable(String),
+	BadValue { table: &'static str, id: ID, column: &'static str, value: String },  // Unknown enum or unparsable value
+	Dangling { table: &'static str, id: ID, target: &'static str, target_id: ID },  // Foreign key to nothing
+	MissingTranslation { table: &'static str, id: ID, lang: String, english: bool },  // Loaded with the English name or a placeholder
+	Empty(&'static str),  // Nothing loaded from the table
+	File(PathBuf, String),  // Unreadable or invalid data file
+	SlotTaken { set: ID, slot: String, armors: Vec<ID> },  // More armors of a set for the same body part
+}
+
+impl fmt::Display for DataError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			DataError::Sql(e) => write!(f, "Database error: {}", e),
+			DataError::MissingTable(table) => write!(f, "Missing table {}", table),
+			DataError::BadValue { table, id, column, value } => write!(f, "{} {}: invalid {} \"{}\"", table, id, column, value),
+			DataError::Dangling { table, id, target, target_id } => write!(f, "{} {}: no {} with id {}", table, id, target, target_id),
+			DataError::MissingTranslation { table, id, lang, english: true } => write!(f, "{} {}: no name in language {}, English used", table, id, lang),
+			DataError::MissingTranslation { table, id, english: false, .. } => write!(f, "{} {}: no name, placeholder used", table, id),
+			DataError::Empty(table) => write!(f, "Nothing loaded from {}", table),
+			DataError::File(path, e) => write!(f, "{}: {}", path.display(), e),
+			DataError::SlotTaken { set, slot, armors } => write!(f, "armorset {}: armors {} all in the {} slot", set, armors.iter().map(ID::to_string).collect::<Vec<_>>().join(", "), slot),
+		}
+	}
+}
+
+impl From<rusqlite::Error> for DataError {
+	fn from(e: rusqlite::Error) -> Self {
+		if let rusqlite::Error::SqliteFailure(_, Some(message)) = &e {
+			if let Some(table) = message.strip_prefix("no such table: ") {
+				return DataError::MissingTable(table.to_string());
+			}
+		}
+		DataError::Sql(e)
+	}
+}
+
+// Data quality problems found while loading, nothing of it stops the application
+#[derive(Default)]
+pub struct LoadReport {
+	pub issues: Vec<DataError>,
+}
+
+impl LoadReport {
+	pub fn is_empty(&self) -> bool {
+		self.issues.is_empty()
+	}
+
+	// Entities loaded with the English name or a placeholder
+	pub fn fallbacks(&self) -> impl Iterator<Item = (&'static str, ID)> + '_ {
+		self.issues.iter().filter_map(|issue| match issue {
+			DataError::MissingTranslation { table, id, .. } => Some((*table, *id)),
+			_ => None,
+		})
+	}
+
+	pub(crate) fn push(&mut self, issue: DataError) {
+		self.issues.push(issue);
+	}
+
+	// found, or report that table id points to a missing target
+	pub(crate) fn reference<'a, T>(&mut self, found: Option<&'a Arc<T>>, table: &'static str, id: ID, target: &'static str, target_id: ID) -> Option<&'a Arc<T>> {
+		if found.is_none() {
+			self.push(DataError::Dangling { table, id, target, target_id });
+		}
+		found
+	}
+
+	pub(crate) fn parse<T: FromStr>(&mut self, table: &'static str, id: ID, column: &'static str, value: &str) -> Option<T> {
+		let ret = T::from_str(value).ok();
+		if ret.is_none() {
+			self.push(DataError::BadValue { table, id, column, value: value.to_string() });
+		}
+		ret
+	}
+
+	pub(crate) fn expect_rows(&mut self, table: &'static str, loaded: usize) {
+		if loaded == 0 {
+			self.push(DataError::Empty(table));
+		}
+	}
+}
+
+impl fmt::Display for LoadReport {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{} data issues", self.issues.len())?;
+		for issue in self.issues.iter() {
+			write!(f, "\n\t{}", issue)?;
+		}
+		Ok(())
+	}
+}
+
 pub struct DB {
 	connection: rusqlite::Connection,
+	path: PathBuf,
 	lang: Option<String>,
 }
 
 impl DB {
-	pub fn new() -> Self {
-		let conn = Connection::open_with_flags("MHWorldData/mhw.db", rusqlite::OpenFlags::SQLITE_OPEN_READ_ONLY).unwrap();
+	// First database found in the default locations
+	pub fn new() -> Result<Self, DbError> {
+		DB::locate(None)
+	}
+
+	pub fn with_language(lang: String) -> Result<Self, DbError> {
+		let mut db = DB::new()?;
+		db.set_language(lang);
+		Ok(db)
+	}
 
-		DB {
+	pub fn open(path: &Path) -> Result<Self, DbError> {
+		let conn = Connection::open_with_flags(path, rusqlite::OpenFlags::SQLITE_OPEN_READ_ONLY)
+			.map_err(|e| DbError::Open(path.to_path_buf(), e))?;
+		// SQLite opens any file, check that it is the MHWorldData one
+		conn.prepare("SELECT id, name FROM language;")
+			.map_err(|e| DbError::Open(path.to_path_buf(), e))?;
+		Ok(DB {
 			connection: conn,
+			path: path.to_path_buf(),
 			lang: None,
+		})
+	}
+
+	// Try configured (usually from the Settings) and then the default locations
+	pub fn locate(configured: Option<&Path>) -> Result<Self, DbError> {
+		let candidates = DB::candidates(configured);
+		for path in candidates.iter() {
+			if path.is_file() {
+				return DB::open(path);
+			}
 		}
+		Err(DbError::NotFound(candidates))
 	}
 
-	pub fn with_language(lang: String) -> Self {
-		let conn = Connection::open_with_flags("MHWorldData/mhw.db", rusqlite::OpenFlags::SQLITE_OPEN_READ_ONLY).unwrap();
+	// Where the database is searched, in order
+	pub fn candidates(configured: Option<&Path>) -> Vec<PathBuf> {
+		let mut ret = Vec::new();
+		if let Some(path) = configured {
+			ret.push(path.to_path_buf());
+		}
+		if let Some(path) = env::var_os(DB_ENV) {
+			ret.push(PathBuf::from(path));
+		}
+		ret.push(Path::new("MHWorldData").join(DB_FILE));
+		if let Some(dir) = env::current_exe().ok().as_ref().and_then(|exe| exe.parent()) {
+			ret.push(dir.join("MHWorldData").join(DB_FILE));
+			// Run from target/<profile> inside the repository
+			if let Some(root) = dir.parent().and_then(|d| d.parent()) {
+				ret.push(root.join("MHWorldData").join(DB_FILE));
+			}
+		}
+		if let Some(proj_dirs) = ProjectDirs::from("org", "SilverCorp", "mhwiss") {
+			ret.push(proj_dirs.data_dir().join(DB_FILE));
+		}
+		ret
+	}
 
-		DB {
-			connection: conn,
-			lang: Some(lang),
+	pub fn path(&self) -> &Path {
+		&self.path
+	}
+
+	pub(crate) fn connection(&self) -> &Connection {
+		&self.connection
+	}
+
+	// The ammo tables of the bowguns by id, every <ammo>_clip column is an ammo type
+	fn load_ammo(&self) -> Result<HashMap<ID, AmmoTable>, DataError> {
+		let mut statement = self.connection.prepare("SELECT * FROM weapon_ammo;")?;
+		let names: Vec<String> = statement.column_names().into_iter().map(String::from).collect();
+		let mut rows = statement.query([])?;
+		let mut ret = HashMap::new();
+		while let Some(row) = rows.next()? {
+			let mut values = HashMap::new();
+			for name in names.iter() {
+				values.insert(name.as_str(), text(row, name)?);
+			}
+			let id: ID = row.get("id")?;
+			ret.insert(id, ammo_table(&names, &|column| values.get(column).cloned().flatten()));
 		}
+		Ok(ret)
 	}
 
-	pub fn get_available_languages(&self) -> Vec<(String, String)> {
+	pub fn get_available_languages(&self) -> Result<Vec<(String, String)>, DataError> {
 		let mut statement = self.connection.prepare(
-			"SELECT id, name FROM language;").unwrap();
-		let mut rows = statement.query(params![]).unwrap();
+			"SELECT id, name FROM language;")?;
+		let mut rows = statement.query(params![])?;
 		let mut ret = Vec::new();
-		while let Some(row) = rows.next().unwrap() {
-			let id: String = row.get("id").unwrap();
-			let name: String = row.get("name").unwrap();
+		while let Some(row) = rows.next()? {
+			let id: String = row.get("id")?;
+			let name: String = row.get("name")?;
 			ret.push((id, name));
 		}
-		ret
+		Ok(ret)
 	}
 
 	pub fn set_language(&mut self, lang: String) {
 		self.lang = Some(lang);
 	}
 
-	pub fn load_skills(&self, skills: &mut Skills) {
+	pub fn get_language(&self) -> Option<&str> {
+		self.lang.as_deref()
+	}
+
+	// Names of every id of table in the current language, with the same fallbacks of the loaders
+	pub(crate) fn load_names(&self, kind: &'static str, table: &str, id: &str, text_table: &str, report: &mut LoadReport) -> Result<HashMap<ID, String>, DataError> {
+		let mut statement = self.connection.prepare(&format!(
+			"SELECT DISTINCT x.{id} AS id, t.name, en.name AS name_en
+FROM {table} AS x
+LEFT JOIN {text_table} AS t ON t.id = x.{id} AND t.lang_id = ?1
+LEFT JOIN {text_table} AS en ON en.id = x.{id} AND en.lang_id = ?2;"))?;
+		let mut rows = statement.query(params![&self.lang, FALLBACK_LANG])?;
+		let mut ret = HashMap::new();
+		while let Some(row) = rows.next()? {
+			let id = row.get("id")?;
+			ret.insert(id, localized_name(row, kind, id, &self.lang, report)?);
+		}
+		Ok(ret)
+	}
+
+	// Only the names really in lang, no fallback
+	pub(crate) fn load_translations(&self, lang: &str, text_table: &str) -> Result<HashMap<ID, String>, DataError> {
+		let mut statement = self.connection.prepare(&format!(
+			"SELECT id, name FROM {text_table} WHERE lang_id = ?1;"))?;
+		let mut rows = statement.query(params![lang])?;
+		let mut ret = HashMap::new();
+		while let Some(row) = rows.next()? {
+			ret.insert(row.get("id")?, row.get("name")?);
+		}
+		Ok(ret)
+	}
+
+	// The skills found by English name, whatever the language
+	fn english_skill_ids(&self) -> Result<HashMap<String, ID>, DataError> {
+		Ok(self.load_translations(FALLBACK_LANG, "skilltree_text")?
+			.into_iter()
+			.map(|(id, name)| (name, id))
+			.collect())
+	}
+
+	// The hitzones by monster id
+	fn load_hitzones(&self, report: &mut LoadReport) -> Result<HashMap<ID, Vec<Hitzone>>, DataError> {
 		let mut statement = self.connection.prepare(
-			"SELECT s.id, max_level, secret, unlocks_id, name, description
+			"SELECT h.*, t.name, en.name AS name_en
+		FROM monster_hitzone h
+		LEFT JOIN monster_hitzone_text t ON h.id = t.id AND t.lang_id = ?1
+		LEFT JOIN monster_hitzone_text en ON h.id = en.id AND en.lang_id = ?2
+		ORDER BY h.monster_id, h.id;")?;
+		let mut rows = statement.query(params![&self.lang, FALLBACK_LANG])?;
+		let mut ret: HashMap<ID, Vec<Hitzone>> = HashMap::new();
+		while let Some(row) = rows.next()? {
+			let id = row.get("id")?;
+			let mut hitzone = Hitzone::new(id, localized_name(row, "monster_hitzone", id, &self.lang, report)?);
+			hitzone_data(&mut hitzone, &|column| text(row, column).ok().flatten(), report);
+			ret.entry(row.get("monster_id")?).or_default().push(hitzone);
+		}
+		Ok(ret)
+	}
+
+	pub(crate) fn load_skill_descriptions(&self) -> Result<HashMap<ID, String>, DataError> {
+		let mut statement = self.connection.prepare(
+			"SELECT s.id, COALESCE(t.description, en.description, '') AS description
 FROM skilltree AS s
-JOIN skilltree_text ON skilltree_text.id = s.id
-WHERE skilltree_text.lang_id = ?1
-ORDER BY unlocks_id;").unwrap();
-		let mut rows = statement.query(params![&self.lang]).unwrap();
-
-		while let Some(row) = rows.next().unwrap() {
-			let unlock_id = row.get("unlocks_id").unwrap();
-			let unlock_skill = {
-				if let Some(id) = unlock_id {
-					Some(Arc::clone(get_skill_by_id(skills, id).unwrap()))
-				} else {
-					None
-				}
-			};
-			let id = row.get("id").unwrap();
+LEFT JOIN skilltree_text AS t ON t.id = s.id AND t.lang_id = ?1
+LEFT JOIN skilltree_text AS en ON en.id = s.id AND en.lang_id = ?2;")?;
+		let mut rows = statement.query(params![&self.lang, FALLBACK_LANG])?;
+		let mut ret = HashMap::new();
+		while let Some(row) = rows.next()? {
+			ret.insert(row.get("id")?, row.get("description")?);
+		}
+		Ok(ret)
+	}
+}
+
+// The MHWorldData schema
+impl DataSource for DB {
+	fn load_skills(&self, skills: &mut Skills, report: &mut LoadReport) -> Result<(), DataError> {
+		let mut statement = self.connection.prepare(
+			"SELECT s.id, max_level, secret, unlocks_id,
+	t.name, en.name AS name_en, COALESCE(t.description, en.description, '') AS description
+FROM skilltree AS s
+LEFT JOIN skilltree_text AS t ON t.id = s.id AND t.lang_id = ?1
+LEFT JOIN skilltree_text AS en ON en.id = s.id AND en.lang_id = ?2
+ORDER BY unlocks_id;")?;
+		let mut rows = statement.query(params![&self.lang, FALLBACK_LANG])?;
+
+		while let Some(row) = rows.next()? {
+			let id = row.get("id")?;
+			let name = localized_name(row, "skilltree", id, &self.lang, report)?;
+			let unlock_id: Option<ID> = row.get("unlocks_id")?;
+			let unlock_skill = unlock_id
+				.and_then(|unlock| report.reference(skills.get(unlock), "skilltree", id, "skilltree", unlock))
+				.map(Arc::clone);
 			let skill = Skill::new(
 				id,
-				row.get("name").unwrap(),
-				row.get("description").unwrap(),
-				row.get("max_level").unwrap(),
-				row.get("secret").unwrap(),
+				name,
+				row.get("description")?,
+				row.get("max_level")?,
+				row.get("secret")?,
 				unlock_skill,
 			);
 			skills.insert(Arc::new(skill));
 		}
+		report.expect_rows("skilltree", skills.len());
+		Ok(())
 	}
 
-	pub fn load_set_skills(&self, setskills: &mut SetSkills, skills: &Skills) {
+	fn load_set_skills(&self, setskills: &mut SetSkills, skills: &Skills, report: &mut LoadReport) -> Result<(), DataError> {
 		let mut statement = self.connection.prepare(
-			"SELECT setbonus_id, skilltree_id, required, name
+			"SELECT setbonus_id, skilltree_id, required, t.name, en.name AS name_en
 		FROM armorset_bonus_skill AS abs
-		JOIN armorset_bonus_text AS abt ON abs.setbonus_id = abt.id
-		WHERE lang_id = ?1
-		ORDER BY setbonus_id;").unwrap();
-		let mut rows = statement.query(params![&self.lang]).unwrap();
-
-		fn new_setskill(row: &Row, skills: &Skills) -> SetSkill {
-			let id = row.get("setbonus_id").unwrap();
-			let skill_id = row.get("skilltree_id").unwrap();
-			let req = row.get("required").unwrap();
-			let name = row.get("name").unwrap();
-			let mut tmp = SetSkill::new(id, name);
-			tmp.add_skill( get_skill_by_id(skills, skill_id).unwrap(), req);
-			tmp
-		}
-
-		let row = rows.next().unwrap().unwrap();
-		let mut id;
-		let mut setskill = new_setskill(row, skills);
-
-		while let Some(row) = rows.next().unwrap() {
-			id = row.get("setbonus_id").unwrap();
-			if setskill.id == id {
-				let skill_id = row.get("skilltree_id").unwrap();
-				let req = row.get("required").unwrap();
-				setskill.add_skill(get_skill_by_id(skills, skill_id).unwrap(), req);
-			} else {
-				setskills.insert(Arc::new(setskill));
-				setskill = new_setskill(row, skills);
+		LEFT JOIN armorset_bonus_text AS t ON abs.setbonus_id = t.id AND t.lang_id = ?1
+		LEFT JOIN armorset_bonus_text AS en ON abs.setbonus_id = en.id AND en.lang_id = ?2
+		ORDER BY setbonus_id;")?;
+		let mut rows = statement.query(params![&self.lang, FALLBACK_LANG])?;
+
+		// One row for each skill of the set skill
+		let mut setskill: Option<SetSkill> = None;
+		while let Some(row) = rows.next()? {
+			let id: ID = row.get("setbonus_id")?;
+			if setskill.as_ref().is_none_or(|s| s.id != id) {
+				if let Some(done) = setskill.replace(SetSkill::new(id, localized_name(row, "armorset_bonus", id, &self.lang, report)?)) {
+					setskills.insert(Arc::new(done));
+				}
 			}
+			let skill_id = row.get("skilltree_id")?;
+			if let Some(skill) = report.reference(skills.get(skill_id), "armorset_bonus_skill", id, "skilltree", skill_id) {
+				setskill.as_mut().unwrap().add_skill(skill, row.get("required")?);
+			}
+		}
+		if let Some(done) = setskill {
+			setskills.insert(Arc::new(done));
 		}
-		setskills.insert(Arc::new(setskill));
+		report.expect_rows("armorset_bonus_skill", setskills.len());
+		Ok(())
 	}
 
-	pub fn load_armors(&self, armors: &mut Armors, skills: &Skills, setskills: &SetSkills) {
+	fn load_armors(&self, armors: &mut Armors, skills: &Skills, setskills: &SetSkills, report: &mut LoadReport) -> Result<(), DataError> {
 		let mut statement = self.connection.prepare(
-			"SELECT armor.id, name, rank, armor_type,
+			"SELECT armor.id, t.name, en.name AS name_en, rank, armor_type,
 					armorset_id, armorset_bonus_id,
 					male, female,
 					slot_1, slot_2, slot_3,
@@ -134,31 +407,41 @@ ORDER BY unlocks_id;").unwrap();
 					skilltree_id, level
 				FROM armor
 					LEFT JOIN armor_skill ON armor_skill.armor_id == armor.id
-					JOIN      armor_text ON armor.id = armor_text.id
-				WHERE lang_id =  ?1;").unwrap();
-		let mut rows = statement.query(params![&self.lang]).unwrap();
-
-		fn new_armor(row: &Row, skills: &Skills, setskills: &SetSkills) -> Armor {
-			let slots = [row.get("slot_1").unwrap(),
-				row.get("slot_2").unwrap(),
-				row.get("slot_3").unwrap()];
-			let defence = [row.get("defense_base").unwrap(),
-				row.get("defense_max").unwrap(),
-				row.get("defense_augment_max").unwrap()];
-			let elements = [row.get("fire").unwrap(),
-				row.get("water").unwrap(),
-				row.get("thunder").unwrap(),
-				row.get("ice").unwrap(),
-				row.get("dragon").unwrap(),
+					LEFT JOIN armor_text AS t ON armor.id = t.id AND t.lang_id = ?1
+					LEFT JOIN armor_text AS en ON armor.id = en.id AND en.lang_id = ?2
+				ORDER BY armor.id;")?;
+		let mut rows = statement.query(params![&self.lang, FALLBACK_LANG])?;
+
+		// None if the armor is broken, the problem is in the report
+		fn new_armor(row: &Row, setskills: &SetSkills, lang: &Option<String>, report: &mut LoadReport) -> Result<Option<Armor>, DataError> {
+			let id = row.get("id")?;
+			let slots = [row.get("slot_1")?,
+				row.get("slot_2")?,
+				row.get("slot_3")?];
+			let defence = [row.get("defense_base")?,
+				row.get("defense_max")?,
+				row.get("defense_augment_max")?];
+			let elements = [row.get("fire")?,
+				row.get("water")?,
+				row.get("thunder")?,
+				row.get("ice")?,
+				row.get("dragon")?,
 			];
-			let gender = Gender::new(row.get("male").unwrap(), row.get("female").unwrap());
-			let str : String = row.get("armor_type").unwrap();
-			let class = ArmorClass::from_str(&str).expect("Parse error");
-			let str : String = row.get("rank").unwrap();
-			let rank = ArmorRank::from_str(&str).expect("Parse error");
+			let male: bool = row.get("male")?;
+			let female: bool = row.get("female")?;
+			let gender = Gender::new(male, female);
+			if gender.is_none() {
+				report.push(DataError::BadValue { table: "armor", id, column: "male, female", value: String::from("0, 0") });
+			}
+			let class = report.parse::<ArmorClass>("armor", id, "armor_type", &row.get::<_, String>("armor_type")?);
+			let rank = report.parse::<ArmorRank>("armor", id, "rank", &row.get::<_, String>("rank")?);
+			let (gender, class, rank) = match (gender, class, rank) {
+				(Some(gender), Some(class), Some(rank)) => (gender, class, rank),
+				_ => return Ok(None),
+			};
 			let mut armor = Armor::new(
-				row.get("id").unwrap(),
-				row.get("name").unwrap(),
+				id,
+				localized_name(row, "armor", id, lang, report)?,
 				class,
 				rank,
 				gender,
@@ -166,242 +449,485 @@ ORDER BY unlocks_id;").unwrap();
 				defence,
 				elements,
 			);
-			let skill_id: Option<ID> = row.get("skilltree_id").unwrap();
-			if let Some(id) = skill_id {
-				let skill_lev = row.get("level").unwrap();
-				armor.add_skill(get_skill_by_id(skills, id).unwrap(), skill_lev);
+			let setskill_id: Option<ID> = row.get("armorset_bonus_id")?;
+			if let Some(setskill_id) = setskill_id {
+				if let Some(setskill) = report.reference(setskills.get(setskill_id), "armor", id, "armorset_bonus", setskill_id) {
+					armor.add_setskill(setskill);
+				}
 			}
-			let setskill_id: Option<ID> = row.get("armorset_bonus_id").unwrap();
-			if let Some(id) = setskill_id {
-				armor.add_setskill(get_set_skill_by_id(setskills, id).unwrap());
+			Ok(Some(armor))
+		}
+
+		// One row for each skill of the armor, armors without skills have one row with a NULL skill
+		let mut current: Option<(ID, Option<Armor>)> = None;
+		while let Some(row) = rows.next()? {
+			let id: ID = row.get("id")?;
+			if current.as_ref().is_none_or(|(current_id, _)| *current_id != id) {
+				if let Some((_, Some(mut done))) = current.replace((id, new_armor(row, setskills, &self.lang, report)?)) {
+					done.skills.shrink_to_fit();
+					armors.insert(Arc::new(done));
+				}
 			}
-			armor
-		}
-
-		let row = rows.next().unwrap().unwrap();
-		let mut armor = new_armor(row, &skills, setskills);
-		let mut id;
-
-		while let Some(row) = rows.next().unwrap() {
-			id = row.get("id").unwrap();
-			if armor.id == id {
-				let skill_id = row.get("skilltree_id").unwrap();
-				let skill_lev = row.get("level").unwrap();
-				armor.add_skill(get_skill_by_id(skills, skill_id).unwrap(), skill_lev);
-			} else {
-				armor.skills.shrink_to_fit();
-				armors.insert(Arc::new(armor));
-				armor = new_armor(row, &skills, setskills);
+			let skill_id: Option<ID> = row.get("skilltree_id")?;
+			if let (Some((_, Some(armor))), Some(skill_id)) = (current.as_mut(), skill_id) {
+				if let Some(skill) = report.reference(skills.get(skill_id), "armor", id, "skilltree", skill_id) {
+					armor.add_skill(skill, row.get("level")?);
+				}
 			}
 		}
-		armor.skills.shrink_to_fit();
-		armors.insert(Arc::new(armor));
+		if let Some((_, Some(mut done))) = current {
+			done.skills.shrink_to_fit();
+			armors.insert(Arc::new(done));
+		}
+		report.expect_rows("armor", armors.len());
+		Ok(())
 	}
 
-	pub fn load_sets(&self, sets: &mut ArmorSets, armors: &Armors, set_skills: &SetSkills) {
+	fn load_sets(&self, sets: &mut ArmorSets, armors: &Armors, set_skills: &SetSkills, report: &mut LoadReport) -> Result<(), DataError> {
 		let mut statement = self.connection.prepare(
-			"SELECT armorset.id AS armorset_id, armor.id AS armor_id, armorset_text.name, armorset.rank, armor.armorset_bonus_id
+			"SELECT armorset.id AS armorset_id, armor.id AS armor_id, t.name, en.name AS name_en, armorset.rank, armor.armorset_bonus_id
 FROM armorset
 	 JOIN armor ON armorset.id == armor.armorset_id
-	 JOIN armorset_text ON armorset_text.id == armorset.id
-WHERE armorset_text.lang_id = ?1;").unwrap();
-		let mut rows = statement.query(params![&self.lang]).unwrap();
-
-		fn new_set(row: &Row, armors: &Armors, set_skills: &SetSkills) -> ArmorSet {
-			let id = row.get("armorset_id").unwrap();
-			let armor_id = row.get("armor_id").unwrap();
-			let name = row.get("name").unwrap();
-			let tmp:String = row.get("rank").unwrap();
-			let rank: ArmorRank = ArmorRank::from_str(&tmp).expect("Parse Error");
-			let skill = {
-				if let Some(id) = row.get("armorset_bonus_id").unwrap() {
-					Some(Arc::clone(get_set_skill_by_id(set_skills, id).unwrap()))
-				} else { None }
+	 LEFT JOIN armorset_text AS t ON t.id == armorset.id AND t.lang_id = ?1
+	 LEFT JOIN armorset_text AS en ON en.id == armorset.id AND en.lang_id = ?2
+ORDER BY armorset.id;")?;
+		let mut rows = statement.query(params![&self.lang, FALLBACK_LANG])?;
+
+		fn new_set(row: &Row, set_skills: &SetSkills, lang: &Option<String>, report: &mut LoadReport) -> Result<Option<ArmorSet>, DataError> {
+			let id = row.get("armorset_id")?;
+			let name = localized_name(row, "armorset", id, lang, report)?;
+			let rank = match report.parse::<ArmorRank>("armorset", id, "rank", &row.get::<_, String>("rank")?) {
+				Some(rank) => rank,
+				None => return Ok(None),
 			};
-			let mut set = ArmorSet::new(id, name, rank, skill);
-			set.add_armor(get_armor_by_id(armors, armor_id).unwrap());
-			set
-		}
-
-		let row = rows.next().unwrap().unwrap();
-		let mut id ;
-		let mut set = new_set(row, &armors, &set_skills);
-
-		while let Some(row) = rows.next().unwrap() {
-			id = row.get("armorset_id").unwrap();
-			if set.id == id {
-				let armor_id = row.get("armor_id").unwrap();
-				set.add_armor(get_armor_by_id(armors, armor_id).unwrap());
-			} else {
-				sets.insert( Arc::new(set));
-				set = new_set(row, &armors, &set_skills);
+			let skill_id: Option<ID> = row.get("armorset_bonus_id")?;
+			let skill = skill_id
+				.and_then(|skill_id| report.reference(set_skills.get(skill_id), "armorset", id, "armorset_bonus", skill_id))
+				.map(Arc::clone);
+			Ok(Some(ArmorSet::new(id, name, rank, skill)))
+		}
+
+		// One row for each armor of the set
+		let mut current: Option<(ID, Option<ArmorSet>)> = None;
+		while let Some(row) = rows.next()? {
+			let id: ID = row.get("armorset_id")?;
+			if current.as_ref().is_none_or(|(current_id, _)| *current_id != id) {
+				if let Some((_, Some(done))) = current.replace((id, new_set(row, set_skills, &self.lang, report)?)) {
+					sets.insert(Arc::new(done));
+				}
 			}
+			let armor_id = row.get("armor_id")?;
+			if let Some((_, Some(set))) = current.as_mut() {
+				if let Some(armor) = report.reference(armors.get(armor_id), "armorset", id, "armor", armor_id) {
+					if let Err(taken) = set.add_armor(armor) {
+						report.push(DataError::SlotTaken { set: id, slot: armor.class.to_string(), armors: vec![taken, armor_id] });
+					}
+				}
+			}
+		}
+		if let Some((_, Some(done))) = current {
+			sets.insert(Arc::new(done));
 		}
-		sets.insert(Arc::new(set));
+		report.expect_rows("armorset", sets.len());
+		Ok(())
 	}
 
-	pub fn load_decorations(&self, decorations: &mut Decorations, skills: &Skills) {
+	fn load_decorations(&self, decorations: &mut Decorations, skills: &Skills, report: &mut LoadReport) -> Result<(), DataError> {
 		let mut statement = self.connection.prepare(
-			"SELECT decoration.id, name, slot, skilltree_id, skilltree_level, skilltree2_id, skilltree2_level
+			"SELECT decoration.id, t.name, en.name AS name_en, slot, skilltree_id, skilltree_level, skilltree2_id, skilltree2_level
 				 FROM decoration
-				 JOIN decoration_text ON decoration_text.id == decoration.id
-				 WHERE lang_id == ?1").unwrap();
-		let mut rows = statement.query(params![&self.lang]).unwrap();
+				 LEFT JOIN decoration_text AS t ON t.id == decoration.id AND t.lang_id == ?1
+				 LEFT JOIN decoration_text AS en ON en.id == decoration.id AND en.lang_id == ?2")?;
+		let mut rows = statement.query(params![&self.lang, FALLBACK_LANG])?;
 
-		while let Some(row) = rows.next().unwrap() {
+		while let Some(row) = rows.next()? {
+			let id = row.get("id")?;
 			let mut deco_skills = SkillsLevel::new();
-
-			let skill_id = row.get("skilltree_id").unwrap();
-			let skill = get_skill_by_id(skills, skill_id).unwrap();
-			let level = row.get("skilltree_level").unwrap();
-			deco_skills.insert(SkillLevel::new(Arc::clone(skill), level));
-
-			if let Some(skill_id) = row.get("skilltree2_id").unwrap() {
-				let skill = get_skill_by_id(skills, skill_id).unwrap();
-				let level = row.get("skilltree2_level").unwrap();
-				deco_skills.insert(SkillLevel::new(Arc::clone(skill), level));
+			// Like the other items, a dangling skill is skipped and the decoration is kept
+			for (skill_column, level_column) in [("skilltree_id", "skilltree_level"), ("skilltree2_id", "skilltree2_level")] {
+				let skill_id: Option<ID> = row.get(skill_column)?;
+				if let Some(skill_id) = skill_id {
+					if let Some(skill) = report.reference(skills.get(skill_id), "decoration", id, "skilltree", skill_id) {
+						deco_skills.insert(SkillLevel::new(Arc::clone(skill), row.get(level_column)?));
+					}
+				}
 			}
 
-			let id = row.get("id").unwrap();
 			decorations.insert(Arc::new(Decoration::new(
 				id,
-				row.get("name").unwrap(),
-				row.get("slot").unwrap(),
+				localized_name(row, "decoration", id, &self.lang, report)?,
+				row.get("slot")?,
 				deco_skills,
 			)));
 		}
+		report.expect_rows("decoration", decorations.len());
+		Ok(())
 	}
 
-	pub fn load_charms(&self, charms: &mut Charms, skills: &Skills) {
+	fn load_charms(&self, charms: &mut Charms, skills: &Skills, report: &mut LoadReport) -> Result<(), DataError> {
 		let mut statement = self.connection.prepare(
-			"SELECT charm.id, charm.previous_id, skilltree_id, level, name
+			"SELECT charm.id, charm.previous_id, skilltree_id, level, t.name, en.name AS name_en
 FROM charm
 JOIN charm_skill cs on charm.id = cs.charm_id
-JOIN charm_text ct on charm.id = ct.id
-WHERE lang_id = ?1").unwrap();
-		let mut rows = statement.query(params![&self.lang]).unwrap();
-
-
-		fn new_charm(row: &Row, skills: &Skills) -> Charm {
-			let id = row.get("id").unwrap();
-			let skill_id = row.get("skilltree_id").unwrap();
-			let skill_lev = row.get("level").unwrap();
-			let mut charm = Charm::new(
-				id,
-				row.get("name").unwrap(),
-			);
-			charm.add_skill(get_skill_by_id(skills, skill_id).unwrap(), skill_lev);
-			charm
-		}
-
-		let row = rows.next().unwrap().unwrap();
-		let mut charm = new_charm(row, &skills);
-		let mut id ;
-		while let Some(row) = rows.next().unwrap() {
-			id = row.get("id").unwrap();
-			if charm.id == id {
-				let skill_id = row.get("skilltree_id").unwrap();
-				let skill_lev = row.get("level").unwrap();
-				charm.add_skill(get_skill_by_id(skills, skill_id).unwrap(), skill_lev);
-			} else {
-				charms.insert(Arc::new(charm));
-				charm = new_charm(row, &skills);
+LEFT JOIN charm_text t on charm.id = t.id AND t.lang_id = ?1
+LEFT JOIN charm_text en on charm.id = en.id AND en.lang_id = ?2
+ORDER BY charm.id")?;
+		let mut rows = statement.query(params![&self.lang, FALLBACK_LANG])?;
+
+		// One row for each skill of the charm
+		let mut charm: Option<Charm> = None;
+		while let Some(row) = rows.next()? {
+			let id: ID = row.get("id")?;
+			if charm.as_ref().is_none_or(|c| c.id != id) {
+				let mut new = Charm::new(id, localized_name(row, "charm", id, &self.lang, report)?);
+				new.previous_id = row.get("previous_id")?;
+				if let Some(done) = charm.replace(new) {
+					charms.insert(Arc::new(done));
+				}
 			}
+			let skill_id = row.get("skilltree_id")?;
+			if let Some(skill) = report.reference(skills.get(skill_id), "charm", id, "skilltree", skill_id) {
+				charm.as_mut().unwrap().add_skill(skill, row.get("level")?);
+			}
+		}
+		if let Some(done) = charm {
+			charms.insert(Arc::new(done));
 		}
-		charms.insert( Arc::new(charm));
+		report.expect_rows("charm", charms.len());
+		Ok(())
 	}
 
-	pub fn load_weapons(&self, weapons: &mut Weapons, skills: &Skills, set_skills: &SetSkills) {
+	fn load_weapons(&self, weapons: &mut Weapons, skills: &Skills, set_skills: &SetSkills, report: &mut LoadReport) -> Result<(), DataError> {
 		let mut statement = self.connection.prepare(
-			"SELECT weapon.id, previous_weapon_id, weapon_type, name,
+			"SELECT weapon.id, previous_weapon_id, weapon_type, t.name, en.name AS name_en,
 		attack_true, affinity, sharpness, defense,
 		slot_1, slot_2, slot_3,
 		element1, element1_attack, element2, element2_attack, element_hidden, elderseal,
-		armorset_bonus_id, skilltree_id
+		phial, phial_power, shelling, shelling_level, kinsect_bonus, notes,
+		coating_close, coating_power, coating_paralysis, coating_poison, coating_sleep, coating_blast, ammo_id,
+		armorset_bonus_id, skilltree_id, ws.level
 		FROM weapon
 		LEFT JOIN weapon_skill ws ON weapon.id = ws.weapon_id
-		LEFT JOIN weapon_text wt ON weapon.id = wt.id
-		WHERE lang_id = ?1;").unwrap();
-		let mut rows = statement.query(params![&self.lang]).unwrap();
-
-		while let Some(row) = rows.next().unwrap() {
-			let id = row.get("id").unwrap();
-			let prev = row.get("previous_weapon_id").unwrap();
-			let tmp:String = row.get("weapon_type").unwrap();
-			let class = WeaponClass::from_str(&tmp).expect("Parse Error");
-			let name = row.get("name").unwrap();
-			let affinity = row.get("affinity").unwrap();
-			let attack = row.get("attack_true").unwrap();
-			let defense = row.get("defense").unwrap();
-			let slots = [row.get("slot_1").unwrap(),
-				row.get("slot_2").unwrap(),
-				row.get("slot_3").unwrap()];
+		LEFT JOIN weapon_text t ON weapon.id = t.id AND t.lang_id = ?1
+		LEFT JOIN weapon_text en ON weapon.id = en.id AND en.lang_id = ?2
+		ORDER BY weapon.id;")?;
+		let mut rows = statement.query(params![&self.lang, FALLBACK_LANG])?;
+		let ammo = self.load_ammo()?;
+
+		// None if the weapon is broken, the problem is in the report
+		fn new_weapon(row: &Row, set_skills: &SetSkills, ammo: &HashMap<ID, AmmoTable>, lang: &Option<String>, report: &mut LoadReport) -> Result<Option<Weapon>, DataError> {
+			let id = row.get("id")?;
+			let prev = row.get("previous_weapon_id")?;
+			let class = match report.parse::<WeaponClass>("weapon", id, "weapon_type", &row.get::<_, String>("weapon_type")?) {
+				Some(class) => class,
+				None => return Ok(None),
+			};
+			let name = localized_name(row, "weapon", id, lang, report)?;
+			let affinity = row.get("affinity")?;
+			let attack = row.get("attack_true")?;
+			let defense = row.get("defense")?;
+			let slots = [row.get("slot_1")?,
+				row.get("slot_2")?,
+				row.get("slot_3")?];
 
 			let sharpness = {
-				let tmp: Option<String> = row.get("sharpness").unwrap();
-				if let Some(s) = tmp {
-					let s: String = s;
-					let mut sharp = [0u8; 7];
-					let temp = s.as_str().split(',');
-					for (i, n) in temp.enumerate() {
-						sharp[i] = n.parse::<u8>().unwrap();
+				let tmp: Option<String> = row.get("sharpness")?;
+				tmp.and_then(|s| {
+					let sharp = parse_sharpness(&s);
+					if sharp.is_none() {
+						report.push(DataError::BadValue { table: "weapon", id, column: "sharpness", value: s });
 					}
-					Some(sharp)
-				} else { None }
+					sharp
+				})
 			};
 
+			// An unknown element is dropped, the weapon is still usable
 			let mut elements = Vec::new();
-			let tmp: Option<String> = row.get("element1").unwrap();
-			if let Some(e) = tmp {
-				elements.push((Element::from_str(e.to_lowercase().as_str()).expect("Parse error"), row.get("element1_attack").unwrap()));
-			}
-			let tmp: Option<String> = row.get("element2").unwrap();
-			if let Some(e) = tmp {
-				elements.push((Element::from_str(e.to_lowercase().as_str()).expect("Parse error"), row.get("element2_attack").unwrap()));
+			for (element_column, attack_column) in [("element1", "element1_attack"), ("element2", "element2_attack")] {
+				let tmp: Option<String> = row.get(element_column)?;
+				if let Some(e) = tmp {
+					if let Some(element) = report.parse::<Element>("weapon", id, element_column, &e.to_lowercase()) {
+						elements.push((element, row.get(attack_column)?));
+					}
+				}
 			}
 			elements.shrink_to_fit();
-			let element_hidden = row.get("element_hidden").unwrap();
-			let tmp: Option<String> = row.get("elderseal").unwrap();
-			let elderseal = ElderSeal::new(tmp);
+			let element_hidden = row.get("element_hidden")?;
+			let tmp: Option<String> = row.get("elderseal")?;
+			let elderseal = match ElderSeal::new(tmp.as_deref()) {
+				Some(elderseal) => elderseal,
+				None => {
+					report.push(DataError::BadValue { table: "weapon", id, column: "elderseal", value: tmp.unwrap_or_default() });
+					ElderSeal::Empty
+				}
+			};
 			let armoset_bonus = {
-				let tmp: Option<ID> = row.get("armorset_bonus_id").unwrap();
-				if let Some(id) = tmp {
-					Some(Arc::clone(get_set_skill_by_id(set_skills, id).unwrap()))
-				} else { None }
+				let tmp: Option<ID> = row.get("armorset_bonus_id")?;
+				tmp.and_then(|bonus_id| report.reference(set_skills.get(bonus_id), "weapon", id, "armorset_bonus", bonus_id))
+					.map(Arc::clone)
 			};
-			let mut skill = SkillsLevel::new();
-			if let Some(id) = row.get("skilltree_id").unwrap() {
-				skill.insert(SkillLevel::new(Arc::clone(get_skill_by_id(skills, id).unwrap()), 1));
-			}
 
+			let mut columns = HashMap::new();
+			for column in CLASS_COLUMNS {
+				columns.insert(column, text(row, column)?);
+			}
+			let ammo_id: Option<ID> = row.get("ammo_id")?;
+			let ammo = match ammo_id {
+				Some(ammo_id) if class.has_ammo() => {
+					let table = ammo.get(&ammo_id).cloned();
+					if table.is_none() {
+						report.push(DataError::Dangling { table: "weapon", id, target: "weapon_ammo", target_id: ammo_id });
+					}
+					table
+				}
+				_ => None,
+			};
 
-			let w = Weapon::new(id, prev, class, name,
+			let mut weapon = Weapon::new(id, prev, class, name,
 				attack, affinity, sharpness, defense,
 				slots, elements, element_hidden, elderseal,
-				armoset_bonus, skill,
+				armoset_bonus, SkillsLevel::new(),
 			);
-			weapons.insert(Arc::new(w));
+			weapon.class_data = class_data(class, id, &|column| columns.get(column).cloned().flatten(), ammo, report);
+			Ok(Some(weapon))
+		}
+
+		// One row for each skill of the weapon, weapons without skills have one row with a NULL skill
+		let mut current: Option<(ID, Option<Weapon>)> = None;
+		while let Some(row) = rows.next()? {
+			let id: ID = row.get("id")?;
+			if current.as_ref().is_none_or(|(current_id, _)| *current_id != id) {
+				if let Some((_, Some(mut done))) = current.replace((id, new_weapon(row, set_skills, &ammo, &self.lang, report)?)) {
+					done.skill.shrink_to_fit();
+					weapons.insert(Arc::new(done));
+				}
+			}
+			let skill_id: Option<ID> = row.get("skilltree_id")?;
+			if let (Some((_, Some(weapon))), Some(skill_id)) = (current.as_mut(), skill_id) {
+				if let Some(skill) = report.reference(skills.get(skill_id), "weapon", id, "skilltree", skill_id) {
+					weapon.skill.insert(SkillLevel::new(Arc::clone(skill), row.get("level")?));
+				}
+			}
+		}
+		if let Some((_, Some(mut done))) = current {
+			done.skill.shrink_to_fit();
+			weapons.insert(Arc::new(done));
 		}
 		weapons.shrink_to_fit();
+		report.expect_rows("weapon", weapons.len());
+		Ok(())
 	}
 
-	pub fn load_tools(&self, tools: &mut Tools) {
+	fn load_tools(&self, tools: &mut Tools, _report: &mut LoadReport) -> Result<(), DataError> {
 		tools.insert(Arc::new(Tool::new(1, String::from("ToDo"), [2,2,1])));
+		Ok(())
+	}
+
+	fn load_monsters(&self, monsters: &mut Monsters, skills: &Skills, report: &mut LoadReport) -> Result<(), DataError> {
+		let mut statement = self.connection.prepare(
+			"SELECT monster.*, t.name, en.name AS name_en
+		FROM monster
+		LEFT JOIN monster_text t ON monster.id = t.id AND t.lang_id = ?1
+		LEFT JOIN monster_text en ON monster.id = en.id AND en.lang_id = ?2
+		ORDER BY monster.id;")?;
+		let mut rows = statement.query(params![&self.lang, FALLBACK_LANG])?;
+		let english_ids = self.english_skill_ids()?;
+		let mut hitzones = self.load_hitzones(report)?;
+		while let Some(row) = rows.next()? {
+			let id = row.get("id")?;
+			let size: Option<String> = row.get("size")?;
+			let mut monster = Monster::new(id, localized_name(row, "monster", id, &self.lang, report)?, size.as_deref() != Some("small"));
+			let mut columns = HashMap::new();
+			for column in WEAKNESS_COLUMNS.iter().chain(AILMENT_COLUMNS.iter()) {
+				columns.insert(*column, text(row, column)?);
+			}
+			monster_data(&mut monster, &|column| columns.get(column).cloned().flatten(), report);
+			monster.suggest_skills(skills, &english_ids);
+			monster.hitzones = hitzones.remove(&id).unwrap_or_default();
+			monsters.insert(Arc::new(monster));
+		}
+		for (monster_id, zones) in hitzones.iter() {
+			for hitzone in zones.iter() {
+				report.push(DataError::Dangling { table: "monster_hitzone", id: hitzone.id, target: "monster", target_id: *monster_id });
+			}
+		}
+		monsters.shrink_to_fit();
+		report.expect_rows("monster", monsters.len());
+		Ok(())
+	}
+
+	fn load_damage_skills(&self, damage_skills: &mut DamageSkills, skills: &Skills, _report: &mut LoadReport) -> Result<(), DataError> {
+		resolve_damage_skills(damage_skills, skills, &self.english_skill_ids()?);
+		Ok(())
+	}
+
+	// Only the items used in a recipe are loaded
+	fn load_recipes(&self, materials: &mut Materials, recipes: &mut Recipes, report: &mut LoadReport) -> Result<(), DataError> {
+		let mut statement = self.connection.prepare(
+			"SELECT item.id, item.rarity, t.name, en.name AS name_en
+		FROM item
+		LEFT JOIN item_text t ON item.id = t.id AND t.lang_id = ?1
+		LEFT JOIN item_text en ON item.id = en.id AND en.lang_id = ?2
+		WHERE item.id IN (SELECT item_id FROM recipe_item)
+		ORDER BY item.id;")?;
+		let mut rows = statement.query(params![&self.lang, FALLBACK_LANG])?;
+		while let Some(row) = rows.next()? {
+			let id = row.get("id")?;
+			let rarity: Option<u8> = row.get("rarity")?;
+			materials.insert(Arc::new(Material::new(id, localized_name(row, "item", id, &self.lang, report)?, rarity.unwrap_or_default())));
+		}
+
+		// Every recipe_id column, an entity without recipe has none
+		let mut statement = self.connection.prepare(
+			"SELECT x.kind, x.id, ri.item_id, ri.quantity
+		FROM (
+			SELECT 'armor' AS kind, id, recipe_id FROM armor
+			UNION ALL SELECT 'charm', id, recipe_id FROM charm
+			UNION ALL SELECT 'weapon create', id, create_recipe_id FROM weapon
+			UNION ALL SELECT 'weapon upgrade', id, upgrade_recipe_id FROM weapon
+		) AS x
+		JOIN recipe_item ri ON ri.recipe_id = x.recipe_id
+		ORDER BY x.kind, x.id, ri.item_id;")?;
+		let mut rows = statement.query([])?;
+		while let Some(row) = rows.next()? {
+			let id: ID = row.get("id")?;
+			let kind: String = row.get("kind")?;
+			let kind = match report.parse::<RecipeKind>("recipe_item", id, "kind", &kind) {
+				Some(kind) => kind,
+				None => continue,
+			};
+			let item_id = row.get("item_id")?;
+			if let Some(material) = report.reference(materials.get(item_id), "recipe_item", id, "item", item_id) {
+				recipes.entry((kind, id)).or_default().push((Arc::clone(material), row.get("quantity")?));
+			}
+		}
+		report.expect_rows("item", materials.len());
+		Ok(())
 	}
 }
 
-pub(crate) fn get_skill_by_id(skills: &Skills, id: ID) -> Option<&Arc<Skill>> {
-	skills.iter().find(|s| s.id == id)
+// The comma separated hits of each sharpness level, from red to purple, the missing levels are 0
+pub(crate) fn parse_sharpness(s: &str) -> Option<[u8; SHARPNESS_LEVELS]> {
+	let mut sharp = [0u8; SHARPNESS_LEVELS];
+	let values: Option<Vec<u8>> = s.split(',').map(|n| n.trim().parse::<u8>().ok()).collect();
+	match values {
+		Some(values) if values.len() <= sharp.len() => {
+			sharp[..values.len()].copy_from_slice(&values);
+			Some(sharp)
+		}
+		_ => None,
+	}
 }
 
-pub(crate) fn get_set_skill_by_id(set_skills: &SetSkills, id: ID) -> Option<&Arc<SetSkill>> {
-	set_skills.iter().find(|s| s.id == id)
+// Any column as text, None for NULL and empty text
+fn text(row: &Row, column: &str) -> Result<Option<String>, DataError> {
+	Ok(match row.get::<_, Value>(column)? {
+		Value::Integer(i) => Some(i.to_string()),
+		Value::Real(r) => Some(r.to_string()),
+		Value::Text(t) => Some(t).filter(|t| !t.is_empty()),
+		Value::Null | Value::Blob(_) => None,
+	})
 }
 
-pub(crate) fn get_armor_by_id(armors: &Armors, id: ID) -> Option<&Arc<Armor>> {
-	armors.iter().find(|s| s.id == id)
+// The columns of the class data, the same in mhw.db and in the source files
+pub(crate) const CLASS_COLUMNS: [&str; 12] = [
+	"phial", "phial_power", "shelling", "shelling_level", "kinsect_bonus", "notes",
+	"coating_close", "coating_power", "coating_paralysis", "coating_poison", "coating_sleep", "coating_blast",
+];
+
+fn is_true(value: &str) -> bool {
+	value == "1" || value.eq_ignore_ascii_case("true")
 }
 
-pub(crate) fn get_decorations_by_id(decorations: &Decorations, id: ID) -> Option<&Arc<Decoration>> {
-	decorations.iter().find(|s| s.id == id)
+// The class data of weapon id from the text of its CLASS_COLUMNS, ammo is the table of a bowgun
+pub(crate) fn class_data(class: WeaponClass, id: ID, column: &dyn Fn(&str) -> Option<String>, ammo: Option<AmmoTable>, report: &mut LoadReport) -> ClassData {
+	match class {
+		WeaponClass::SwitchAxe | WeaponClass::ChargeBlade => column("phial").map(|kind| ClassData::Phial {
+			kind,
+			power: column("phial_power").and_then(|power| report.parse("weapon", id, "phial_power", &power)),
+		}),
+		WeaponClass::Gunlance => column("shelling").map(|kind| ClassData::Shelling {
+			kind,
+			level: column("shelling_level").and_then(|level| report.parse("weapon", id, "shelling_level", &level)).unwrap_or(0),
+		}),
+		WeaponClass::InsectGlaive => column("kinsect_bonus").map(|bonus| ClassData::Kinsect { bonus }),
+		WeaponClass::HuntingHorn => column("notes").map(ClassData::Notes),
+		WeaponClass::Bow => Some(ClassData::Coatings(CLASS_COLUMNS.iter()
+			.filter(|c| column(c).is_some_and(|v| is_true(&v)))
+			.filter_map(|c| c.strip_prefix("coating_"))
+			.map(String::from)
+			.collect())),
+		WeaponClass::LightBowgun | WeaponClass::HeavyBowgun => ammo.map(|table| ClassData::Ammo(Box::new(table))),
+		_ => None,
+	}.unwrap_or_default()
+}
+
+// The usable ammo of a weapon_ammo row with these columns, an invalid number is an unusable ammo
+pub(crate) fn ammo_table(columns: &[String], column: &dyn Fn(&str) -> Option<String>) -> AmmoTable {
+	let ammo = columns.iter()
+		.filter_map(|c| c.strip_suffix("_clip"))
+		.filter_map(|name| {
+			let clip = column(&format!("{}_clip", name))?.parse::<u8>().ok().filter(|clip| *clip > 0)?;
+			Some(Ammo {
+				name: name.to_string(),
+				clip,
+				rapid: column(&format!("{}_rapid", name)).is_some_and(|v| is_true(&v)),
+				recoil: column(&format!("{}_recoil", name)).and_then(|v| v.parse().ok()),
+				reload: column(&format!("{}_reload", name)).unwrap_or_default(),
+			})
+		})
+		.collect();
+	AmmoTable {
+		deviation: column("deviation").unwrap_or_default(),
+		special: column("special_ammo").unwrap_or_default(),
+		ammo,
+	}
+}
+
+// The weakness_<element> columns in the order of Element, the stars of the monster in its normal state
+pub(crate) const WEAKNESS_COLUMNS: [&str; Element::COUNT] = [
+	"weakness_fire", "weakness_water", "weakness_thunder", "weakness_ice", "weakness_dragon",
+	"weakness_poison", "weakness_sleep", "weakness_paralysis", "weakness_blast", "weakness_stun",
+];
+
+// The weaknesses and the ailments of monster from the text of its columns, the same in mhw.db and in the source files
+pub(crate) fn monster_data(monster: &mut Monster, column: &dyn Fn(&str) -> Option<String>, report: &mut LoadReport) {
+	for (i, name) in WEAKNESS_COLUMNS.iter().enumerate() {
+		monster.weaknesses[i] = column(name).and_then(|stars| report.parse("monster", monster.id, name, &stars)).unwrap_or_default();
+	}
+	monster.ailments = AILMENT_COLUMNS.iter()
+		.filter_map(|name| Ailment::from_column(name, &column(name)?))
+		.collect();
+}
+
+// The columns of a hitzone, the elements in the order of Element
+pub(crate) const HITZONE_ELEMENT_COLUMNS: [&str; Element::len()] = ["fire", "water", "thunder", "ice", "dragon"];
+
+// The hitzone values from the columns of the same name, the missing ones are 0
+pub(crate) fn hitzone_data(hitzone: &mut Hitzone, column: &dyn Fn(&str) -> Option<String>, report: &mut LoadReport) {
+	let id = hitzone.id;
+	let mut value = |name: &'static str| column(name).and_then(|v| report.parse("monster_hitzone", id, name, &v)).unwrap_or_default();
+	hitzone.cut = value("cut");
+	hitzone.impact = value("impact");
+	hitzone.shot = value("shot");
+	hitzone.ko = value("ko");
+	for (i, name) in HITZONE_ELEMENT_COLUMNS.iter().enumerate() {
+		hitzone.elements[i] = value(name);
+	}
+}
+
+// The name in lang, else the English one, else a placeholder. Every fallback is reported
+fn localized_name(row: &Row, table: &'static str, id: ID, lang: &Option<String>, report: &mut LoadReport) -> Result<String, DataError> {
+	let name: Option<String> = row.get("name")?;
+	if let Some(name) = name {
+		return Ok(name);
+	}
+	let english: Option<String> = row.get("name_en")?;
+	report.push(DataError::MissingTranslation {
+		table,
+		id,
+		lang: lang.clone().unwrap_or_default(),
+		english: english.is_some(),
+	});
+	Ok(english.unwrap_or_else(|| format!("{} {}", table, id)))
 }
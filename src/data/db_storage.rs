@@ -1,8 +1,30 @@
+use std::{
+	collections::HashMap,
+	sync::Arc,
+};
 use crate::data::{
-	db::DB,
-	db_types::{Weapons, Charms, Decorations, ArmorSets, Armors, SetSkills, Skills, Tools},
+	damage::DamageSkills,
+	db::{DataError, DB, LoadReport},
+	source::DataSource,
+	db_types::{ID, Weapons, Charms, Decorations, ArmorSets, Armors, Materials, Monsters, Recipes, SetSkills, Skills, Tools, id_map::{Entity, IdMap}, localized::Localized, monster::Monster, skill::Skill},
 };
 
+// Give to every item the name of its id in names
+fn rename<T: Entity>(items: &IdMap<T>, names: &mut HashMap<ID, String>, name: fn(&T) -> &Localized, set: fn(&Localized, Option<String>)) {
+	for item in items.iter() {
+		set(name(item), names.remove(&item.id()));
+	}
+	items.index_names();
+}
+
+// The hitzones are not indexed, they are searched in their monster
+fn rename_hitzones(monsters: &Monsters, names: &mut HashMap<ID, String>, set: fn(&Localized, Option<String>)) {
+	for hitzone in monsters.iter().flat_map(|m| m.hitzones.iter()) {
+		set(&hitzone.name, names.remove(&hitzone.id));
+	}
+}
+
+#[derive(Clone)]
 pub struct Storage {
 	pub skills: Skills,  // Len 168
 	pub set_skills: SetSkills,
@@ -12,6 +34,10 @@ pub struct Storage {
 	pub charms: Charms,
 	pub weapons: Weapons,
 	pub tools: Tools,
+	pub monsters: Monsters,
+	pub damage_skills: DamageSkills,
+	pub materials: Materials,  // Only the ones in a recipe
+	pub recipes: Recipes,
 }
 
 impl Storage {
@@ -26,18 +52,80 @@ impl Storage {
 			charms: Default::default(),
 			weapons: Default::default(),
 			tools: Default::default(),
+			monsters: Default::default(),
+			damage_skills: Default::default(),
+			materials: Default::default(),
+			recipes: Default::default(),
 		}
 	}
 
-	pub fn load_all(&mut self, db: &DB) {
-		db.load_skills(&mut self.skills);
-		db.load_set_skills(&mut self.set_skills, &self.skills);
-		db.load_armors(&mut self.armors, &self.skills, &self.set_skills);
-		db.load_sets(&mut self.sets, &self.armors, &self.set_skills);
-		db.load_charms(&mut self.charms, &self.skills);
-		db.load_decorations(&mut self.decorations, &self.skills);
-		db.load_weapons(&mut self.weapons, &self.skills, &self.set_skills);
-		db.load_tools(&mut self.tools);
+	// Err only if the source is unusable, the skipped entities are in the report
+	pub fn load_all(&mut self, source: &dyn DataSource) -> Result<LoadReport, DataError> {
+		let mut report = LoadReport::default();
+		source.load_skills(&mut self.skills, &mut report)?;
+		source.load_set_skills(&mut self.set_skills, &self.skills, &mut report)?;
+		source.load_armors(&mut self.armors, &self.skills, &self.set_skills, &mut report)?;
+		source.load_sets(&mut self.sets, &self.armors, &self.set_skills, &mut report)?;
+		source.load_charms(&mut self.charms, &self.skills, &mut report)?;
+		source.load_decorations(&mut self.decorations, &self.skills, &mut report)?;
+		source.load_weapons(&mut self.weapons, &self.skills, &self.set_skills, &mut report)?;
+		source.load_tools(&mut self.tools, &mut report)?;
+		source.load_monsters(&mut self.monsters, &self.skills, &mut report)?;
+		source.load_damage_skills(&mut self.damage_skills, &self.skills, &mut report)?;
+		source.load_recipes(&mut self.materials, &mut self.recipes, &mut report)?;
+		Ok(report)
+	}
+
+	/*
+	Rewrite the names in the language of db, nothing else changes.
+	The entities are the same Arcs, so DynamicStorage, the constraints and the results keep working.
+	*/
+	pub fn reload_names(&self, db: &DB) -> Result<LoadReport, DataError> {
+		let set: fn(&Localized, Option<String>) = |name, new| if let Some(new) = new { name.set(new) };
+		let mut report = LoadReport::default();
+		let mut names = db.load_names("skilltree", "skilltree", "id", "skilltree_text", &mut report)?;
+		rename(&self.skills, &mut names, |s| &s.name, set);
+		let mut descriptions = db.load_skill_descriptions()?;
+		rename(&self.skills, &mut descriptions, |s| &s.description, set);
+		let mut names = db.load_names("armorset_bonus", "armorset_bonus_skill", "setbonus_id", "armorset_bonus_text", &mut report)?;
+		rename(&self.set_skills, &mut names, |s| &s.name, set);
+		let mut names = db.load_names("armor", "armor", "id", "armor_text", &mut report)?;
+		rename(&self.armors, &mut names, |a| &a.name, set);
+		let mut names = db.load_names("armorset", "armorset", "id", "armorset_text", &mut report)?;
+		rename(&self.sets, &mut names, |s| &s.name, set);
+		let mut names = db.load_names("charm", "charm", "id", "charm_text", &mut report)?;
+		rename(&self.charms, &mut names, |c| &c.name, set);
+		let mut names = db.load_names("decoration", "decoration", "id", "decoration_text", &mut report)?;
+		rename(&self.decorations, &mut names, |d| &d.name, set);
+		let mut names = db.load_names("weapon", "weapon", "id", "weapon_text", &mut report)?;
+		rename(&self.weapons, &mut names, |w| &w.name, set);
+		let mut names = db.load_names("monster", "monster", "id", "monster_text", &mut report)?;
+		rename(&self.monsters, &mut names, |m| &m.name, set);
+		let mut names = db.load_names("monster_hitzone", "monster_hitzone", "id", "monster_hitzone_text", &mut report)?;
+		rename_hitzones(&self.monsters, &mut names, set);
+		let mut names = db.load_names("item", "recipe_item", "item_id", "item_text", &mut report)?;
+		rename(&self.materials, &mut names, |m| &m.name, set);
+		Ok(report)
+	}
+
+	// Names in lang shown next to the current ones, None shows only the current ones
+	pub fn set_secondary_language(&self, db: &DB, lang: Option<&str>) -> Result<(), DataError> {
+		let set: fn(&Localized, Option<String>) = Localized::set_secondary;
+		let load = |text_table: &str| match lang {
+			Some(lang) => db.load_translations(lang, text_table),
+			None => Ok(HashMap::new()),
+		};
+		rename(&self.skills, &mut load("skilltree_text")?, |s| &s.name, set);
+		rename(&self.set_skills, &mut load("armorset_bonus_text")?, |s| &s.name, set);
+		rename(&self.armors, &mut load("armor_text")?, |a| &a.name, set);
+		rename(&self.sets, &mut load("armorset_text")?, |s| &s.name, set);
+		rename(&self.charms, &mut load("charm_text")?, |c| &c.name, set);
+		rename(&self.decorations, &mut load("decoration_text")?, |d| &d.name, set);
+		rename(&self.weapons, &mut load("weapon_text")?, |w| &w.name, set);
+		rename(&self.monsters, &mut load("monster_text")?, |m| &m.name, set);
+		rename_hitzones(&self.monsters, &mut load("monster_hitzone_text")?, set);
+		rename(&self.materials, &mut load("item_text")?, |m| &m.name, set);
+		Ok(())
 	}
 
 	#[allow(dead_code)]
@@ -50,5 +138,30 @@ impl Storage {
 		println!("\t{} charms", self.charms.len());
 		println!("\t{} decorations", self.decorations.len());
 		println!("\t{} weapons", self.weapons.len());
+		println!("\t{} monsters", self.monsters.len());
+	}
+
+	pub fn get_skill_from_name(&self, name: &str) -> Option<Arc<Skill>> {
+		self.skills.get_by_name(name)
+			.filter(|skill| skill.name.is(name))
+			.map(Arc::clone)
+	}
+
+	// Skill by id or by name in one of the two languages, the name match ignores the case
+	pub fn find_skill(&self, query: &str) -> Option<Arc<Skill>> {
+		let query = query.trim();
+		if let Ok(id) = query.parse::<ID>() {
+			return self.skills.get(id).map(Arc::clone);
+		}
+		self.skills.get_by_name(query).map(Arc::clone)
 	}
-}
\ No newline at end of file
+
+	// By id or by name in any loaded language, like find_skill()
+	pub fn find_monster(&self, query: &str) -> Option<Arc<Monster>> {
+		let query = query.trim();
+		if let Ok(id) = query.parse::<ID>() {
+			return self.monsters.get(id).map(Arc::clone);
+		}
+		self.monsters.get_by_name(query).map(Arc::clone)
+	}
+}
@@ -1,14 +1,17 @@
 use std::{
-	collections::HashSet,
+	collections::HashMap,
 	slice::Iter,
-	sync::Arc,
 };
+use serde::{Deserialize, Serialize};
 use strum::{Display, EnumIter, EnumString, EnumCount};
 use crate::data::db_types::{
 	armor::{Armor, ArmorSet},
 	charm::Charm,
 	tool::Tool,
 	decoration::Decoration,
+	id_map::IdMap,
+	material::{Material, Recipe, RecipeKind},
+	monster::Monster,
 	skill::{SetSkill, Skill, SkillsLevel},
 	weapon::Weapon,
 	Element::{Blast, Dragon, Fire, Ice, Paralysis, Poison, Sleep, Stun, Thunder, Water},
@@ -17,6 +20,10 @@ use crate::data::db_types::{
 pub mod armor;
 pub mod charm;
 pub mod decoration;
+pub mod id_map;
+pub mod localized;
+pub mod material;
+pub mod monster;
 pub mod skill;
 pub mod tool;
 pub mod weapon;
@@ -25,14 +32,17 @@ pub type ID = u16;
 pub type Level = u8;
 pub type Slot = u8;
 
-pub type Weapons =     HashSet<Arc<Weapon>>;
-pub type Armors =      HashSet<Arc<Armor>>;
-pub type ArmorSets =   HashSet<Arc<ArmorSet>>;
-pub type Charms =      HashSet<Arc<Charm>>;
-pub type Decorations = HashSet<Arc<Decoration>>;
-pub type Skills =      HashSet<Arc<Skill>>;
-pub type SetSkills =   HashSet<Arc<SetSkill>>;
-pub type Tools =       HashSet<Arc<Tool>>;
+pub type Weapons =     IdMap<Weapon>;
+pub type Armors =      IdMap<Armor>;
+pub type ArmorSets =   IdMap<ArmorSet>;
+pub type Charms =      IdMap<Charm>;
+pub type Decorations = IdMap<Decoration>;
+pub type Skills =      IdMap<Skill>;
+pub type SetSkills =   IdMap<SetSkill>;
+pub type Tools =       IdMap<Tool>;
+pub type Monsters =    IdMap<Monster>;
+pub type Materials =   IdMap<Material>;
+pub type Recipes =     HashMap<(RecipeKind, ID), Recipe>;  // By what they make and its id
 pub type Slots =       Vec<Slot>;
 
 pub const MAX_SLOTS: usize = 3;
@@ -47,7 +57,7 @@ pub trait Item {
 }
 
 // Elements
-#[derive(Display, EnumString, EnumIter)]
+#[derive(Display, EnumString, EnumIter, EnumCount, Clone, Copy, Serialize, Deserialize)]
 pub enum Element {
 	#[strum(serialize = "fire")]
 	Fire,
@@ -92,7 +102,7 @@ impl Element {
 
 // Armor Class
 #[repr(usize)]
-#[derive(EnumCount, EnumString, EnumIter, Display, Copy, Clone)]
+#[derive(EnumCount, EnumString, EnumIter, Display, Copy, Clone, Serialize, Deserialize)]
 pub enum ArmorClass {
 	#[strum(serialize = "head")]
 	Head,
@@ -107,7 +117,7 @@ pub enum ArmorClass {
 }
 
 // ArmorSet rank level
-#[derive(EnumCount, EnumString, EnumIter, Display, Copy, Clone, PartialEq, Eq)]
+#[derive(EnumCount, EnumString, EnumIter, Display, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ArmorRank {
 	#[strum(serialize = "LR")]
 	Low,
@@ -118,27 +128,31 @@ pub enum ArmorRank {
 }
 
 // Armor related. There are some armors only for some gender
-#[derive(PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(EnumString, Display, PartialEq, Eq, PartialOrd, Ord, Hash, Copy, Clone, Serialize, Deserialize)]
 pub enum Gender {
+	#[strum(serialize = "male")]
 	Male,
+	#[strum(serialize = "female")]
 	Female,
+	#[strum(serialize = "all")]
 	All,
 }
 
 impl Gender {
-	pub fn new(male: bool, female: bool) -> Self {
+	// None if nobody can wear it
+	pub fn new(male: bool, female: bool) -> Option<Self> {
 		match (male, female) {
-			(false, false) => panic!("No gender"),
-			(true, false) => Gender::Male,
-			(false, true) => Gender::Female,
-			(true, true) => Gender::All,
+			(false, false) => None,
+			(true, false) => Some(Gender::Male),
+			(false, true) => Some(Gender::Female),
+			(true, true) => Some(Gender::All),
 		}
 	}
 }
 
 // Weapon type
 #[repr(usize)]
-#[derive(EnumCount, EnumString, EnumIter, Display, Copy, Clone)]
+#[derive(EnumCount, EnumString, EnumIter, Display, Copy, Clone, PartialEq, Serialize, Deserialize)]
 pub enum WeaponClass {
 	#[strum(serialize = "bow")]
 	Bow,
@@ -170,7 +184,14 @@ pub enum WeaponClass {
 	SwordAndShield,
 }
 
+impl WeaponClass {
+	pub fn has_ammo(&self) -> bool {
+		matches!(self, WeaponClass::LightBowgun | WeaponClass::HeavyBowgun)
+	}
+}
+
 // Elder Seal level only for weapons
+#[derive(Copy, Clone, Serialize, Deserialize)]
 pub enum ElderSeal {
 	Empty,
 	Low,
@@ -179,16 +200,14 @@ pub enum ElderSeal {
 }
 
 impl ElderSeal {
-	pub fn new(lev: Option<String>) -> Self {
-		if lev.is_none() {
-			ElderSeal::Empty
-		} else {
-			match lev.unwrap().as_str() {
-				"low" => ElderSeal::Low,
-				"average" => ElderSeal::Average,
-				"high" => ElderSeal::High,
-				_ => panic!("Elderseal parsing error")
-			}
+	// None if lev is not a known elderseal
+	pub fn new(lev: Option<&str>) -> Option<Self> {
+		match lev {
+			None => Some(ElderSeal::Empty),
+			Some("low") => Some(ElderSeal::Low),
+			Some("average") => Some(ElderSeal::Average),
+			Some("high") => Some(ElderSeal::High),
+			Some(_) => None,
 		}
 	}
 }
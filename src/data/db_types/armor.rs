@@ -5,6 +5,8 @@ use std::{
 };
 use strum::EnumCount;
 use crate::data::db_types::{
+    id_map::Entity,
+    localized::Localized,
     ID,
     MAX_SLOTS,
     ArmorClass,
@@ -18,7 +20,7 @@ use crate::data::db_types::{
 
 pub struct Armor {
     pub id: ID,
-    pub name: String,
+    pub name: Localized,
     pub class: ArmorClass,
     pub rank: ArmorRank,
     pub skills: SkillsLevel,
@@ -31,7 +33,7 @@ pub struct Armor {
 
 impl Armor {
     pub fn new(id: u16, name: String, class: ArmorClass, rank: ArmorRank, gender: Gender, slots: [u8; MAX_SLOTS], defence: [u8; 3], elements: [i8; 5]) -> Self {
-        Armor { id, name, class, rank, skills: SkillsLevel::new(), set_skill: None, gender, slots, defence, elements }
+        Armor { id, name: Localized::new(name), class, rank, skills: SkillsLevel::new(), set_skill: None, gender, slots, defence, elements }
     }
 
     pub fn add_skill(&mut self, skill: &Arc<Skill>, level: u8) {
@@ -71,27 +73,38 @@ impl Hash for Armor {
     }
 }
 
+impl Entity for Armor {
+    fn id(&self) -> ID {
+        self.id
+    }
+    fn name(&self) -> &Localized {
+        &self.name
+    }
+}
+
 #[allow(dead_code)]
 pub struct ArmorSet {
     pub id: u16,
-    pub name: String,
+    pub name: Localized,
     pub rank: ArmorRank,
     pub armors: [Option<Arc<Armor>>; ArmorClass::COUNT],
-    armorset_skill: Option<Arc<SetSkill>>,
+    pub(crate) armorset_skill: Option<Arc<SetSkill>>,
 }
 
 impl ArmorSet {
     pub fn new(id: u16, name: String, rank: ArmorRank, armorset_skill: Option<Arc<SetSkill>>) -> Self {
-        ArmorSet { id, name, rank, armors: [None, None, None, None, None], armorset_skill}
+        ArmorSet { id, name: Localized::new(name), rank, armors: [None, None, None, None, None], armorset_skill}
     }
 
-    pub fn add_armor(&mut self, armor: &Arc<Armor>) {
+    // Err with the id of the armor already in the slot, that one is kept
+    pub fn add_armor(&mut self, armor: &Arc<Armor>) -> Result<(), ID> {
         let i = armor.class as usize;
-        if self.armors[i].is_some() {
-            panic!("Element of a set already full");
+        if let Some(taken) = &self.armors[i] {
+            Err(taken.id)
         }
         else {
             self.armors[i] = Some(Arc::clone(armor));
+            Ok(())
         }
     }
 
@@ -111,4 +124,13 @@ impl Hash for ArmorSet {
     fn hash<H: Hasher>(&self, state: &mut H) {
         self.id.hash(state);
     }
-}
\ No newline at end of file
+}
+
+impl Entity for ArmorSet {
+    fn id(&self) -> ID {
+        self.id
+    }
+    fn name(&self) -> &Localized {
+        &self.name
+    }
+}
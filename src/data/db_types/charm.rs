@@ -4,6 +4,8 @@ use std::{
 	hash::{Hash, Hasher},
 };
 use crate::data::db_types::{
+	id_map::Entity,
+	localized::Localized,
 	ID,
 	Item,
 	skill::{Skill, SkillLevel, SkillsLevel},
@@ -12,13 +14,14 @@ use crate::data::db_types::{
 
 pub struct Charm {
 	pub id: ID,
-	pub name: String,
+	pub name: Localized,
 	pub skills: SkillsLevel,
+	pub previous_id: Option<ID>,  // The lower level of the charm
 }
 
 impl Charm {
 	pub fn new(id: ID, name: String) -> Self {
-		Charm { id, name, skills: SkillsLevel::new() }
+		Charm { id, name: Localized::new(name), skills: SkillsLevel::new(), previous_id: None }
 	}
 	pub fn add_skill(&mut self, skill: &Arc<Skill>, level: u8) {
 		self.skills.insert(SkillLevel::new( Arc::clone(skill), level));
@@ -48,8 +51,17 @@ impl Hash for Charm {
 	}
 }
 
+impl Entity for Charm {
+	fn id(&self) -> ID {
+		self.id
+	}
+	fn name(&self) -> &Localized {
+		&self.name
+	}
+}
+
 impl fmt::Display for Charm {
 	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
 		write!(f, "{0: <45}| {1: <45}", format!("{} [{}]", self.name, self.id), self.skills.to_string())
 	}
-}
\ No newline at end of file
+}
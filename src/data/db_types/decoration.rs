@@ -3,6 +3,8 @@ use std::{
 	hash::{Hash, Hasher},
 };
 use crate::data::db_types::{
+	id_map::Entity,
+	localized::Localized,
 	ID, Item,
 	skill::SkillsLevel,
 };
@@ -10,14 +12,14 @@ use crate::data::db_types::{
 
 pub struct Decoration {
 	pub id: ID,
-	pub name: String,
+	pub name: Localized,
 	pub size: u8,
 	pub skills: SkillsLevel,
 }
 
 impl Decoration {
 	pub fn new(id: ID, name: String, size: u8, skills: SkillsLevel) -> Self {
-		Decoration { id, name, size, skills: skills }
+		Decoration { id, name: Localized::new(name), size, skills }
 	}
 }
 
@@ -43,6 +45,15 @@ impl Hash for Decoration {
 	}
 }
 
+impl Entity for Decoration {
+	fn id(&self) -> ID {
+		self.id
+	}
+	fn name(&self) -> &Localized {
+		&self.name
+	}
+}
+
 impl fmt::Display for Decoration {
 	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
 		write!(f, "{0: <45}|{1: <45}", format!("{} [{}]", self.name, self.id), self.skills.to_string())
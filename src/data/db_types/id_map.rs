@@ -0,0 +1,156 @@
+use std::{
+	collections::HashMap,
+	iter::FromIterator,
+	slice::Iter,
+	sync::{Arc, RwLock},
+};
+use crate::data::db_types::{ID, localized::{self, Localized}};
+
+// What IdMap needs to index an entity
+pub trait Entity {
+	fn id(&self) -> ID;
+	fn name(&self) -> &Localized;
+}
+
+/*
+Entities in load order, indexed by id and by lowercase name in both languages.
+The names change in place on a language change, so the name index is rebuilt by index_names(),
+or on the next lookup for the copies of the map, while the ids never change.
+*/
+pub struct IdMap<T> {
+	items: Vec<Arc<T>>,
+	ids: HashMap<ID, usize>,
+	names: RwLock<(u64, HashMap<String, ID>)>,  // With the localized::generation() it was built at
+}
+
+impl<T> IdMap<T> {
+	pub fn len(&self) -> usize {
+		self.items.len()
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.items.is_empty()
+	}
+
+	pub fn iter(&self) -> Iter<'_, Arc<T>> {
+		self.items.iter()
+	}
+
+	pub fn get(&self, id: ID) -> Option<&Arc<T>> {
+		self.ids.get(&id).map(|i| &self.items[*i])
+	}
+
+	pub fn contains(&self, id: ID) -> bool {
+		self.ids.contains_key(&id)
+	}
+
+}
+
+impl<T: Entity> IdMap<T> {
+	pub fn new() -> Self {
+		IdMap {
+			items: Vec::new(),
+			ids: HashMap::new(),
+			names: RwLock::new((localized::generation(), HashMap::new())),
+		}
+	}
+
+	// Name in one of the two languages, ignoring the case. The first loaded wins on duplicated names
+	pub fn get_by_name(&self, name: &str) -> Option<&Arc<T>> {
+		if self.names.read().unwrap().0 != localized::generation() {
+			self.index_names();
+		}
+		let id = *self.names.read().unwrap().1.get(&name.to_lowercase())?;
+		self.get(id)
+	}
+
+	// An item with an id already present takes its place, the old one is returned
+	pub fn insert(&mut self, item: Arc<T>) -> Option<Arc<T>> {
+		let names = &mut self.names.get_mut().unwrap().1;
+		let text = item.name().get().to_lowercase();
+		names.entry(text).or_insert_with(|| item.id());
+		if let Some(secondary) = item.name().secondary() {
+			names.entry(secondary.to_lowercase()).or_insert_with(|| item.id());
+		}
+		match self.ids.get(&item.id()) {
+			Some(i) => {
+				let old = std::mem::replace(&mut self.items[*i], item);
+				self.index_names();
+				Some(old)
+			}
+			None => {
+				self.ids.insert(item.id(), self.items.len());
+				self.items.push(item);
+				None
+			}
+		}
+	}
+
+	pub fn retain<F: FnMut(&Arc<T>) -> bool>(&mut self, keep: F) {
+		self.items.retain(keep);
+		self.ids = self.items.iter().enumerate().map(|(i, item)| (item.id(), i)).collect();
+		self.index_names();
+	}
+
+	pub fn shrink_to_fit(&mut self) {
+		self.items.shrink_to_fit();
+		self.ids.shrink_to_fit();
+	}
+
+	pub fn clear(&mut self) {
+		self.items.clear();
+		self.ids.clear();
+		self.names.get_mut().unwrap().1.clear();
+	}
+
+	// Call after the names have been rewritten
+	pub fn index_names(&self) {
+		let generation = localized::generation();
+		let mut names = HashMap::with_capacity(self.items.len() * 2);
+		// Current language first, a secondary name never hides a primary one
+		for item in self.items.iter() {
+			names.entry(item.name().get().to_lowercase()).or_insert_with(|| item.id());
+		}
+		for item in self.items.iter() {
+			if let Some(secondary) = item.name().secondary() {
+				names.entry(secondary.to_lowercase()).or_insert_with(|| item.id());
+			}
+		}
+		*self.names.write().unwrap() = (generation, names);
+	}
+}
+
+impl<T: Entity> Default for IdMap<T> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<T> Clone for IdMap<T> {
+	fn clone(&self) -> Self {
+		IdMap {
+			items: self.items.clone(),
+			ids: self.ids.clone(),
+			names: RwLock::new(self.names.read().unwrap().clone()),
+		}
+	}
+}
+
+impl<T: Entity> FromIterator<Arc<T>> for IdMap<T> {
+	fn from_iter<I: IntoIterator<Item = Arc<T>>>(iter: I) -> Self {
+		let mut map = IdMap::new();
+		for item in iter {
+			map.insert(item);
+		}
+		map
+	}
+}
+
+impl<'a, T> IntoIterator for &'a IdMap<T> {
+	type Item = &'a Arc<T>;
+	type IntoIter = Iter<'a, Arc<T>>;
+
+	fn into_iter(self) -> Self::IntoIter {
+		self.items.iter()
+	}
+}
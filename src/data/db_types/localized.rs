@@ -0,0 +1,121 @@
+use std::{
+	cmp::Ordering,
+	fmt,
+	sync::{
+		Arc, RwLock,
+		atomic::{self, AtomicU64},
+	},
+};
+use serde::{Serialize, Serializer};
+
+// Bumped on every rewrite of a text, the name indexes built before are stale
+static GENERATION: AtomicU64 = AtomicU64::new(0);
+
+pub(crate) fn generation() -> u64 {
+	GENERATION.load(atomic::Ordering::Acquire)
+}
+
+/*
+Text of an entity in the current language, optionally with the same text in a secondary language.
+Rewritten in place on a language change so every Arc of the entity, the constraints and the results
+included, shows the new text.
+*/
+pub struct Localized {
+	text: RwLock<Arc<str>>,
+	secondary: RwLock<Option<Arc<str>>>,
+}
+
+impl Localized {
+	pub fn new(text: String) -> Self {
+		Localized {
+			text: RwLock::new(Arc::from(text)),
+			secondary: RwLock::new(None),
+		}
+	}
+
+	// Text in the current language only
+	pub fn get(&self) -> Arc<str> {
+		Arc::clone(&self.text.read().unwrap())
+	}
+
+	pub fn secondary(&self) -> Option<Arc<str>> {
+		self.secondary.read().unwrap().clone()
+	}
+
+	pub(crate) fn set(&self, text: String) {
+		*self.text.write().unwrap() = Arc::from(text);
+		GENERATION.fetch_add(1, atomic::Ordering::Release);
+	}
+
+	pub(crate) fn set_secondary(&self, text: Option<String>) {
+		*self.secondary.write().unwrap() = text.map(Arc::from);
+		GENERATION.fetch_add(1, atomic::Ordering::Release);
+	}
+
+	// Exactly the text in one of the two languages
+	pub fn is(&self, text: &str) -> bool {
+		*self.get() == *text || self.secondary().is_some_and(|s| *s == *text)
+	}
+
+	// Same as is() ignoring the case
+	pub fn matches(&self, text: &str) -> bool {
+		let text = text.to_lowercase();
+		self.get().to_lowercase() == text || self.secondary().is_some_and(|s| s.to_lowercase() == text)
+	}
+}
+
+// "Occhio critico (Critical Eye)" when there is a different secondary text
+impl fmt::Display for Localized {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let text = self.get();
+		match self.secondary() {
+			Some(secondary) if secondary != text => f.pad(&format!("{} ({})", text, secondary)),
+			_ => f.pad(&text),
+		}
+	}
+}
+
+impl fmt::Debug for Localized {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		self.get().fmt(f)
+	}
+}
+
+impl PartialEq for Localized {
+	fn eq(&self, other: &Self) -> bool {
+		self.get() == other.get()
+	}
+}
+
+impl Eq for Localized {}
+
+impl PartialEq<str> for Localized {
+	fn eq(&self, other: &str) -> bool {
+		*self.get() == *other
+	}
+}
+
+impl PartialEq<&str> for Localized {
+	fn eq(&self, other: &&str) -> bool {
+		*self.get() == **other
+	}
+}
+
+impl PartialOrd for Localized {
+	fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+		Some(self.cmp(other))
+	}
+}
+
+impl Ord for Localized {
+	fn cmp(&self, other: &Self) -> Ordering {
+		self.get().cmp(&other.get())
+	}
+}
+
+// Only the current language, the records are read back by id
+impl Serialize for Localized {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		serializer.serialize_str(&self.get())
+	}
+}
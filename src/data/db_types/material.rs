@@ -0,0 +1,69 @@
+use std::{
+	fmt,
+	sync::Arc,
+	hash::{Hash, Hasher},
+};
+use serde::{Deserialize, Serialize};
+use strum::{Display, EnumString};
+use crate::data::db_types::{
+	ID,
+	id_map::Entity,
+	localized::Localized,
+};
+
+// An item of the item table used in a recipe
+pub struct Material {
+	pub id: ID,
+	pub name: Localized,
+	pub rarity: u8,
+}
+
+impl Material {
+	pub fn new(id: ID, name: String, rarity: u8) -> Self {
+		Material { id, name: Localized::new(name), rarity }
+	}
+}
+
+// What a recipe makes. A weapon upgrade starts from the previous weapon, a charm recipe from the previous level if any
+#[derive(Display, EnumString, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum RecipeKind {
+	#[strum(serialize = "armor")]
+	Armor,
+	#[strum(serialize = "charm")]
+	Charm,
+	#[strum(serialize = "weapon create")]
+	WeaponCreate,
+	#[strum(serialize = "weapon upgrade")]
+	WeaponUpgrade,
+}
+
+// The materials and their quantity
+pub type Recipe = Vec<(Arc<Material>, u8)>;
+
+impl PartialEq for Material {
+	fn eq(&self, other: &Self) -> bool {
+		self.id == other.id
+	}
+}
+impl Eq for Material {}
+
+impl Hash for Material {
+	fn hash<H: Hasher>(&self, state: &mut H) {
+		self.id.hash(state);
+	}
+}
+
+impl Entity for Material {
+	fn id(&self) -> ID {
+		self.id
+	}
+	fn name(&self) -> &Localized {
+		&self.name
+	}
+}
+
+impl fmt::Display for Material {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{} [{}] rarity {}", self.name, self.id, self.rarity)
+	}
+}
@@ -0,0 +1,297 @@
+use std::{
+	cmp::Reverse,
+	collections::HashMap,
+	fmt,
+	sync::Arc,
+	hash::{Hash, Hasher},
+	ops::Not,
+};
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+use strum::{Display, EnumCount, EnumIter, IntoEnumIterator};
+use crate::data::db_types::{
+	ID, Element, Level, Skills, WeaponClass, Weapons,
+	id_map::Entity,
+	localized::Localized,
+	skill::{SkillLevel, SkillsLevel},
+	weapon::Weapon,
+};
+
+// What a monster inflicts, the roars, winds and tremors by strength
+#[derive(EnumIter, Display, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Ailment {
+	#[strum(serialize = "roar")]
+	Roar,
+	#[strum(serialize = "large roar")]
+	LargeRoar,
+	#[strum(serialize = "wind")]
+	Wind,
+	#[strum(serialize = "strong wind")]
+	StrongWind,
+	#[strum(serialize = "tremor")]
+	Tremor,
+	#[strum(serialize = "large tremor")]
+	LargeTremor,
+	#[strum(serialize = "defense down")]
+	DefenseDown,
+	#[strum(serialize = "fireblight")]
+	Fireblight,
+	#[strum(serialize = "waterblight")]
+	Waterblight,
+	#[strum(serialize = "thunderblight")]
+	Thunderblight,
+	#[strum(serialize = "iceblight")]
+	Iceblight,
+	#[strum(serialize = "dragonblight")]
+	Dragonblight,
+	#[strum(serialize = "blastblight")]
+	Blastblight,
+	#[strum(serialize = "regional")]
+	Regional,
+	#[strum(serialize = "poison")]
+	Poison,
+	#[strum(serialize = "sleep")]
+	Sleep,
+	#[strum(serialize = "paralysis")]
+	Paralysis,
+	#[strum(serialize = "bleed")]
+	Bleed,
+	#[strum(serialize = "stun")]
+	Stun,
+	#[strum(serialize = "mud")]
+	Mud,
+	#[strum(serialize = "effluvia")]
+	Effluvia,
+}
+
+// The ailment_<name> columns of MHWorldData, a flag or the strength of the roar, wind and tremor
+pub(crate) const AILMENT_COLUMNS: [&str; 18] = [
+	"ailment_roar", "ailment_wind", "ailment_tremor", "ailment_defensedown", "ailment_fireblight", "ailment_waterblight",
+	"ailment_thunderblight", "ailment_iceblight", "ailment_dragonblight", "ailment_blastblight", "ailment_regional",
+	"ailment_poison", "ailment_sleep", "ailment_paralysis", "ailment_bleed", "ailment_stun", "ailment_mud", "ailment_effluvia",
+];
+
+impl Ailment {
+	// The ailment of a non empty ailment_<name> column, None for a false flag
+	pub(crate) fn from_column(column: &str, value: &str) -> Option<Self> {
+		let value = value.trim().to_lowercase();
+		let strong = value == "large" || value == "extreme";
+		if value.is_empty() || value == "0" || value == "false" {
+			return None;
+		}
+		Some(match column.strip_prefix("ailment_").unwrap_or(column) {
+			"roar" if strong => Ailment::LargeRoar,
+			"roar" => Ailment::Roar,
+			"wind" if strong => Ailment::StrongWind,
+			"wind" => Ailment::Wind,
+			"tremor" if strong => Ailment::LargeTremor,
+			"tremor" => Ailment::Tremor,
+			"defensedown" => Ailment::DefenseDown,
+			"fireblight" => Ailment::Fireblight,
+			"waterblight" => Ailment::Waterblight,
+			"thunderblight" => Ailment::Thunderblight,
+			"iceblight" => Ailment::Iceblight,
+			"dragonblight" => Ailment::Dragonblight,
+			"blastblight" => Ailment::Blastblight,
+			"regional" => Ailment::Regional,
+			"poison" => Ailment::Poison,
+			"sleep" => Ailment::Sleep,
+			"paralysis" => Ailment::Paralysis,
+			"bleed" => Ailment::Bleed,
+			"stun" => Ailment::Stun,
+			"mud" => Ailment::Mud,
+			"effluvia" => Ailment::Effluvia,
+			_ => return None,
+		})
+	}
+
+	// The English name of the skill that negates the ailment and its level, as in Iceborne
+	fn counter(&self) -> Option<(&'static str, Level)> {
+		match self {
+			Ailment::Roar => Some(("Earplugs", 3)),
+			Ailment::LargeRoar => Some(("Earplugs", 5)),
+			Ailment::Wind => Some(("Windproof", 3)),
+			Ailment::StrongWind => Some(("Windproof", 5)),
+			Ailment::Tremor => Some(("Tremor Resistance", 2)),
+			Ailment::LargeTremor => Some(("Tremor Resistance", 3)),
+			Ailment::DefenseDown => Some(("Iron Skin", 3)),
+			Ailment::Fireblight => Some(("Fire Resistance", 3)),
+			Ailment::Waterblight => Some(("Water Resistance", 3)),
+			Ailment::Thunderblight => Some(("Thunder Resistance", 3)),
+			Ailment::Iceblight => Some(("Ice Resistance", 3)),
+			Ailment::Dragonblight => Some(("Dragon Resistance", 3)),
+			Ailment::Blastblight => Some(("Blight Resistance", 3)),
+			Ailment::Poison => Some(("Poison Resistance", 3)),
+			Ailment::Sleep => Some(("Sleep Resistance", 3)),
+			Ailment::Paralysis => Some(("Paralysis Resistance", 3)),
+			Ailment::Bleed => Some(("Bleeding Resistance", 3)),
+			Ailment::Stun => Some(("Stun Resistance", 3)),
+			Ailment::Effluvia => Some(("Effluvia Resistance", 3)),
+			Ailment::Regional | Ailment::Mud => None,
+		}
+	}
+}
+
+// A body part and how much of each kind of damage goes through it, in percent
+pub struct Hitzone {
+	pub id: ID,
+	pub name: Localized,
+	pub cut: u8,
+	pub impact: u8,
+	pub shot: u8,
+	pub elements: [u8; Element::len()],  // By Element, from fire to dragon
+	pub ko: u8,
+}
+
+impl Hitzone {
+	pub fn new(id: ID, name: String) -> Self {
+		Hitzone { id, name: Localized::new(name), cut: 0, impact: 0, shot: 0, elements: [0; Element::len()], ko: 0 }
+	}
+
+	// The raw hitzone of the kind of damage the weapon class deals
+	pub fn raw(&self, class: WeaponClass) -> u8 {
+		match class {
+			WeaponClass::Hammer | WeaponClass::HuntingHorn => self.impact,
+			WeaponClass::Bow | WeaponClass::LightBowgun | WeaponClass::HeavyBowgun => self.shot,
+			_ => self.cut,
+		}
+	}
+
+	// 0 for the status elements, no hitzone reduces them
+	pub fn element(&self, element: Element) -> u8 {
+		self.elements.get(element as usize).copied().unwrap_or(0)
+	}
+}
+
+impl fmt::Display for Hitzone {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{} [{}] cut {} impact {} shot {}", self.name, self.id, self.cut, self.impact, self.shot)?;
+		for element in Element::iter_element() {
+			write!(f, " {} {}", element, self.element(*element))?;
+		}
+		Ok(())
+	}
+}
+
+pub struct Monster {
+	pub id: ID,
+	pub name: Localized,
+	pub large: bool,
+	pub weaknesses: [u8; Element::COUNT],  // Stars from 0 to 3, by Element
+	pub ailments: Vec<Ailment>,
+	pub suggested: SkillsLevel,  // The skills against the ailments
+	pub hitzones: Vec<Hitzone>,  // In the order of the database
+}
+
+impl Monster {
+	pub fn new(id: ID, name: String, large: bool) -> Self {
+		Monster {
+			id, name: Localized::new(name), large, weaknesses: [0; Element::COUNT], ailments: Vec::new(),
+			suggested: SkillsLevel::new(), hitzones: Vec::new(),
+		}
+	}
+
+	// By id or by name in one of the two languages, ignoring the case
+	pub fn find_hitzone(&self, query: &str) -> Option<&Hitzone> {
+		let query = query.trim();
+		match query.parse::<ID>() {
+			Ok(id) => self.hitzones.iter().find(|h| h.id == id),
+			Err(_) => self.hitzones.iter().find(|h| h.name.matches(query)),
+		}
+	}
+
+	pub fn weakness(&self, element: Element) -> u8 {
+		self.weaknesses[element as usize]
+	}
+
+	/*
+	Fill suggested from the ailments, english_ids are the ids of the skills by English name.
+	The skills missing in the data are left out, the levels are at most the max level of the skill.
+	*/
+	pub(crate) fn suggest_skills(&mut self, skills: &Skills, english_ids: &HashMap<String, ID>) {
+		for (name, level) in self.ailments.iter().filter_map(Ailment::counter) {
+			if let Some(skill) = english_ids.get(name).and_then(|id| skills.get(*id)) {
+				let level = level.min(skill.max_level);
+				if self.suggested.get_level(Arc::clone(skill)).is_none_or(|current| current < level) {
+					self.suggested.set(SkillLevel::new(Arc::clone(skill), level));
+				}
+			}
+		}
+	}
+
+	// Raise the constraints to the suggested levels, a higher requested level stays
+	pub fn apply_suggested(&self, constraints: &mut SkillsLevel) {
+		for skill in self.suggested.iter() {
+			if constraints.get_level(skill.get_skill()).is_none_or(|level| level < skill.get_level()) {
+				constraints.set(skill.clone());
+			}
+		}
+	}
+
+	// Element attack times the stars of the weakness. The hidden elements need Free Element, they do not count
+	pub fn weapon_score(&self, weapon: &Weapon) -> u32 {
+		if weapon.element_hidden {
+			return 0;
+		}
+		weapon.elements.iter().map(|(element, attack)| *attack as u32 * self.weakness(*element) as u32).sum()
+	}
+
+	// The weapons from the best against the monster, the ties by attack
+	pub fn rank_weapons<'a>(&self, weapons: &'a Weapons) -> Vec<&'a Arc<Weapon>> {
+		weapons.iter()
+			.sorted_by_key(|w| (Reverse(self.weapon_score(w)), Reverse(w.attack_true), w.id))
+			.collect()
+	}
+
+	// The first n of rank_weapons() that hit a weakness
+	pub fn best_weapons<'a>(&self, weapons: &'a Weapons, n: usize) -> Vec<&'a Arc<Weapon>> {
+		self.rank_weapons(weapons).into_iter()
+			.filter(|w| self.weapon_score(w) > 0)
+			.take(n)
+			.collect()
+	}
+
+	// "fire ★★★, dragon ★★", the strongest first
+	pub fn weaknesses_text(&self) -> String {
+		Element::iter()
+			.filter(|e| self.weakness(*e) > 0)
+			.sorted_by_key(|e| Reverse(self.weakness(*e)))
+			.map(|e| format!("{} {}", e, "★".repeat(self.weakness(e) as usize)))
+			.join(", ")
+	}
+}
+
+impl PartialEq for Monster {
+	fn eq(&self, other: &Self) -> bool {
+		self.id == other.id
+	}
+}
+impl Eq for Monster {}
+
+impl Hash for Monster {
+	fn hash<H: Hasher>(&self, state: &mut H) {
+		self.id.hash(state);
+	}
+}
+
+impl Entity for Monster {
+	fn id(&self) -> ID {
+		self.id
+	}
+	fn name(&self) -> &Localized {
+		&self.name
+	}
+}
+
+impl fmt::Display for Monster {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{} [{}]", self.name, self.id)?;
+		if self.weaknesses.iter().any(|stars| *stars > 0) {
+			write!(f, ", weak to {}", self.weaknesses_text())?;
+		}
+		if self.ailments.is_empty().not() {
+			write!(f, ", inflicts {}", self.ailments.iter().join(", "))?;
+		}
+		Ok(())
+	}
+}
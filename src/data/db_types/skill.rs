@@ -11,13 +11,13 @@ use std::{
 	},
 };
 use itertools::Itertools;
-use crate::data::db_types::{ID, Level};
+use crate::data::db_types::{ID, Level, id_map::Entity, localized::Localized};
 
 
 pub struct Skill {
 	pub id: ID,
-	pub name: String,
-	pub description: String,
+	pub name: Localized,
+	pub description: Localized,
 	pub max_level: u8,
 	pub secret: u8,
 	pub unlock: Option<Arc<Skill>>,
@@ -25,7 +25,7 @@ pub struct Skill {
 
 impl Skill {
 	pub fn new(id: ID, name: String, description: String, max_level: u8, secret: u8, unlock: Option<Arc<Skill>>) -> Self {
-		Skill { id, name, description, max_level, secret, unlock }
+		Skill { id, name: Localized::new(name), description: Localized::new(description), max_level, secret, unlock }
 	}
 }
 
@@ -43,6 +43,15 @@ impl Hash for Skill {
 	}
 }
 
+impl Entity for Skill {
+	fn id(&self) -> ID {
+		self.id
+	}
+	fn name(&self) -> &Localized {
+		&self.name
+	}
+}
+
 impl fmt::Display for Skill {
 	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
 		write!(f, "{} [{}]", self.name, self.id)
@@ -52,13 +61,13 @@ impl fmt::Display for Skill {
 // TODO Struct description
 pub struct SetSkill {
 	pub id: ID,
-	pub name: String,
+	pub name: Localized,
 	pub skills: SkillsLevel,  // This level indicate the require level of self for enabling the skill related in skills
 }
 
 impl SetSkill {
 	pub fn new(id: ID, name: String) -> Self {
-		SetSkill { id, name, skills: SkillsLevel::new() }
+		SetSkill { id, name: Localized::new(name), skills: SkillsLevel::new() }
 	}
 
 	pub fn add_skill(&mut self, skill: &Arc<Skill>, lev: u8) {
@@ -91,6 +100,15 @@ impl Hash for SetSkill {
 	}
 }
 
+impl Entity for SetSkill {
+	fn id(&self) -> ID {
+		self.id
+	}
+	fn name(&self) -> &Localized {
+		&self.name
+	}
+}
+
 /*
 SkillLevel is used to indicate the level/strength of the skill, generally used only in SkillsLevel
 */
@@ -3,20 +3,20 @@ use std::{
 };
 use std::hash::{Hash, Hasher};
 
-use crate::data::db_types::{ID, Item, MAX_SLOTS};
+use crate::data::db_types::{ID, Item, MAX_SLOTS, id_map::Entity, localized::Localized};
 use crate::data::db_types::skill::SkillsLevel;
 
 pub struct Tool {
-	pub(crate) id: ID,
-	pub name: String,
-	slots: [u8; MAX_SLOTS],
+	pub id: ID,
+	pub name: Localized,
+	pub(crate) slots: [u8; MAX_SLOTS],
 }
 
 impl Tool {
 	pub(crate) fn new(id: ID, name: String, slots: [u8; MAX_SLOTS]) -> Self {
 		Tool {
 			id,
-			name,
+			name: Localized::new(name),
 			slots,
 		}
 	}
@@ -48,6 +48,15 @@ impl Hash for Tool {
 		self.id.hash(state);
 	}
 }
+
+impl Entity for Tool {
+	fn id(&self) -> ID {
+		self.id
+	}
+	fn name(&self) -> &Localized {
+		&self.name
+	}
+}
 impl fmt::Display for Tool {
 	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
 		write!(f, "{0: <45}", self.name)
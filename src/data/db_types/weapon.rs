@@ -1,34 +1,143 @@
 use std::{
     fmt,
+    str::FromStr,
     sync::Arc,
 	hash::{Hash, Hasher},
 };
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
 use crate::data::db_types::{
 	ID, MAX_SLOTS, SHARPNESS_LEVELS, ElderSeal, Element, WeaponClass, Item, Slot,
+	id_map::Entity,
+	localized::Localized,
 	skill::{SetSkill, SkillsLevel},
 };
 
 #[allow(dead_code)]
 pub struct Weapon {
 	pub id: ID,
-	previous_id: Option<ID>,
+	pub(crate) previous_id: Option<ID>,
 	pub class: WeaponClass,
-	pub name: String,
+	pub name: Localized,
 	pub attack_true: u16,
-	affinity: i8,
-	sharpness: Option<[u8; SHARPNESS_LEVELS]>,
-	defense: u8,
-	elements: Vec<(Element, u16)>,
-	element_hidden: bool,
-	elderseal: ElderSeal,
+	pub(crate) affinity: i8,
+	pub(crate) sharpness: Option<[u8; SHARPNESS_LEVELS]>,
+	pub(crate) defense: u8,
+	pub(crate) elements: Vec<(Element, u16)>,
+	pub(crate) element_hidden: bool,
+	pub(crate) elderseal: ElderSeal,
 	pub skill: SkillsLevel,
 	pub slots: [Slot; MAX_SLOTS],
-	armorset_skill: Option<Arc<SetSkill>>,
+	pub(crate) armorset_skill: Option<Arc<SetSkill>>,
+	pub class_data: ClassData,
+}
+
+// One ammo type of a bowgun
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct Ammo {
+	pub name: String,  // As in the MHWorldData columns: normal1, spread2, flaming...
+	pub clip: u8,
+	pub rapid: bool,
+	pub recoil: Option<u8>,
+	pub reload: String,
+}
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct AmmoTable {
+	pub deviation: String,
+	pub special: String,
+	pub ammo: Vec<Ammo>,  // Only the usable ones
+}
+
+// The mechanics of the weapon class, with the names MHWorldData uses
+#[derive(Clone, PartialEq, Serialize, Deserialize, Default)]
+pub enum ClassData {
+	#[default]
+	None,
+	Phial { kind: String, power: Option<u16> },  // Switch axe and charge blade
+	Shelling { kind: String, level: u8 },  // Gunlance
+	Kinsect { bonus: String },  // Insect glaive
+	Notes(String),  // Hunting horn, one letter for each note color
+	Coatings(Vec<String>),  // Bow
+	Ammo(Box<AmmoTable>),  // Light and heavy bowgun
+}
+
+impl fmt::Display for ClassData {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			ClassData::None => Ok(()),
+			ClassData::Phial { kind, power: Some(power) } => write!(f, "{} {} phial", kind, power),
+			ClassData::Phial { kind, power: None } => write!(f, "{} phial", kind),
+			ClassData::Shelling { kind, level } => write!(f, "{} shelling lv{}", kind, level),
+			ClassData::Kinsect { bonus } => write!(f, "{} kinsect bonus", bonus),
+			ClassData::Notes(notes) => write!(f, "notes {}", notes),
+			ClassData::Coatings(coatings) => write!(f, "coatings {}", coatings.join(", ")),
+			ClassData::Ammo(table) => {
+				write!(f, "deviation {}, special {}, ammo ", table.deviation, table.special)?;
+				write!(f, "{}", table.ammo.iter().map(|a| format!("{} x{}{}", a.name, a.clip, if a.rapid { " rapid" } else { "" })).join(", "))
+			}
+		}
+	}
+}
+
+/*
+A search filter on the class data, written as <kind>:<value>:
+phial:<type>, shelling:<type>[:<min level>], kinsect:<bonus>, notes:<letters>, coating:<name>, ammo:<name>.
+Weapons of other classes never match.
+*/
+#[derive(Clone, PartialEq)]
+pub enum WeaponFeature {
+	Phial(String),
+	Shelling(String, u8),
+	Kinsect(String),
+	Notes(String),  // Every one of these notes
+	Coating(String),
+	Ammo(String),
+}
+
+impl FromStr for WeaponFeature {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let s = s.to_lowercase();
+		let (kind, value) = s.split_once(':').ok_or(format!("Expected <kind>:<value>, found {}", s))?;
+		let value = value.trim().to_string();
+		match kind.trim() {
+			"phial" => Ok(WeaponFeature::Phial(value)),
+			"shelling" => match value.split_once(':') {
+				Some((kind, level)) => Ok(WeaponFeature::Shelling(kind.to_string(), level.parse().map_err(|_| format!("Invalid shelling level {}", level))?)),
+				None => Ok(WeaponFeature::Shelling(value, 0)),
+			},
+			"kinsect" => Ok(WeaponFeature::Kinsect(value)),
+			"notes" => Ok(WeaponFeature::Notes(value)),
+			"coating" => Ok(WeaponFeature::Coating(value)),
+			"ammo" => Ok(WeaponFeature::Ammo(value)),
+			_ => Err(format!("Unknown weapon feature {}", kind)),
+		}
+	}
+}
+
+impl ClassData {
+	pub fn has(&self, feature: &WeaponFeature) -> bool {
+		match (self, feature) {
+			(ClassData::Phial { kind, .. }, WeaponFeature::Phial(wanted)) => kind.eq_ignore_ascii_case(wanted),
+			(ClassData::Shelling { kind, level }, WeaponFeature::Shelling(wanted, min_level)) =>
+				(wanted.is_empty() || kind.eq_ignore_ascii_case(wanted)) && level >= min_level,
+			(ClassData::Kinsect { bonus }, WeaponFeature::Kinsect(wanted)) => bonus.eq_ignore_ascii_case(wanted),
+			(ClassData::Notes(notes), WeaponFeature::Notes(wanted)) => {
+				let notes = notes.to_lowercase();
+				wanted.chars().all(|note| notes.contains(note))
+			}
+			(ClassData::Coatings(coatings), WeaponFeature::Coating(wanted)) => coatings.iter().any(|c| c.eq_ignore_ascii_case(wanted)),
+			(ClassData::Ammo(table), WeaponFeature::Ammo(wanted)) => table.ammo.iter().any(|a| a.name.eq_ignore_ascii_case(wanted)),
+			_ => false,
+		}
+	}
 }
 
 impl Weapon {
 	pub fn new(id: ID, previous_id: Option<ID>, class: WeaponClass, name: String, attack_true: u16, affinity: i8, sharpness: Option<[u8; 7]>, defense: u8, slots: [u8; 3], elements: Vec<(Element, u16)>, element_hidden: bool, elderseal: ElderSeal, armorset_bonus_id: Option<Arc<SetSkill>>, skill: SkillsLevel) -> Self {
-		Weapon { id, previous_id, class, name, attack_true, affinity, sharpness, defense, slots, elements, element_hidden, elderseal, armorset_skill: armorset_bonus_id, skill }
+		Weapon { id, previous_id, class, name: Localized::new(name), attack_true, affinity, sharpness, defense, slots, elements, element_hidden, elderseal, armorset_skill: armorset_bonus_id, skill, class_data: ClassData::None }
 	}
 }
 
@@ -54,6 +163,15 @@ impl Hash for Weapon {
 	}
 }
 
+impl Entity for Weapon {
+	fn id(&self) -> ID {
+		self.id
+	}
+	fn name(&self) -> &Localized {
+		&self.name
+	}
+}
+
 impl fmt::Display for Weapon {
 	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
 		write!(f, "{0: <45}|{1: <45}", format!("{} [{}]", self.name, self.id), self.skill.to_string())
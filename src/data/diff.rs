@@ -0,0 +1,250 @@
+use std::fmt;
+use itertools::Itertools;
+use serde::Serialize;
+use crate::data::{
+	db_storage::Storage,
+	db_types::{
+		ElderSeal, ID,
+		armor::Armor,
+		charm::Charm,
+		decoration::Decoration,
+		id_map::{Entity, IdMap},
+		monster::Monster,
+		skill::{Skill, SkillsLevel},
+		weapon::Weapon,
+	},
+};
+
+/*
+What changed between two storages, usually loaded from two versions of mhw.db in the same language.
+The entities are matched by id, an entity is changed when one of its fields() is different.
+*/
+#[derive(Serialize)]
+pub struct Change {
+	pub field: &'static str,
+	pub old: String,
+	pub new: String,
+}
+
+#[derive(Serialize)]
+pub struct EntityDiff {
+	pub id: ID,
+	pub name: String,
+	#[serde(skip_serializing_if = "Vec::is_empty")]
+	pub changes: Vec<Change>,
+}
+
+#[derive(Serialize, Default)]
+pub struct CollectionDiff {
+	pub added: Vec<EntityDiff>,
+	pub removed: Vec<EntityDiff>,
+	pub changed: Vec<EntityDiff>,
+}
+
+#[derive(Serialize)]
+pub struct StorageDiff {
+	pub skills: CollectionDiff,
+	pub armors: CollectionDiff,
+	pub decorations: CollectionDiff,
+	pub charms: CollectionDiff,
+	pub weapons: CollectionDiff,
+	pub monsters: CollectionDiff,
+}
+
+// The compared fields of an entity, as text
+trait Fields: Entity {
+	fn fields(&self) -> Vec<(&'static str, String)>;
+}
+
+// Sorted by name, the order of SkillsLevel is not stable
+fn skills(skills: &SkillsLevel) -> String {
+	skills.iter().map(|s| format!("{} {}", s.get_skill().name, s.get_level())).sorted().join(", ")
+}
+
+fn list<T: ToString>(values: &[T]) -> String {
+	values.iter().map(T::to_string).join(", ")
+}
+
+fn optional<T: ToString>(value: Option<T>) -> String {
+	value.map_or_else(String::new, |v| v.to_string())
+}
+
+impl Fields for Skill {
+	fn fields(&self) -> Vec<(&'static str, String)> {
+		vec![
+			("name", self.name.to_string()),
+			("description", self.description.to_string()),
+			("max_level", self.max_level.to_string()),
+			("secret", self.secret.to_string()),
+			("unlock", optional(self.unlock.as_ref().map(|s| s.name.get()))),
+		]
+	}
+}
+
+impl Fields for Armor {
+	fn fields(&self) -> Vec<(&'static str, String)> {
+		vec![
+			("name", self.name.to_string()),
+			("class", self.class.to_string()),
+			("rank", self.rank.to_string()),
+			("gender", self.gender.to_string()),
+			("slots", list(&self.slots)),
+			("defense", list(&self.defence)),
+			("elements", list(&self.elements)),
+			("skills", skills(&self.skills)),
+			("set_skill", optional(self.set_skill.as_ref().map(|s| s.name.get()))),
+		]
+	}
+}
+
+impl Fields for Decoration {
+	fn fields(&self) -> Vec<(&'static str, String)> {
+		vec![
+			("name", self.name.to_string()),
+			("size", self.size.to_string()),
+			("skills", skills(&self.skills)),
+		]
+	}
+}
+
+impl Fields for Charm {
+	fn fields(&self) -> Vec<(&'static str, String)> {
+		vec![
+			("name", self.name.to_string()),
+			("previous", optional(self.previous_id)),
+			("skills", skills(&self.skills)),
+		]
+	}
+}
+
+impl Fields for Weapon {
+	fn fields(&self) -> Vec<(&'static str, String)> {
+		let elderseal = match self.elderseal {
+			ElderSeal::Empty => "",
+			ElderSeal::Low => "low",
+			ElderSeal::Average => "average",
+			ElderSeal::High => "high",
+		};
+		vec![
+			("name", self.name.to_string()),
+			("class", self.class.to_string()),
+			("previous", optional(self.previous_id)),
+			("attack", self.attack_true.to_string()),
+			("affinity", self.affinity.to_string()),
+			("sharpness", optional(self.sharpness.map(|s| list(&s)))),
+			("defense", self.defense.to_string()),
+			("slots", list(&self.slots)),
+			("elements", self.elements.iter().map(|(e, attack)| format!("{} {}", e, attack)).join(", ")),
+			("element_hidden", self.element_hidden.to_string()),
+			("elderseal", elderseal.to_string()),
+			("skills", skills(&self.skill)),
+			("set_skill", optional(self.armorset_skill.as_ref().map(|s| s.name.get()))),
+			("class_data", self.class_data.to_string()),
+		]
+	}
+}
+
+impl Fields for Monster {
+	fn fields(&self) -> Vec<(&'static str, String)> {
+		vec![
+			("name", self.name.to_string()),
+			("large", self.large.to_string()),
+			("weaknesses", self.weaknesses_text()),
+			("ailments", list(&self.ailments)),
+			("suggested", skills(&self.suggested)),
+			("hitzones", self.hitzones.iter().map(|h| h.to_string()).join("; ")),
+		]
+	}
+}
+
+fn entity<T: Fields>(item: &T, changes: Vec<Change>) -> EntityDiff {
+	EntityDiff { id: item.id(), name: item.name().to_string(), changes }
+}
+
+fn diff_collection<T: Fields>(old: &IdMap<T>, new: &IdMap<T>) -> CollectionDiff {
+	let mut ret = CollectionDiff::default();
+	for item in new.iter() {
+		match old.get(item.id()) {
+			None => ret.added.push(entity(item.as_ref(), Vec::new())),
+			Some(old_item) => {
+				let changes: Vec<Change> = old_item.fields().into_iter().zip(item.fields())
+					.filter(|((_, old), (_, new))| old != new)
+					.map(|((field, old), (_, new))| Change { field, old, new })
+					.collect();
+				if !changes.is_empty() {
+					ret.changed.push(entity(item.as_ref(), changes));
+				}
+			}
+		}
+	}
+	ret.removed = old.iter().filter(|item| !new.contains(item.id())).map(|item| entity(item.as_ref(), Vec::new())).collect();
+	for entities in [&mut ret.added, &mut ret.removed, &mut ret.changed] {
+		entities.sort_by_key(|e| e.id);
+	}
+	ret
+}
+
+impl CollectionDiff {
+	pub fn is_empty(&self) -> bool {
+		self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+	}
+}
+
+impl StorageDiff {
+	pub fn new(old: &Storage, new: &Storage) -> Self {
+		StorageDiff {
+			skills: diff_collection(&old.skills, &new.skills),
+			armors: diff_collection(&old.armors, &new.armors),
+			decorations: diff_collection(&old.decorations, &new.decorations),
+			charms: diff_collection(&old.charms, &new.charms),
+			weapons: diff_collection(&old.weapons, &new.weapons),
+			monsters: diff_collection(&old.monsters, &new.monsters),
+		}
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.collections().iter().all(|(_, diff)| diff.is_empty())
+	}
+
+	fn collections(&self) -> [(&'static str, &CollectionDiff); 6] {
+		[
+			("Skills", &self.skills),
+			("Armors", &self.armors),
+			("Decorations", &self.decorations),
+			("Charms", &self.charms),
+			("Weapons", &self.weapons),
+			("Monsters", &self.monsters),
+		]
+	}
+}
+
+/*
+Skills: 1 added, 0 removed, 1 changed
+	+ 120 Foo
+	~ 5 Attack Boost
+		max_level: 7 -> 8
+*/
+impl fmt::Display for StorageDiff {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		if self.is_empty() {
+			return write!(f, "No changes");
+		}
+		let mut first = true;
+		for (title, diff) in self.collections().iter().filter(|(_, diff)| !diff.is_empty()) {
+			if !first {
+				writeln!(f)?;
+			}
+			first = false;
+			write!(f, "{}: {} added, {} removed, {} changed", title, diff.added.len(), diff.removed.len(), diff.changed.len())?;
+			for (sign, entities) in [("+", &diff.added), ("-", &diff.removed), ("~", &diff.changed)] {
+				for e in entities.iter() {
+					write!(f, "\n\t{} {} {}", sign, e.id, e.name)?;
+					for change in e.changes.iter() {
+						write!(f, "\n\t\t{}: {} -> {}", change.field, change.old, change.new)?;
+					}
+				}
+			}
+		}
+		Ok(())
+	}
+}
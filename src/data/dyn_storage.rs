@@ -15,6 +15,7 @@ use crate::data::{
 };
 
 pub struct DynamicStorage {
+	skills: Skills,
 	skills_constraints: RefCell<SkillsLevel>,
 	quantity_decorations: RefCell<HashMap<Arc<Decoration>, u8>>,
 	usable_weapons: RefCell<HashMap<Arc<Weapon>, bool>>,
@@ -27,6 +28,7 @@ impl DynamicStorage {
 	pub fn new(storage: &Storage) -> Self {
 		let dynamic_storage =
 			DynamicStorage {
+				skills: storage.skills.clone(),
 				skills_constraints: RefCell::new(SkillsLevel::new()),
 				quantity_decorations: Default::default(),
 				usable_weapons: Default::default(),
@@ -115,14 +117,18 @@ impl DynamicStorage {
 	// TODO return a new type of storage
 	pub fn generate_storage(&self) -> Storage {
 		let mut storage = Storage {
-			skills: Default::default(),
+			skills: self.skills.clone(),
 			set_skills: Default::default(),
 			armors: Default::default(),
 			sets: Default::default(),
 			decorations: Default::default(),
 			charms: Default::default(),
 			weapons: Default::default(),
-			tools: Default::default()
+			tools: Default::default(),
+			monsters: Default::default(),
+			damage_skills: Default::default(),
+			materials: Default::default(),
+			recipes: Default::default(),
 		};
 		// TODO Not used quantity
 		for (decoration, quantity) in self.quantity_decorations.borrow().iter() {
@@ -130,6 +136,11 @@ impl DynamicStorage {
 				storage.decorations.insert(Arc::clone(decoration));
 			}
 		}
+		for (weapon, insert) in self.usable_weapons.borrow().iter() {
+			if *insert {
+				storage.weapons.insert(Arc::clone(weapon));
+			}
+		}
 		for (armor, insert) in self.usable_armors.borrow().iter() {
 			if *insert {
 				storage.armors.insert(Arc::clone(armor));
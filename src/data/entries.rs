@@ -0,0 +1,542 @@
+use std::{
+	collections::HashMap,
+	sync::Arc,
+};
+use serde::{Deserialize, Serialize};
+use strum::EnumCount;
+use crate::data::{
+	damage::{DamageSkill, DamageSkills, resolve_damage_skills},
+	db::{DataError, LoadReport},
+	db_storage::Storage,
+	db_types::{
+		ArmorClass, ArmorRank, Armors, ArmorSets, Charms, Decorations, ElderSeal, Element, Gender, ID, Level,
+		MAX_SLOTS, Materials, Monsters, Recipes, SHARPNESS_LEVELS, SetSkills, Skills, Slot, Tools, WeaponClass, Weapons,
+		armor::{Armor, ArmorSet},
+		charm::Charm,
+		decoration::Decoration,
+		localized::Localized,
+		material::{Material, RecipeKind},
+		monster::{Ailment, Hitzone, Monster},
+		skill::{SetSkill, Skill, SkillLevel, SkillsLevel},
+		tool::Tool,
+		weapon::{ClassData, Weapon},
+	},
+	source::DataSource,
+};
+
+/*
+Plain copies of the entities, the references to other entities are ids.
+Written by the cache and by hand in the files of a FileSource, the fields with a default can be left out.
+*/
+pub type Levels = Vec<(ID, Level)>;
+
+#[derive(Serialize, Deserialize)]
+pub struct SkillEntry {
+	pub id: ID,
+	pub name: String,
+	#[serde(default)]
+	pub description: String,
+	pub max_level: Level,
+	#[serde(default)]
+	pub secret: u8,
+	#[serde(default)]
+	pub unlock: Option<ID>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct SetSkillEntry {
+	pub id: ID,
+	pub name: String,
+	#[serde(default)]
+	pub skills: Levels,
+}
+
+fn all_genders() -> Gender {
+	Gender::All
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ArmorEntry {
+	pub id: ID,
+	pub name: String,
+	pub class: ArmorClass,
+	pub rank: ArmorRank,
+	#[serde(default = "all_genders")]
+	pub gender: Gender,
+	#[serde(default)]
+	pub slots: [Slot; MAX_SLOTS],
+	#[serde(default)]
+	pub defence: [u8; 3],
+	#[serde(default)]
+	pub elements: [i8; Element::len()],
+	#[serde(default)]
+	pub skills: Levels,
+	#[serde(default)]
+	pub set_skill: Option<ID>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ArmorSetEntry {
+	pub id: ID,
+	pub name: String,
+	pub rank: ArmorRank,
+	#[serde(default)]
+	pub armors: Vec<ID>,
+	#[serde(default)]
+	pub set_skill: Option<ID>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct CharmEntry {
+	pub id: ID,
+	pub name: String,
+	#[serde(default)]
+	pub skills: Levels,
+	#[serde(default)]
+	pub previous_id: Option<ID>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct DecorationEntry {
+	pub id: ID,
+	pub name: String,
+	pub size: Slot,
+	#[serde(default)]
+	pub skills: Levels,
+}
+
+fn no_elderseal() -> ElderSeal {
+	ElderSeal::Empty
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct WeaponEntry {
+	pub id: ID,
+	#[serde(default)]
+	pub previous_id: Option<ID>,
+	pub class: WeaponClass,
+	pub name: String,
+	pub attack_true: u16,
+	#[serde(default)]
+	pub affinity: i8,
+	#[serde(default)]
+	pub sharpness: Option<[u8; SHARPNESS_LEVELS]>,
+	#[serde(default)]
+	pub defense: u8,
+	#[serde(default)]
+	pub slots: [Slot; MAX_SLOTS],
+	#[serde(default)]
+	pub elements: Vec<(Element, u16)>,
+	#[serde(default)]
+	pub element_hidden: bool,
+	#[serde(default = "no_elderseal")]
+	pub elderseal: ElderSeal,
+	#[serde(default)]
+	pub skills: Levels,
+	#[serde(default)]
+	pub set_skill: Option<ID>,
+	#[serde(default)]
+	pub class_data: ClassData,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ToolEntry {
+	pub id: ID,
+	pub name: String,
+	#[serde(default)]
+	pub slots: [Slot; MAX_SLOTS],
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct MonsterEntry {
+	pub id: ID,
+	pub name: String,
+	#[serde(default)]
+	pub large: bool,
+	#[serde(default)]
+	pub weaknesses: [u8; Element::COUNT],
+	#[serde(default)]
+	pub ailments: Vec<Ailment>,
+	#[serde(default)]
+	pub suggested: Levels,
+	#[serde(default)]
+	pub hitzones: Vec<HitzoneEntry>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct HitzoneEntry {
+	pub id: ID,
+	pub name: String,
+	#[serde(default)]
+	pub cut: u8,
+	#[serde(default)]
+	pub impact: u8,
+	#[serde(default)]
+	pub shot: u8,
+	#[serde(default)]
+	pub elements: [u8; Element::len()],
+	#[serde(default)]
+	pub ko: u8,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct MaterialEntry {
+	pub id: ID,
+	pub name: String,
+	#[serde(default)]
+	pub rarity: u8,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct RecipeEntry {
+	pub kind: RecipeKind,
+	pub id: ID,  // Of the armor, charm or weapon
+	pub materials: Vec<(ID, u8)>,
+}
+
+/*
+Every entity of a Storage as entries. It is also the in-memory DataSource of the tests:
+Entries::default().skill(1, "Attack Boost", 7).armor(1, "Helm", ArmorClass::Head, ArmorRank::Master, [1, 0, 0], &[(1, 2)])
+*/
+#[derive(Default, Serialize, Deserialize)]
+pub struct Entries {
+	#[serde(default)]
+	pub skills: Vec<SkillEntry>,
+	#[serde(default)]
+	pub set_skills: Vec<SetSkillEntry>,
+	#[serde(default)]
+	pub armors: Vec<ArmorEntry>,
+	#[serde(default)]
+	pub sets: Vec<ArmorSetEntry>,
+	#[serde(default)]
+	pub charms: Vec<CharmEntry>,
+	#[serde(default)]
+	pub decorations: Vec<DecorationEntry>,
+	#[serde(default)]
+	pub weapons: Vec<WeaponEntry>,
+	#[serde(default)]
+	pub tools: Vec<ToolEntry>,
+	#[serde(default)]
+	pub monsters: Vec<MonsterEntry>,
+	#[serde(default)]
+	pub damage_skills: Vec<(DamageSkill, ID)>,
+	#[serde(default)]
+	pub materials: Vec<MaterialEntry>,
+	#[serde(default)]
+	pub recipes: Vec<RecipeEntry>,
+}
+
+fn levels(skills: &SkillsLevel) -> Levels {
+	skills.iter().map(|s| (s.get_id(), s.get_level())).collect()
+}
+
+// The skills of table id, the missing ones are reported and left out
+fn skills_level(levels: &[(ID, Level)], skills: &Skills, table: &'static str, id: ID, report: &mut LoadReport) -> SkillsLevel {
+	let mut ret = SkillsLevel::new();
+	for (skill_id, level) in levels.iter() {
+		if let Some(skill) = report.reference(skills.get(*skill_id), table, id, "skilltree", *skill_id) {
+			ret.insert(SkillLevel::new(Arc::clone(skill), *level));
+		}
+	}
+	ret
+}
+
+impl Entries {
+	pub fn new(storage: &Storage) -> Self {
+		Entries {
+			skills: storage.skills.iter().map(|s| SkillEntry {
+				id: s.id,
+				name: s.name.get().to_string(),
+				description: s.description.get().to_string(),
+				max_level: s.max_level,
+				secret: s.secret,
+				unlock: s.unlock.as_ref().map(|u| u.id),
+			}).collect(),
+			set_skills: storage.set_skills.iter().map(|s| SetSkillEntry {
+				id: s.id,
+				name: s.name.get().to_string(),
+				skills: levels(&s.skills),
+			}).collect(),
+			armors: storage.armors.iter().map(|a| ArmorEntry {
+				id: a.id,
+				name: a.name.get().to_string(),
+				class: a.class,
+				rank: a.rank,
+				gender: a.gender,
+				slots: a.slots,
+				defence: a.defence,
+				elements: a.elements,
+				skills: levels(&a.skills),
+				set_skill: a.set_skill.as_ref().map(|s| s.id),
+			}).collect(),
+			sets: storage.sets.iter().map(|s| ArmorSetEntry {
+				id: s.id,
+				name: s.name.get().to_string(),
+				rank: s.rank,
+				armors: s.armors.iter().flatten().map(|a| a.id).collect(),
+				set_skill: s.armorset_skill.as_ref().map(|s| s.id),
+			}).collect(),
+			charms: storage.charms.iter().map(|c| CharmEntry {
+				id: c.id,
+				name: c.name.get().to_string(),
+				skills: levels(&c.skills),
+				previous_id: c.previous_id,
+			}).collect(),
+			decorations: storage.decorations.iter().map(|d| DecorationEntry {
+				id: d.id,
+				name: d.name.get().to_string(),
+				size: d.size,
+				skills: levels(&d.skills),
+			}).collect(),
+			weapons: storage.weapons.iter().map(|w| WeaponEntry {
+				id: w.id,
+				previous_id: w.previous_id,
+				class: w.class,
+				name: w.name.get().to_string(),
+				attack_true: w.attack_true,
+				affinity: w.affinity,
+				sharpness: w.sharpness,
+				defense: w.defense,
+				slots: w.slots,
+				elements: w.elements.clone(),
+				element_hidden: w.element_hidden,
+				elderseal: w.elderseal,
+				skills: levels(&w.skill),
+				set_skill: w.armorset_skill.as_ref().map(|s| s.id),
+				class_data: w.class_data.clone(),
+			}).collect(),
+			tools: storage.tools.iter().map(|t| ToolEntry {
+				id: t.id,
+				name: t.name.get().to_string(),
+				slots: t.slots,
+			}).collect(),
+			monsters: storage.monsters.iter().map(|m| MonsterEntry {
+				id: m.id,
+				name: m.name.get().to_string(),
+				large: m.large,
+				weaknesses: m.weaknesses,
+				ailments: m.ailments.clone(),
+				suggested: levels(&m.suggested),
+				hitzones: m.hitzones.iter().map(|h| HitzoneEntry {
+					id: h.id,
+					name: h.name.get().to_string(),
+					cut: h.cut,
+					impact: h.impact,
+					shot: h.shot,
+					elements: h.elements,
+					ko: h.ko,
+				}).collect(),
+			}).collect(),
+			damage_skills: storage.damage_skills.iter().map(|(which, skill)| (*which, skill.id)).collect(),
+			materials: storage.materials.iter().map(|m| MaterialEntry {
+				id: m.id,
+				name: m.name.get().to_string(),
+				rarity: m.rarity,
+			}).collect(),
+			recipes: storage.recipes.iter().map(|((kind, id), recipe)| RecipeEntry {
+				kind: *kind,
+				id: *id,
+				materials: recipe.iter().map(|(m, quantity)| (m.id, *quantity)).collect(),
+			}).collect(),
+		}
+	}
+
+	// Builder, the fields left out take their default
+	pub fn skill(mut self, id: ID, name: &str, max_level: Level) -> Self {
+		self.skills.push(SkillEntry { id, name: name.to_string(), description: String::new(), max_level, secret: 0, unlock: None });
+		self
+	}
+
+	pub fn set_skill(mut self, id: ID, name: &str, skills: &[(ID, Level)]) -> Self {
+		self.set_skills.push(SetSkillEntry { id, name: name.to_string(), skills: skills.to_vec() });
+		self
+	}
+
+	pub fn armor(mut self, id: ID, name: &str, class: ArmorClass, rank: ArmorRank, slots: [Slot; MAX_SLOTS], skills: &[(ID, Level)]) -> Self {
+		self.armors.push(ArmorEntry {
+			id, name: name.to_string(), class, rank, gender: Gender::All, slots,
+			defence: [0; 3], elements: [0; Element::len()], skills: skills.to_vec(), set_skill: None,
+		});
+		self
+	}
+
+	pub fn charm(mut self, id: ID, name: &str, skills: &[(ID, Level)]) -> Self {
+		self.charms.push(CharmEntry { id, name: name.to_string(), skills: skills.to_vec(), previous_id: None });
+		self
+	}
+
+	pub fn decoration(mut self, id: ID, name: &str, size: Slot, skills: &[(ID, Level)]) -> Self {
+		self.decorations.push(DecorationEntry { id, name: name.to_string(), size, skills: skills.to_vec() });
+		self
+	}
+
+	pub fn weapon(mut self, id: ID, name: &str, class: WeaponClass, attack_true: u16, slots: [Slot; MAX_SLOTS], skills: &[(ID, Level)]) -> Self {
+		self.weapons.push(WeaponEntry {
+			id, previous_id: None, class, name: name.to_string(), attack_true, affinity: 0, sharpness: None, defense: 0, slots,
+			elements: Vec::new(), element_hidden: false, elderseal: ElderSeal::Empty, skills: skills.to_vec(), set_skill: None,
+			class_data: ClassData::None,
+		});
+		self
+	}
+
+	pub fn material(mut self, id: ID, name: &str) -> Self {
+		self.materials.push(MaterialEntry { id, name: name.to_string(), rarity: 0 });
+		self
+	}
+
+	pub fn recipe(mut self, kind: RecipeKind, id: ID, materials: &[(ID, u8)]) -> Self {
+		self.recipes.push(RecipeEntry { kind, id, materials: materials.to_vec() });
+		self
+	}
+}
+
+impl DataSource for Entries {
+	fn load_skills(&self, skills: &mut Skills, report: &mut LoadReport) -> Result<(), DataError> {
+		for s in self.skills.iter() {
+			let unlock = s.unlock
+				.and_then(|unlock| report.reference(skills.get(unlock), "skilltree", s.id, "skilltree", unlock))
+				.map(Arc::clone);
+			skills.insert(Arc::new(Skill::new(s.id, s.name.clone(), s.description.clone(), s.max_level, s.secret, unlock)));
+		}
+		report.expect_rows("skilltree", skills.len());
+		Ok(())
+	}
+
+	fn load_set_skills(&self, set_skills: &mut SetSkills, skills: &Skills, report: &mut LoadReport) -> Result<(), DataError> {
+		for s in self.set_skills.iter() {
+			let mut set_skill = SetSkill::new(s.id, s.name.clone());
+			set_skill.skills = skills_level(&s.skills, skills, "armorset_bonus", s.id, report);
+			set_skills.insert(Arc::new(set_skill));
+		}
+		report.expect_rows("armorset_bonus_skill", set_skills.len());
+		Ok(())
+	}
+
+	fn load_armors(&self, armors: &mut Armors, skills: &Skills, set_skills: &SetSkills, report: &mut LoadReport) -> Result<(), DataError> {
+		for a in self.armors.iter() {
+			let mut armor = Armor::new(a.id, a.name.clone(), a.class, a.rank, a.gender, a.slots, a.defence, a.elements);
+			armor.skills = skills_level(&a.skills, skills, "armor", a.id, report);
+			if let Some(set_skill) = a.set_skill.and_then(|s| report.reference(set_skills.get(s), "armor", a.id, "armorset_bonus", s)) {
+				armor.add_setskill(set_skill);
+			}
+			armors.insert(Arc::new(armor));
+		}
+		report.expect_rows("armor", armors.len());
+		Ok(())
+	}
+
+	fn load_sets(&self, sets: &mut ArmorSets, armors: &Armors, set_skills: &SetSkills, report: &mut LoadReport) -> Result<(), DataError> {
+		for s in self.sets.iter() {
+			let set_skill = s.set_skill
+				.and_then(|skill| report.reference(set_skills.get(skill), "armorset", s.id, "armorset_bonus", skill))
+				.map(Arc::clone);
+			let mut set = ArmorSet::new(s.id, s.name.clone(), s.rank, set_skill);
+			for armor_id in s.armors.iter() {
+				if let Some(armor) = report.reference(armors.get(*armor_id), "armorset", s.id, "armor", *armor_id) {
+					if let Err(taken) = set.add_armor(armor) {
+						report.push(DataError::SlotTaken { set: s.id, slot: armor.class.to_string(), armors: vec![taken, *armor_id] });
+					}
+				}
+			}
+			sets.insert(Arc::new(set));
+		}
+		report.expect_rows("armorset", sets.len());
+		Ok(())
+	}
+
+	fn load_charms(&self, charms: &mut Charms, skills: &Skills, report: &mut LoadReport) -> Result<(), DataError> {
+		for c in self.charms.iter() {
+			let mut charm = Charm::new(c.id, c.name.clone());
+			charm.skills = skills_level(&c.skills, skills, "charm", c.id, report);
+			charm.previous_id = c.previous_id;
+			charms.insert(Arc::new(charm));
+		}
+		report.expect_rows("charm", charms.len());
+		Ok(())
+	}
+
+	fn load_decorations(&self, decorations: &mut Decorations, skills: &Skills, report: &mut LoadReport) -> Result<(), DataError> {
+		for d in self.decorations.iter() {
+			let deco_skills = skills_level(&d.skills, skills, "decoration", d.id, report);
+			decorations.insert(Arc::new(Decoration::new(d.id, d.name.clone(), d.size, deco_skills)));
+		}
+		report.expect_rows("decoration", decorations.len());
+		Ok(())
+	}
+
+	fn load_weapons(&self, weapons: &mut Weapons, skills: &Skills, set_skills: &SetSkills, report: &mut LoadReport) -> Result<(), DataError> {
+		for w in self.weapons.iter() {
+			let weapon_skills = skills_level(&w.skills, skills, "weapon", w.id, report);
+			let set_skill = w.set_skill
+				.and_then(|skill| report.reference(set_skills.get(skill), "weapon", w.id, "armorset_bonus", skill))
+				.map(Arc::clone);
+			let mut weapon = Weapon::new(w.id, w.previous_id, w.class, w.name.clone(),
+				w.attack_true, w.affinity, w.sharpness, w.defense,
+				w.slots, w.elements.clone(), w.element_hidden, w.elderseal,
+				set_skill, weapon_skills,
+			);
+			weapon.class_data = w.class_data.clone();
+			weapons.insert(Arc::new(weapon));
+		}
+		report.expect_rows("weapon", weapons.len());
+		Ok(())
+	}
+
+	fn load_tools(&self, tools: &mut Tools, _report: &mut LoadReport) -> Result<(), DataError> {
+		for t in self.tools.iter() {
+			tools.insert(Arc::new(Tool::new(t.id, t.name.clone(), t.slots)));
+		}
+		Ok(())
+	}
+
+	fn load_monsters(&self, monsters: &mut Monsters, skills: &Skills, report: &mut LoadReport) -> Result<(), DataError> {
+		for m in self.monsters.iter() {
+			let mut monster = Monster::new(m.id, m.name.clone(), m.large);
+			monster.weaknesses = m.weaknesses;
+			monster.ailments = m.ailments.clone();
+			monster.suggested = skills_level(&m.suggested, skills, "monster", m.id, report);
+			monster.hitzones = m.hitzones.iter().map(|h| Hitzone {
+				id: h.id,
+				name: Localized::new(h.name.clone()),
+				cut: h.cut,
+				impact: h.impact,
+				shot: h.shot,
+				elements: h.elements,
+				ko: h.ko,
+			}).collect();
+			monsters.insert(Arc::new(monster));
+		}
+		report.expect_rows("monster", monsters.len());
+		Ok(())
+	}
+
+	// Without entries the skills are found by name, the names of a FileSource are usually English
+	fn load_damage_skills(&self, damage_skills: &mut DamageSkills, skills: &Skills, report: &mut LoadReport) -> Result<(), DataError> {
+		if self.damage_skills.is_empty() {
+			let english_ids: HashMap<String, ID> = skills.iter().map(|s| (s.name.get().to_string(), s.id)).collect();
+			resolve_damage_skills(damage_skills, skills, &english_ids);
+		}
+		for (which, id) in self.damage_skills.iter() {
+			if let Some(skill) = report.reference(skills.get(*id), "damage_skills", *id, "skilltree", *id) {
+				damage_skills.insert(*which, Arc::clone(skill));
+			}
+		}
+		Ok(())
+	}
+
+	fn load_recipes(&self, materials: &mut Materials, recipes: &mut Recipes, report: &mut LoadReport) -> Result<(), DataError> {
+		for m in self.materials.iter() {
+			materials.insert(Arc::new(Material::new(m.id, m.name.clone(), m.rarity)));
+		}
+		for r in self.recipes.iter() {
+			let recipe = r.materials.iter()
+				.filter_map(|(item_id, quantity)| {
+					let material = report.reference(materials.get(*item_id), "recipe_item", r.id, "item", *item_id)?;
+					Some((Arc::clone(material), *quantity))
+				})
+				.collect();
+			recipes.insert((r.kind, r.id), recipe);
+		}
+		Ok(())
+	}
+}
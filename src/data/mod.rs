@@ -1,5 +1,15 @@
+pub mod cache;
+pub mod crafting;
+pub mod csv_source;
+pub mod damage;
 pub mod db_storage;
 pub mod db_types;
+pub mod diff;
 pub mod mutable;
 pub mod db;
+pub mod entries;
 pub mod dyn_storage;
+pub mod pruning;
+pub mod records;
+pub mod source;
+pub mod validate;
@@ -52,7 +52,7 @@ impl<T> AttachedDecorations<T> where T: Item {
 		None
 	}
 
-	fn set_deco(&mut self, index: usize, decoration: Arc<Decoration>) {
+	pub(crate) fn set_deco(&mut self, index: usize, decoration: Arc<Decoration>) {
 		if let Some(deco) = self.decorations.get(index) {
 			let empty = deco.is_none();
 			let item_slots = self.item.get_slots();
@@ -1,4 +1,5 @@
 use std::{
+	cmp::min,
 	collections::HashMap,
 	fmt,
 	sync::Arc,
@@ -44,6 +45,26 @@ impl Equipment {
 		value
 	}
 
+	// True if every skill in requirements is reached at least at the requested level
+	pub fn satisfies(&self, requirements: &SkillsLevel) -> bool {
+		let equipment_skills = self.get_skills();
+		for constraint_skill in requirements.iter() {
+			match equipment_skills.get_level(constraint_skill.get_skill()) {
+				Some(level) if level >= constraint_skill.get_level() => {},
+				_ => return false,
+			}
+		}
+		true
+	}
+
+	// Sum of the requested levels reached, each skill counts at most its requested level
+	pub fn reached_levels(&self, requirements: &SkillsLevel) -> u32 {
+		let equipment_skills = self.get_skills();
+		requirements.iter()
+			.map(|c| min(equipment_skills.get_level(c.get_skill()).unwrap_or(0), c.get_level()) as u32)
+			.sum()
+	}
+
 	pub fn try_add_weapon(&mut self, weapon: AttachedDecorations<Weapon>) -> bool {
 		if self.weapon.is_some() {
 			false
@@ -222,14 +243,14 @@ impl Item for Equipment {
 impl PartialEq for Equipment {
 	fn eq(&self, other: &Self) -> bool {
 		self.weapon == other.weapon &&
-			self.set[0] == self.set[0] &&
-			self.set[1] == self.set[1] &&
-			self.set[2] == self.set[2] &&
-			self.set[3] == self.set[3] &&
-			self.set[4] == self.set[4] &&
-			self.charm == self.charm &&
-			self.tools[0] == self.tools[0] &&
-			self.tools[1] == self.tools[1]
+			self.set[0] == other.set[0] &&
+			self.set[1] == other.set[1] &&
+			self.set[2] == other.set[2] &&
+			self.set[3] == other.set[3] &&
+			self.set[4] == other.set[4] &&
+			self.charm == other.charm &&
+			self.tools[0] == other.tools[0] &&
+			self.tools[1] == other.tools[1]
 	}
 }
 
@@ -0,0 +1,125 @@
+use std::{
+	cmp::{min, Ordering, Reverse},
+	collections::HashSet,
+	fmt,
+	ops::Not,
+	sync::Arc,
+};
+use crate::data::{
+	db_storage::Storage,
+	db_types::{
+		Gender, ID, Item, Level, Slot,
+		armor::Armor,
+		id_map::{Entity, IdMap},
+		skill::SkillsLevel,
+		weapon::Weapon,
+	},
+};
+
+// How many items prune_dominated() removed from each pool
+#[derive(Default, Clone, Copy)]
+pub struct PruneReport {
+	pub armors: usize,
+	pub charms: usize,
+	pub weapons: usize,
+}
+
+impl PruneReport {
+	pub fn total(&self) -> usize {
+		self.armors + self.charms + self.weapons
+	}
+}
+
+impl fmt::Display for PruneReport {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{} dominated items ({} armors, {} charms, {} weapons)", self.total(), self.armors, self.charms, self.weapons)
+	}
+}
+
+/*
+What matters of an item for the constraints: the skill levels capped to the requested level
+(one for each constraint, same order) and the slots sorted from the biggest.
+The values are what the objectives maximize, like the defence of an armor.
+*/
+struct Profile {
+	skills: Vec<Level>,
+	slots: Vec<Slot>,
+	values: Vec<u16>,
+}
+
+impl Profile {
+	fn new<T: Item>(item: &T, constraints: &SkillsLevel, values: Vec<u16>) -> Self {
+		let item_skills = item.get_skills();
+		let skills = constraints.iter()
+			.map(|c| min(item_skills.get_level(c.get_skill()).unwrap_or(0), c.get_level()))
+			.collect();
+		let mut slots = item.get_slots();
+		slots.sort_unstable_by(|a, b| b.cmp(a));
+		Profile { skills, slots, values }
+	}
+
+	// True if self is at least as good as other for every constraint, every slot and every value
+	fn covers(&self, other: &Profile) -> bool {
+		let skills = self.skills.iter().zip(other.skills.iter()).all(|(a, b)| a >= b);
+		let slots = other.slots.iter().enumerate().all(|(i, b)| self.slots.get(i).map_or(*b == 0, |a| a >= b));
+		let values = self.values.iter().zip(other.values.iter()).all(|(a, b)| a >= b);
+		skills && slots && values
+	}
+}
+
+/*
+Remove every item dominated by another one of the same pool.
+replaceable(better, worse) tells if better can take the place of worse in any equipment (same class, gender...),
+values() are the numbers the better one must also match, preferred() breaks the ties between identical items, the greater is kept.
+*/
+fn prune<T, R, V, P, K>(items: &mut IdMap<T>, constraints: &SkillsLevel, replaceable: R, values: V, preferred: P) -> usize
+	where T: Item + Entity, R: Fn(&T, &T) -> bool, V: Fn(&T) -> Vec<u16>, P: Fn(&T) -> K, K: Ord {
+	let profiles: Vec<(Arc<T>, Profile)> = items.iter()
+		.map(|item| (Arc::clone(item), Profile::new(item.as_ref(), constraints, values(item))))
+		.collect();
+	let mut dominated: HashSet<ID> = HashSet::new();
+	for (item, profile) in profiles.iter() {
+		let is_dominated = profiles.iter().any(|(other, other_profile)| {
+			if Arc::ptr_eq(item, other) || replaceable(other, item).not() || other_profile.covers(profile).not() {
+				return false;
+			}
+			// Identical items dominate each other, only the preferred one survives
+			if replaceable(item, other) && profile.covers(other_profile) {
+				preferred(other).cmp(&preferred(item)) == Ordering::Greater
+			} else {
+				true
+			}
+		});
+		if is_dominated {
+			dominated.insert(item.id());
+		}
+	}
+	items.retain(|item| dominated.contains(&item.id()).not());
+	dominated.len()
+}
+
+fn armor_replaceable(better: &Armor, worse: &Armor) -> bool {
+	let gender = better.gender == Gender::All || better.gender == worse.gender;
+	let set_skill = match (&better.set_skill, &worse.set_skill) {
+		(_, None) => true,
+		(Some(b), Some(w)) => b == w,
+		(None, Some(_)) => false,
+	};
+	better.class as usize == worse.class as usize && gender && set_skill
+}
+
+fn weapon_replaceable(better: &Weapon, worse: &Weapon) -> bool {
+	better.class as usize == worse.class as usize
+}
+
+impl Storage {
+	// Drop the armors, charms and weapons that another item of the same class beats for the given constraints.
+	// Only items that cannot be part of a better equipment are removed, so any engine can run on the pruned storage.
+	pub fn prune_dominated(&mut self, constraints: &SkillsLevel) -> PruneReport {
+		PruneReport {
+			armors: prune(&mut self.armors, constraints, armor_replaceable, |a| a.defence.iter().map(|d| *d as u16).collect(), |a| (a.defence[2], Reverse(a.id))),
+			charms: prune(&mut self.charms, constraints, |_, _| true, |_| Vec::new(), |c| Reverse(c.id)),
+			weapons: prune(&mut self.weapons, constraints, weapon_replaceable, |w| vec![w.defense as u16], |w| (w.attack_true, Reverse(w.id))),
+		}
+	}
+}
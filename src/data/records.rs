@@ -0,0 +1,225 @@
+use std::{
+	fmt,
+	ops::Not,
+	sync::Arc,
+};
+use serde::{Deserialize, Serialize};
+use crate::data::{
+	db_storage::Storage,
+	db_types::{
+		ID, Item, Level, ArmorClass,
+		localized::Localized,
+		skill::{SkillLevel, SkillsLevel},
+	},
+	mutable::{
+		attached_decorations::AttachedDecorations,
+		equipment::Equipment,
+	},
+};
+
+/*
+Serializable mirror of the search types.
+Everything is stored by database id so a record can be loaded back against the current Storage,
+the names are written only to make the files readable and are ignored on import.
+*/
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SkillLevelRecord {
+	pub id: ID,
+	#[serde(default, skip_deserializing)]
+	pub name: String,
+	pub level: Level,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ItemRecord {
+	pub id: ID,
+	#[serde(default, skip_deserializing)]
+	pub name: String,
+	#[serde(default)]
+	pub decorations: Vec<Option<ID>>,  // One for each slot of the item, in slot order
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+#[serde(default)]  // Missing pieces are empty
+pub struct EquipmentRecord {
+	pub weapon: Option<ItemRecord>,
+	pub head: Option<ItemRecord>,
+	pub chest: Option<ItemRecord>,
+	pub arms: Option<ItemRecord>,
+	pub waist: Option<ItemRecord>,
+	pub legs: Option<ItemRecord>,
+	pub charm: Option<ItemRecord>,
+	pub tools: [Option<ItemRecord>; 2],
+	#[serde(default, skip_deserializing)]
+	pub skills: Vec<SkillLevelRecord>,  // Total of the equipment, only for the readers
+}
+
+// A constraint set, one record for each requested skill
+pub type ConstraintsRecord = Vec<SkillLevelRecord>;
+
+// A whole search: what was asked and what the engine found, best first
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SearchRecord {
+	pub engine: String,
+	pub constraints: ConstraintsRecord,
+	pub results: Vec<EquipmentRecord>,
+}
+
+pub enum ImportError {
+	Json(serde_json::Error),
+	UnknownSkill(ID),
+	WrongLevel { skill: ID, level: Level, max: Level },  // Outside 1..=max
+	UnknownItem(&'static str, ID),
+	WrongClass(ID),  // Armor in the wrong piece of the set
+	WrongDecoration { item: ID, slot: usize, decoration: ID },
+}
+
+impl fmt::Display for ImportError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			ImportError::Json(e) => write!(f, "Invalid JSON: {}", e),
+			ImportError::UnknownSkill(id) => write!(f, "No skill with id {}", id),
+			ImportError::WrongLevel { skill, level, max } =>
+				write!(f, "Skill {} level {} is not between 1 and {}", skill, level, max),
+			ImportError::UnknownItem(kind, id) => write!(f, "No {} with id {}", kind, id),
+			ImportError::WrongClass(id) => write!(f, "Armor {} is in the wrong piece", id),
+			ImportError::WrongDecoration { item, slot, decoration } =>
+				write!(f, "Decoration {} does not fit slot {} of item {}", decoration, slot, item),
+		}
+	}
+}
+
+impl From<serde_json::Error> for ImportError {
+	fn from(e: serde_json::Error) -> Self {
+		ImportError::Json(e)
+	}
+}
+
+impl SkillsLevel {
+	// Sorted by id so the same skills always give the same record
+	pub fn to_record(&self) -> Vec<SkillLevelRecord> {
+		let mut ret: Vec<SkillLevelRecord> = self.iter()
+			.map(|s| SkillLevelRecord { id: s.get_id(), name: s.get_skill().name.get().to_string(), level: s.get_level() })
+			.collect();
+		ret.sort_by_key(|s| s.id);
+		ret
+	}
+
+	pub fn from_record(record: &[SkillLevelRecord], storage: &Storage) -> Result<Self, ImportError> {
+		let mut ret = SkillsLevel::new();
+		for s in record {
+			let skill = storage.skills.get(s.id).ok_or(ImportError::UnknownSkill(s.id))?;
+			if (1..=skill.max_level).contains(&s.level).not() {
+				return Err(ImportError::WrongLevel { skill: s.id, level: s.level, max: skill.max_level });
+			}
+			ret.set(SkillLevel::new(Arc::clone(skill), s.level));
+		}
+		Ok(ret)
+	}
+}
+
+fn item_record<T: Item>(item: &AttachedDecorations<T>, id: ID, name: &Localized) -> ItemRecord {
+	ItemRecord {
+		id,
+		name: name.get().to_string(),
+		decorations: item.decorations.iter().map(|d| d.as_ref().map(|d| d.id)).collect(),
+	}
+}
+
+// Put the decorations of the record exactly in their slots
+fn attach<T: Item>(item: Arc<T>, record: &ItemRecord, storage: &Storage) -> Result<AttachedDecorations<T>, ImportError> {
+	let slots = item.get_slots();
+	let mut ret = AttachedDecorations::new(item);
+	for (slot, decoration) in record.decorations.iter().enumerate() {
+		if let Some(id) = decoration {
+			let decoration = storage.decorations.get(*id).ok_or(ImportError::UnknownItem("decoration", *id))?;
+			match slots.get(slot) {
+				Some(size) if *size >= decoration.size => ret.set_deco(slot, Arc::clone(decoration)),
+				_ => return Err(ImportError::WrongDecoration { item: record.id, slot, decoration: *id }),
+			}
+		}
+	}
+	Ok(ret)
+}
+
+impl Equipment {
+	pub fn to_record(&self) -> EquipmentRecord {
+		let armor = |class: ArmorClass| self.set[class as usize].as_ref()
+			.map(|a| item_record(a, a.item.id, &a.item.name));
+		let tool = |i: usize| self.tools[i].as_ref()
+			.map(|t| item_record(t, t.item.id, &t.item.name));
+		EquipmentRecord {
+			weapon: self.weapon.as_ref().map(|w| item_record(w, w.item.id, &w.item.name)),
+			head: armor(ArmorClass::Head),
+			chest: armor(ArmorClass::Chest),
+			arms: armor(ArmorClass::Arms),
+			waist: armor(ArmorClass::Waist),
+			legs: armor(ArmorClass::Legs),
+			charm: self.charm.as_ref().map(|c| ItemRecord { id: c.id, name: c.name.get().to_string(), decorations: vec![] }),
+			tools: [tool(0), tool(1)],
+			skills: self.get_skills().to_record(),
+		}
+	}
+
+	pub fn from_record(record: &EquipmentRecord, storage: &Storage) -> Result<Self, ImportError> {
+		let mut ret = Equipment::new();
+		if let Some(w) = &record.weapon {
+			let weapon = storage.weapons.get(w.id).ok_or(ImportError::UnknownItem("weapon", w.id))?;
+			ret.weapon = Some(attach(Arc::clone(weapon), w, storage)?);
+		}
+		let pieces = [
+			(ArmorClass::Head, &record.head),
+			(ArmorClass::Chest, &record.chest),
+			(ArmorClass::Arms, &record.arms),
+			(ArmorClass::Waist, &record.waist),
+			(ArmorClass::Legs, &record.legs),
+		];
+		for (class, piece) in pieces.iter() {
+			if let Some(a) = piece {
+				let armor = storage.armors.get(a.id).ok_or(ImportError::UnknownItem("armor", a.id))?;
+				if armor.class as usize != *class as usize {
+					return Err(ImportError::WrongClass(a.id));
+				}
+				ret.set[*class as usize] = Some(attach(Arc::clone(armor), a, storage)?);
+			}
+		}
+		if let Some(c) = &record.charm {
+			let charm = storage.charms.get(c.id).ok_or(ImportError::UnknownItem("charm", c.id))?;
+			ret.charm = Some(Arc::clone(charm));
+		}
+		for (i, t) in record.tools.iter().enumerate() {
+			if let Some(t) = t {
+				let tool = storage.tools.get(t.id).ok_or(ImportError::UnknownItem("tool", t.id))?;
+				ret.tools[i] = Some(attach(Arc::clone(tool), t, storage)?);
+			}
+		}
+		Ok(ret)
+	}
+}
+
+impl SearchRecord {
+	pub fn new(engine: &str, constraints: &SkillsLevel, results: &[Equipment]) -> Self {
+		SearchRecord {
+			engine: engine.to_string(),
+			constraints: constraints.to_record(),
+			results: results.iter().map(|e| e.to_record()).collect(),
+		}
+	}
+
+	pub fn to_json(&self) -> String {
+		serde_json::to_string_pretty(self).expect("Serialization failed")
+	}
+
+	pub fn from_json(json: &str) -> Result<Self, ImportError> {
+		Ok(serde_json::from_str(json)?)
+	}
+
+	pub fn constraints(&self, storage: &Storage) -> Result<SkillsLevel, ImportError> {
+		SkillsLevel::from_record(&self.constraints, storage)
+	}
+
+	pub fn results(&self, storage: &Storage) -> Result<Vec<Equipment>, ImportError> {
+		self.results.iter().map(|r| Equipment::from_record(r, storage)).collect()
+	}
+}
@@ -0,0 +1,116 @@
+use std::{
+	fs,
+	path::{Path, PathBuf},
+};
+use serde::de::DeserializeOwned;
+use crate::data::{
+	damage::DamageSkills,
+	db::{DataError, LoadReport},
+	db_types::{Armors, ArmorSets, Charms, Decorations, Materials, Monsters, Recipes, SetSkills, Skills, Tools, Weapons},
+	entries::Entries,
+};
+
+/*
+Where Storage::load_all() takes the entities from.
+The loaders are called in this order, each one gets the collections its entities refer to.
+Like the SQL loaders an Err stops the load, the problems of single entities go in the report.
+*/
+pub trait DataSource {
+	fn load_skills(&self, skills: &mut Skills, report: &mut LoadReport) -> Result<(), DataError>;
+	fn load_set_skills(&self, set_skills: &mut SetSkills, skills: &Skills, report: &mut LoadReport) -> Result<(), DataError>;
+	fn load_armors(&self, armors: &mut Armors, skills: &Skills, set_skills: &SetSkills, report: &mut LoadReport) -> Result<(), DataError>;
+	fn load_sets(&self, sets: &mut ArmorSets, armors: &Armors, set_skills: &SetSkills, report: &mut LoadReport) -> Result<(), DataError>;
+	fn load_charms(&self, charms: &mut Charms, skills: &Skills, report: &mut LoadReport) -> Result<(), DataError>;
+	fn load_decorations(&self, decorations: &mut Decorations, skills: &Skills, report: &mut LoadReport) -> Result<(), DataError>;
+	fn load_weapons(&self, weapons: &mut Weapons, skills: &Skills, set_skills: &SetSkills, report: &mut LoadReport) -> Result<(), DataError>;
+	fn load_tools(&self, tools: &mut Tools, report: &mut LoadReport) -> Result<(), DataError>;
+	fn load_monsters(&self, monsters: &mut Monsters, skills: &Skills, report: &mut LoadReport) -> Result<(), DataError>;
+	fn load_damage_skills(&self, damage_skills: &mut DamageSkills, skills: &Skills, report: &mut LoadReport) -> Result<(), DataError>;
+	fn load_recipes(&self, materials: &mut Materials, recipes: &mut Recipes, report: &mut LoadReport) -> Result<(), DataError>;
+}
+
+/*
+A directory with a file for each collection: skills, set_skills, armors, sets, charms, decorations, weapons, tools, monsters,
+damage_skills, materials and recipes, each one a list of entries (see data::entries) in RON (.ron) or JSON (.json).
+A missing file is an empty collection, without damage_skills they are found by skill name. The names are used as they are, in one language.
+*/
+pub struct FileSource {
+	dir: PathBuf,
+}
+
+impl FileSource {
+	pub fn new(dir: &Path) -> Self {
+		FileSource { dir: dir.to_path_buf() }
+	}
+
+	fn read<T: DeserializeOwned>(&self, name: &str) -> Result<Vec<T>, DataError> {
+		let path = match ["ron", "json"].iter().map(|ext| self.dir.join(format!("{}.{}", name, ext))).find(|p| p.exists()) {
+			Some(path) => path,
+			None => return Ok(Vec::new()),
+		};
+		let text = fs::read_to_string(&path).map_err(|e| DataError::File(path.clone(), e.to_string()))?;
+		let parsed = if path.extension().is_some_and(|e| e == "ron") {
+			ron::de::from_str(&text).map_err(|e| e.to_string())
+		} else {
+			serde_json::from_str(&text).map_err(|e| e.to_string())
+		};
+		parsed.map_err(|e| DataError::File(path, e))
+	}
+}
+
+impl DataSource for FileSource {
+	fn load_skills(&self, skills: &mut Skills, report: &mut LoadReport) -> Result<(), DataError> {
+		let entries = Entries { skills: self.read("skills")?, ..Default::default() };
+		entries.load_skills(skills, report)
+	}
+
+	fn load_set_skills(&self, set_skills: &mut SetSkills, skills: &Skills, report: &mut LoadReport) -> Result<(), DataError> {
+		let entries = Entries { set_skills: self.read("set_skills")?, ..Default::default() };
+		entries.load_set_skills(set_skills, skills, report)
+	}
+
+	fn load_armors(&self, armors: &mut Armors, skills: &Skills, set_skills: &SetSkills, report: &mut LoadReport) -> Result<(), DataError> {
+		let entries = Entries { armors: self.read("armors")?, ..Default::default() };
+		entries.load_armors(armors, skills, set_skills, report)
+	}
+
+	fn load_sets(&self, sets: &mut ArmorSets, armors: &Armors, set_skills: &SetSkills, report: &mut LoadReport) -> Result<(), DataError> {
+		let entries = Entries { sets: self.read("sets")?, ..Default::default() };
+		entries.load_sets(sets, armors, set_skills, report)
+	}
+
+	fn load_charms(&self, charms: &mut Charms, skills: &Skills, report: &mut LoadReport) -> Result<(), DataError> {
+		let entries = Entries { charms: self.read("charms")?, ..Default::default() };
+		entries.load_charms(charms, skills, report)
+	}
+
+	fn load_decorations(&self, decorations: &mut Decorations, skills: &Skills, report: &mut LoadReport) -> Result<(), DataError> {
+		let entries = Entries { decorations: self.read("decorations")?, ..Default::default() };
+		entries.load_decorations(decorations, skills, report)
+	}
+
+	fn load_weapons(&self, weapons: &mut Weapons, skills: &Skills, set_skills: &SetSkills, report: &mut LoadReport) -> Result<(), DataError> {
+		let entries = Entries { weapons: self.read("weapons")?, ..Default::default() };
+		entries.load_weapons(weapons, skills, set_skills, report)
+	}
+
+	fn load_tools(&self, tools: &mut Tools, report: &mut LoadReport) -> Result<(), DataError> {
+		let entries = Entries { tools: self.read("tools")?, ..Default::default() };
+		entries.load_tools(tools, report)
+	}
+
+	fn load_monsters(&self, monsters: &mut Monsters, skills: &Skills, report: &mut LoadReport) -> Result<(), DataError> {
+		let entries = Entries { monsters: self.read("monsters")?, ..Default::default() };
+		entries.load_monsters(monsters, skills, report)
+	}
+
+	fn load_damage_skills(&self, damage_skills: &mut DamageSkills, skills: &Skills, report: &mut LoadReport) -> Result<(), DataError> {
+		let entries = Entries { damage_skills: self.read("damage_skills")?, ..Default::default() };
+		entries.load_damage_skills(damage_skills, skills, report)
+	}
+
+	fn load_recipes(&self, materials: &mut Materials, recipes: &mut Recipes, report: &mut LoadReport) -> Result<(), DataError> {
+		let entries = Entries { materials: self.read("materials")?, recipes: self.read("recipes")?, ..Default::default() };
+		entries.load_recipes(materials, recipes, report)
+	}
+}
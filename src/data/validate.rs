@@ -0,0 +1,171 @@
+use std::{
+	collections::HashSet,
+	fmt,
+	str::FromStr,
+};
+use crate::data::{
+	db::{DataError, DB, parse_sharpness},
+	db_types::{ArmorClass, ArmorRank, ID, WeaponClass},
+};
+
+/*
+Checks of a database for the data the loaders would skip or not load at all, run on a new MHWorldData build before using it.
+Each check reads only its own columns, an error in one table is an issue of that check and the others still run.
+*/
+pub struct Check {
+	pub name: &'static str,
+	pub checked: usize,  // Rows or references read
+	pub issues: Vec<DataError>,
+}
+
+pub struct Validation {
+	pub checks: Vec<Check>,
+}
+
+impl Validation {
+	pub fn is_valid(&self) -> bool {
+		self.checks.iter().all(|check| check.issues.is_empty())
+	}
+
+	pub fn issues(&self) -> impl Iterator<Item = &DataError> {
+		self.checks.iter().flat_map(|check| check.issues.iter())
+	}
+}
+
+impl fmt::Display for Validation {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		for check in self.checks.iter() {
+			match check.issues.len() {
+				0 => writeln!(f, "ok      {} ({} checked)", check.name, check.checked)?,
+				n => writeln!(f, "FAILED  {} ({} checked, {} issues)", check.name, check.checked, n)?,
+			}
+			for issue in check.issues.iter() {
+				writeln!(f, "\t{}", issue)?;
+			}
+		}
+		match self.issues().count() {
+			0 => write!(f, "No issues found"),
+			n => write!(f, "{} issues found", n),
+		}
+	}
+}
+
+// Skill references: the table named in the report, the id of the row and the reference
+const SKILL_REFERENCES: [(&str, &str); 7] = [
+	("armor", "SELECT armor_id, skilltree_id FROM armor_skill"),
+	("armorset_bonus_skill", "SELECT setbonus_id, skilltree_id FROM armorset_bonus_skill"),
+	("charm", "SELECT charm_id, skilltree_id FROM charm_skill"),
+	("decoration", "SELECT id, skilltree_id FROM decoration"),
+	("decoration", "SELECT id, skilltree2_id FROM decoration"),
+	("weapon", "SELECT weapon_id, skilltree_id FROM weapon_skill"),
+	("skilltree", "SELECT id, unlocks_id FROM skilltree"),
+];
+const SET_SKILL_REFERENCES: [(&str, &str); 3] = [
+	("armor", "SELECT id, armorset_bonus_id FROM armor"),
+	("armorset", "SELECT id, armorset_bonus_id FROM armorset"),
+	("weapon", "SELECT id, armorset_bonus_id FROM weapon"),
+];
+const SET_REFERENCES: [(&str, &str); 1] = [("armor", "SELECT id, armorset_id FROM armor")];
+const ITEM_REFERENCES: [(&str, &str); 1] = [("recipe_item", "SELECT recipe_id, item_id FROM recipe_item")];
+
+impl DB {
+	pub fn validate(&self) -> Validation {
+		let checks = vec![
+			("armor types", self.check_values::<ArmorClass>("armor", "armor_type")),
+			("armor ranks", self.check_values::<ArmorRank>("armor", "rank")),
+			("armor set ranks", self.check_values::<ArmorRank>("armorset", "rank")),
+			("weapon types", self.check_values::<WeaponClass>("weapon", "weapon_type")),
+			("skill references", self.check_references("skilltree", "SELECT id FROM skilltree", &SKILL_REFERENCES)),
+			("set bonus references", self.check_references("armorset_bonus", "SELECT setbonus_id FROM armorset_bonus_skill", &SET_SKILL_REFERENCES)),
+			("armor set references", self.check_references("armorset", "SELECT id FROM armorset", &SET_REFERENCES)),
+			("recipe item references", self.check_references("item", "SELECT id FROM item", &ITEM_REFERENCES)),
+			("weapon sharpness", self.check_sharpness()),
+			("armor set slots", self.check_set_slots()),
+		];
+		Validation {
+			checks: checks.into_iter().map(|(name, result)| match result {
+				Ok((checked, issues)) => Check { name, checked, issues },
+				Err(e) => Check { name, checked: 0, issues: vec![e] },
+			}).collect(),
+		}
+	}
+
+	// Every value of table.column is a T, NULL too is invalid
+	fn check_values<T: FromStr>(&self, table: &'static str, column: &'static str) -> Result<(usize, Vec<DataError>), DataError> {
+		let mut statement = self.connection().prepare(&format!("SELECT id, {} FROM {};", column, table))?;
+		let mut rows = statement.query([])?;
+		let (mut checked, mut issues) = (0, Vec::new());
+		while let Some(row) = rows.next()? {
+			let value: Option<String> = row.get(1)?;
+			checked += 1;
+			if value.as_deref().is_none_or(|value| T::from_str(value).is_err()) {
+				issues.push(DataError::BadValue { table, id: row.get(0)?, column, value: value.unwrap_or_else(|| String::from("NULL")) });
+			}
+		}
+		Ok((checked, issues))
+	}
+
+	// The non NULL references of each (table, query) are among the ids of target
+	fn check_references(&self, target: &'static str, target_ids: &str, references: &[(&'static str, &str)]) -> Result<(usize, Vec<DataError>), DataError> {
+		let mut statement = self.connection().prepare(target_ids)?;
+		let ids = statement.query_map([], |row| row.get(0))?.collect::<Result<HashSet<ID>, _>>()?;
+		let (mut checked, mut issues) = (0, Vec::new());
+		// A missing table is one more issue, the references of the other tables are still checked
+		for (table, query) in references {
+			let mut check_table = || -> Result<(), DataError> {
+				let mut statement = self.connection().prepare(query)?;
+				let mut rows = statement.query([])?;
+				while let Some(row) = rows.next()? {
+					if let Some(target_id) = row.get::<_, Option<ID>>(1)? {
+						checked += 1;
+						if !ids.contains(&target_id) {
+							issues.push(DataError::Dangling { table, id: row.get(0)?, target, target_id });
+						}
+					}
+				}
+				Ok(())
+			};
+			if let Err(e) = check_table() {
+				issues.push(e);
+			}
+		}
+		Ok((checked, issues))
+	}
+
+	fn check_sharpness(&self) -> Result<(usize, Vec<DataError>), DataError> {
+		let mut statement = self.connection().prepare("SELECT id, sharpness FROM weapon WHERE sharpness IS NOT NULL;")?;
+		let mut rows = statement.query([])?;
+		let (mut checked, mut issues) = (0, Vec::new());
+		while let Some(row) = rows.next()? {
+			let sharpness: String = row.get(1)?;
+			checked += 1;
+			if parse_sharpness(&sharpness).is_none() {
+				issues.push(DataError::BadValue { table: "weapon", id: row.get(0)?, column: "sharpness", value: sharpness });
+			}
+		}
+		Ok((checked, issues))
+	}
+
+	// At most one armor of a set for each body part
+	fn check_set_slots(&self) -> Result<(usize, Vec<DataError>), DataError> {
+		let mut statement = self.connection().prepare(
+			"SELECT armorset_id, armor_type, group_concat(id) AS armors
+				FROM armor
+				WHERE armorset_id IS NOT NULL
+				GROUP BY armorset_id, armor_type;")?;
+		let mut rows = statement.query([])?;
+		let (mut checked, mut issues) = (0, Vec::new());
+		while let Some(row) = rows.next()? {
+			let armors: String = row.get("armors")?;
+			checked += 1;
+			if armors.contains(',') {
+				issues.push(DataError::SlotTaken {
+					set: row.get("armorset_id")?,
+					slot: row.get::<_, Option<String>>("armor_type")?.unwrap_or_default(),
+					armors: armors.split(',').filter_map(|id| id.parse().ok()).collect(),
+				});
+			}
+		}
+		Ok((checked, issues))
+	}
+}
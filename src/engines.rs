@@ -1,25 +1,31 @@
 use std::{
 	rc::Rc,
-	cell::Cell,
-	thread::Builder,
+	cell::{Cell, RefCell},
+	sync::Arc,
 };
 use strum::{Display, EnumIter, EnumString};
-use glib::Sender;
 use crate::data::{
 	mutable::equipment::Equipment,
+	db_storage::Storage,
+	damage::Target,
+	db_types::skill::{SkillLevel, SkillsLevel},
 	dyn_storage::DynamicStorage,
 };
 use crate::engines::{
 	hill_climbing::HillClimbing,
 	greedy::Greedy,
+	progress::SearchContext,
 	EnginesManagerError::{AlreadyRunning, NoConstraints},
 };
-use crate::ui::Callback;
+use crate::search::{Filters, Search, SearchEvent, SearchHandle};
 
+pub(crate) mod additional_skills;
 pub(crate) mod greedy;
 pub(crate) mod hill_climbing;
+pub mod parallel;
+pub mod progress;
 
-#[derive(Display, EnumString, EnumIter)]
+#[derive(Display, EnumString, EnumIter, Copy, Clone)]
 pub enum Engines {
 	Greedy,
 	HillClimbing,
@@ -27,6 +33,7 @@ pub enum Engines {
 
 pub enum EngineError {
 	Impossible,
+	Cancelled,
 }
 
 pub enum EnginesManagerError {
@@ -35,26 +42,70 @@ pub enum EnginesManagerError {
 }
 
 
+// What the engines maximize after the constraints, among the equipments that reach as many levels
+#[derive(Clone, Default)]
+pub enum Objective {
+	#[default]
+	Defence,
+	Damage(Target),  // Expected damage per hit on the target
+}
+
+impl Objective {
+	pub fn value(&self, equipment: &Equipment) -> u32 {
+		match self {
+			Objective::Defence => equipment.get_defence() as u32,
+			// In tenths, an equipment without weapon deals nothing
+			Objective::Damage(target) => target.damage(equipment).map_or(0, |damage| (damage.total() * 10.).round() as u32),
+		}
+	}
+
+	// The constraints the pool is pruned with, the items with the damage skills are kept
+	pub(crate) fn pruning_constraints(&self, constraints: &SkillsLevel) -> SkillsLevel {
+		let mut ret = constraints.clone();
+		if let Objective::Damage(target) = self {
+			for skill in target.skills() {
+				if ret.get_level(Arc::clone(skill)).is_none() {
+					ret.insert(SkillLevel::new(Arc::clone(skill), skill.max_level));
+				}
+			}
+		}
+		ret
+	}
+}
+
 pub(crate) trait Engine {
-	fn run(&mut self) -> Result<Vec<Equipment>, EngineError>;
+	fn run(&mut self, ctx: &SearchContext) -> Result<Vec<Equipment>, EngineError>;
 }
 
+pub(crate) fn new_engine(engine_type: Engines, storage: Storage, constraints: SkillsLevel, objective: Objective, threads: usize) -> Box<dyn Engine> {
+	match engine_type {
+		Engines::Greedy => Box::new(Greedy::new(storage, constraints, objective, threads)) as Box<dyn Engine>,
+		Engines::HillClimbing => Box::new(HillClimbing::new(storage, constraints, objective, threads)) as Box<dyn Engine>,
+	}
+}
+
+/*
+Keeps track of the search started by an interactive frontend, one at a time.
+The events are delivered to the sink from the search thread, the frontend must call ended() when it receives the last one.
+*/
 pub struct EnginesManager {
-	sender: Option<Sender<Callback>>,
+	events: Arc<dyn Fn(SearchEvent) + Send + Sync>,
 	running: Cell<bool>,
+	search: RefCell<Option<SearchHandle>>,  // The running search
 }
 
 impl EnginesManager {
-	pub fn new(sender: Sender<Callback>) -> Self {
-		let searcher = EnginesManager {
-			sender: Some(sender),
+	pub fn new<F>(events: F) -> Self where F: Fn(SearchEvent) + Send + Sync + 'static {
+		EnginesManager {
+			events: Arc::new(events),
 			running: Cell::new(false),
-		};
-		searcher
+			search: RefCell::new(None),
+		}
 	}
 
-	pub fn spawn(&self, engine_type: Engines, dynamic: &Rc<DynamicStorage>) -> Result<(), EnginesManagerError> {
-		let storage = dynamic.generate_storage();
+	pub fn spawn(&self, engine_type: Engines, dynamic: &Rc<DynamicStorage>, filters: &Filters, threads: usize) -> Result<(), EnginesManagerError> {
+		let mut storage = dynamic.generate_storage();
+		filters.apply(&mut storage);
 		let constraints = dynamic.get_constraints();
 		if constraints.len() <= 0 {
 			return Err(NoConstraints);
@@ -64,38 +115,30 @@ impl EnginesManager {
 		}
 
 		self.running.replace(true);
-
-
-		let sender = self.sender.clone();
 		println!("Constrains: {}", &constraints);
-
-		Builder::new().name(engine_type.to_string().into()).spawn(move || {
-			let mut engine = match engine_type {
-				Engines::Greedy => Box::new(Greedy::new(storage, constraints)) as Box<dyn Engine>,
-				Engines::HillClimbing => Box::new(HillClimbing::new(storage, constraints)) as Box<dyn Engine>,
-			};
-			let best_equipment = engine.run();
-
-			if let Some(sender) = sender {
-				match best_equipment {
-					Ok(bests) => {
-						println!("{}", bests.first().unwrap());
-						sender.send(Callback::Done(bests)).expect("Error sending callback");
-					}
-					Err(e) => match e {
-						EngineError::Impossible => {
-							sender.send(Callback::Impossible).expect("Error sending callback");
-						}
-					}
-				}
-			} else {
-				println!("No ui callback found, this should never happen! X_X");
-			}
-		}).unwrap();
+		let events = Arc::clone(&self.events);
+		let search = Search::new(engine_type, constraints)
+			.with_threads(threads)
+			.with_additional_skills(true)
+			.with_weapon_required(filters.requires_weapon())
+			.spawn(storage, move |event| events(event));
+		self.search.replace(Some(search));
 		Ok(())
 	}
 
+	// Ask the running engine to stop, the sink receives SearchEvent::Cancelled
+	pub fn cancel(&self) {
+		if let Some(search) = self.search.borrow().as_ref() {
+			search.cancel();
+		}
+	}
+
+	pub fn is_running(&self) -> bool {
+		self.running.get()
+	}
+
 	pub fn ended(&self) {
 		self.running.replace(false);
+		self.search.replace(None);
 	}
 }
@@ -0,0 +1,63 @@
+use std::{
+	cmp::Ordering,
+	sync::Arc,
+};
+use crate::data::{
+	db_storage::Storage,
+	db_types::{
+		Item, Level,
+		skill::{Skill, SkillLevel, SkillsLevel},
+	},
+};
+use crate::engines::{parallel::parallel_map, progress::SearchContext};
+use crate::search::Search;
+
+/*
+For every skill compute the highest extra level still reachable on top of the constraints,
+searching again with the same item pool and the same settings of the search.
+Skills that cannot be raised any further are not returned.
+*/
+pub(crate) fn additional_skills(search: &Search, storage: &Storage, ctx: &SearchContext) -> Vec<(Arc<Skill>, Level)> {
+	let constraints = &search.constraints;
+	let skills: Vec<Arc<Skill>> = storage.skills.iter()
+		.filter(|skill| constraints.get_level(Arc::clone(skill)).unwrap_or(0) < skill.max_level && is_available(storage, skill))
+		.map(Arc::clone)
+		.collect();
+	// Every skill is an independent job, the engines inside run on a single thread
+	let extra = parallel_map(skills, search.threads, |skill| {
+		let current = constraints.get_level(Arc::clone(&skill)).unwrap_or(0);
+		let mut reachable = current;
+		for level in current + 1..=skill.max_level {
+			let mut query = constraints.clone();
+			query.set(SkillLevel::new(Arc::clone(&skill), level));
+			let probe = Search::new(search.engine, query.clone())
+				.with_threads(1)
+				.with_objective(search.objective.clone())
+				.with_weapon_required(search.weapon_required);
+			ctx.add_explored(1);
+			match probe.run(storage, &ctx.subsearch(&query)) {
+				Ok(results) if results.iter().any(|equipment| equipment.satisfies(&query)) => reachable = level,
+				_ => break,
+			}
+		}
+		(skill, reachable - current)
+	});
+	let mut ret: Vec<(Arc<Skill>, Level)> = extra.into_iter().filter(|(_, extra)| *extra > 0).collect();
+	ret.sort_by(|(a_skill, a_level), (b_skill, b_level)| {
+		match b_level.cmp(a_level) {
+			Ordering::Equal => a_skill.name.cmp(&b_skill.name),
+			ord => ord,
+		}
+	});
+	ret
+}
+
+// Avoid running the engine for skills that no item in the pool can give
+fn is_available(storage: &Storage, skill: &Arc<Skill>) -> bool {
+	let mut query = SkillsLevel::new();
+	query.insert(SkillLevel::new(Arc::clone(skill), 1));
+	storage.armors.iter().any(|i| i.has_skills(&query)) ||
+		storage.charms.iter().any(|i| i.has_skills(&query)) ||
+		storage.decorations.iter().any(|i| i.has_skills(&query)) ||
+		storage.weapons.iter().any(|i| i.has_skills(&query))
+}
@@ -1,17 +1,18 @@
 use std::{
 	cmp::{
 		Ordering,
+		Reverse,
 		min,
 	},
 	fmt,
 	sync::Arc,
 	ops::Not,
-	collections::HashSet,
 };
 use crate::data::{
 	db_storage::Storage,
 	db_types::{
-		Item, Decorations, Slot,
+		Item, Decorations, Slot, ArmorClass,
+		id_map::IdMap,
 		weapon::Weapon,
 		armor::Armor,
 		charm::Charm,
@@ -24,10 +25,15 @@ use crate::data::{
 		attached_decorations::AttachedDecorations,
 	},
 };
-use crate::engines::{Engine, EngineError};
+use crate::engines::{Engine, EngineError, Objective, parallel::parallel_map, progress::SearchContext};
 
 type EvalType = i16;
 
+// Number of head pieces that start a branch of the search
+const HEAD_BRANCHES: usize = 32;
+// Number of equipments returned by run()
+const MAX_RESULTS: usize = 10;
+
 struct EvalContainer<T> {
 	pub item: AttachedDecorations<T>,
 	pub value: EvalType,
@@ -44,7 +50,7 @@ fn eval_skills(item_skills: &SkillsLevel, constraint: &SkillsLevel) -> EvalType
 	value
 }
 
-fn eval_and_assign_slots<T>(item: &mut AttachedDecorations<T>, decorations: &Decorations, constraints: &mut SkillsLevel) -> EvalType where T: Item {  // Sum of the value of best decorations applicable.
+pub(crate) fn eval_and_assign_slots<T>(item: &mut AttachedDecorations<T>, decorations: &Decorations, constraints: &mut SkillsLevel) -> EvalType where T: Item {  // Sum of the value of best decorations applicable.
 	let mut val = 0;
 	let slots = item.get_slots();
 	if slots.len() <= 0 {
@@ -86,6 +92,29 @@ fn get_best_decoration(slot_size: Slot, decorations: &Decorations, constraints:
 	}
 }
 
+// Put in the empty weapon slot the weapon that reaches most constraint levels, then has the best objective value
+pub(crate) fn fill_weapon<'a>(equipment: &mut Equipment, weapons: impl Iterator<Item = &'a Arc<Weapon>>, decorations: &Decorations, constraints: &SkillsLevel, objective: &Objective) {
+	if equipment.weapon.is_some() {
+		return;
+	}
+	let mut missing = constraints.clone();
+	missing.remove_skills(&equipment.get_skills());
+	let mut best: Option<((u32, u32), AttachedDecorations<Weapon>)> = None;
+	for weapon in weapons {
+		let mut candidate = AttachedDecorations::new(Arc::clone(weapon));
+		let mut left = missing.clone();
+		left.remove_skills(&weapon.get_skills());
+		eval_and_assign_slots(&mut candidate, decorations, &mut left);
+		equipment.weapon = Some(candidate);
+		let score = (equipment.reached_levels(constraints), objective.value(equipment));
+		let candidate = equipment.weapon.take().unwrap();
+		if best.as_ref().is_none_or(|(best, _)| score > *best) {
+			best = Some((score, candidate));
+		}
+	}
+	equipment.weapon = best.map(|(_, weapon)| weapon);
+}
+
 impl<T: Item> EvalContainer<T> {
 	fn new(item: &Arc<T>, deco: &Decorations, constraints: &SkillsLevel) -> Self {
 		let item = AttachedDecorations::new(Arc::clone(item));
@@ -109,6 +138,16 @@ impl<T: Item> EvalContainer<T> {
 	}
 }
 
+impl<T: Item> Clone for EvalContainer<T> {
+	fn clone(&self) -> Self {
+		EvalContainer {
+			item: self.item.clone(),
+			value: self.value,
+		}
+	}
+}
+
+#[derive(Clone)]
 enum Wearable {
 	Weapon(EvalContainer<Weapon>),
 	Armor(EvalContainer<Armor>),
@@ -154,16 +193,20 @@ impl Wearable {
 	}
 }
 
+#[derive(Clone)]
 pub(crate) struct Greedy {
 	// Engine Related
 	constraints: SkillsLevel,
+	objective: Objective,  // Ranks the results of the branches, a damage one also picks their weapon
+	threads: usize,
 	// Greedy related
 	current_constrains: SkillsLevel,
 	decorations: Decorations,
 	wearable: Vec<Wearable>,
+	weapons: Vec<Arc<Weapon>>,  // Every weapon, also the ones that do not help the constraints
 }
 
-fn filter_item<T>(items: &HashSet<Arc<T>>, constraints: &SkillsLevel) -> Vec<Arc<T>> where T: Item {
+fn filter_item<T>(items: &IdMap<T>, constraints: &SkillsLevel) -> Vec<Arc<T>> where T: Item {
 	let mut ret = vec![];
 	for item in items.iter() {
 		if item.has_skills(constraints) {
@@ -174,7 +217,7 @@ fn filter_item<T>(items: &HashSet<Arc<T>>, constraints: &SkillsLevel) -> Vec<Arc
 }
 
 impl Greedy {
-	pub(crate) fn new(storage: Storage, constraints: SkillsLevel) -> Self {
+	pub(crate) fn new(storage: Storage, constraints: SkillsLevel, objective: Objective, threads: usize) -> Self {
 		let copy = constraints.clone();
 		let mut decorations: Decorations = Default::default();
 		let mut wearable: Vec<Wearable> = Default::default();
@@ -220,9 +263,12 @@ impl Greedy {
 
 		Greedy {
 			constraints,
+			objective,
+			threads,
 			current_constrains: copy,
 			wearable,
 			decorations,
+			weapons: storage.weapons.iter().map(Arc::clone).collect(),
 		}
 	}
 
@@ -238,26 +284,17 @@ impl Greedy {
 	}
 
 	pub fn satisfy_all_constraints(&self, res: &Equipment) -> bool {
-		let mut satisfied = true;
-		let equipment_skills = res.get_skills();
-		for constraint_skill in self.constraints.iter() {
-			match equipment_skills.get_level(constraint_skill.get_skill()) {
-				Some(equipment_level) => {
-					if constraint_skill.get_level() > equipment_level {
-						satisfied = false;
-					}
-				},
-				None => satisfied = false,
-			}
-		}
-		satisfied
+		res.satisfies(&self.constraints)
 	}
 }
 
-impl Engine for Greedy {
-	fn run(&mut self) -> Result<Vec<Equipment>, EngineError> {
-		let mut result = Equipment::new();
+impl Greedy {
+	// The usual greedy search starting from an equipment with some pieces already placed
+	fn complete(&mut self, mut result: Equipment, ctx: &SearchContext) -> Result<Equipment, EngineError> {
 		while self.satisfy_all_constraints(&result).not() && result.is_full().not() {
+			if ctx.is_cancelled() {
+				return Err(EngineError::Cancelled);
+			}
 			let mut i = 0;
 			let mut insered = false;
 			while insered.not() {  // Loop until a wearable item is suited for placement
@@ -282,6 +319,7 @@ impl Engine for Greedy {
 							},
 						};
 						if insered {  // Go for the next piece
+							ctx.add_explored(1);
 							self.current_constrains.remove_skills(&piece.get_skills());
 						} else {
 							i += 1;
@@ -292,7 +330,61 @@ impl Engine for Greedy {
 			}
 			self.filter();
 		}
-		Ok(vec![result])
+		ctx.update_best(result.reached_levels(&self.constraints));
+		Ok(result)
+	}
+
+	// The best head pieces, each one is the root of a branch of the search tree
+	fn head_branches(&self) -> Vec<AttachedDecorations<Armor>> {
+		self.wearable.iter()
+			.filter_map(|w| match w {
+				Wearable::Armor(a) if a.item.item.class as usize == ArmorClass::Head as usize && a.value > 0 => Some(a.item.clone()),
+				_ => None,
+			})
+			.take(HEAD_BRANCHES)
+			.collect()
+	}
+}
+
+impl Engine for Greedy {
+	// Split the search per head piece and complete every branch in parallel,
+	// the branch without a fixed head is the plain greedy search.
+	fn run(&mut self, ctx: &SearchContext) -> Result<Vec<Equipment>, EngineError> {
+		let mut branches = vec![None];
+		branches.extend(self.head_branches().into_iter().map(Some));
+		let root = &*self;
+		let results = parallel_map(branches, self.threads, |head| {
+			let mut greedy = root.clone();
+			let mut start = Equipment::new();
+			if let Some(head) = head {
+				greedy.current_constrains.remove_skills(&head.get_skills());
+				start.try_add_armor(head);
+				greedy.filter();
+			}
+			let mut result = greedy.complete(start, ctx);
+			// The greedy search places only the weapons with skills, the damage needs one anyway
+			if let (Ok(equipment), Objective::Damage(_)) = (result.as_mut(), &root.objective) {
+				fill_weapon(equipment, root.weapons.iter(), &root.decorations, &root.constraints, &root.objective);
+			}
+			result
+		});
+		if ctx.is_cancelled() {
+			return Err(EngineError::Cancelled);
+		}
+
+		let mut found: Vec<Equipment> = Vec::new();
+		for equipment in results.into_iter().flatten() {
+			if found.contains(&equipment).not() {
+				found.push(equipment);
+			}
+		}
+		if found.is_empty() {
+			return Err(EngineError::Impossible);
+		}
+		let constraints = &self.constraints;
+		found.sort_by_cached_key(|e| Reverse((e.satisfies(constraints), e.evaluate(constraints.clone()), self.objective.value(e))));
+		found.truncate(MAX_RESULTS);
+		Ok(found)
 	}
 }
 
@@ -1,39 +1,188 @@
 use std::{
+	ops::Not,
 	sync::Arc,
 };
-
+use rand::{Rng, seq::SliceRandom};
+use strum::EnumCount;
 use crate::data::{
-	mutable::equipment::Equipment,
+	mutable::{
+		equipment::Equipment,
+		attached_decorations::AttachedDecorations,
+	},
 	db_storage::Storage,
-	db_types::skill::SkillsLevel,
+	db_types::{
+		ArmorClass, Decorations, Item,
+		armor::Armor,
+		charm::Charm,
+		skill::SkillsLevel,
+		weapon::Weapon,
+	},
+};
+use crate::engines::{
+	Engine, EngineError, Objective,
+	greedy::eval_and_assign_slots,
+	parallel::parallel_map,
+	progress::SearchContext,
 };
-use crate::engines::{Engine, EngineError};
-#[allow(dead_code)]
+
+// Independent climbs started by every thread
+const RESTARTS_PER_THREAD: usize = 4;
+// Number of equipments returned by run()
+const MAX_RESULTS: usize = 10;
+// The charm and the weapon follow the armor pieces in a state
+const CHARM: usize = ArmorClass::COUNT;
+const WEAPON: usize = CHARM + 1;
+
+type Score = (u32, u32);  // (Constraints levels reached, Objective value)
+
+// A point of the search space: the index in the pools of every armor piece, of the charm and of the weapon
+#[derive(Clone, PartialEq)]
+struct State {
+	pieces: [Option<usize>; WEAPON + 1],
+}
+
 pub(crate) struct HillClimbing {
-	storage: Arc<Storage>,
 	constraints: SkillsLevel,
+	objective: Objective,
+	armors: Vec<Vec<Arc<Armor>>>,  // A pool for each ArmorClass
+	charms: Vec<Arc<Charm>>,
+	weapons: Vec<Arc<Weapon>>,  // Usually empty, the search is on the armors
+	decorations: Decorations,
 
-	start_points: Vec<Equipment>,
+	threads: usize,
 	iterations: u32,
-	random: u32,
 }
 
 impl HillClimbing {
-	pub(crate) fn new(storage: Storage, constraints: SkillsLevel) -> Self {
+	pub(crate) fn new(storage: Storage, constraints: SkillsLevel, objective: Objective, threads: usize) -> Self {
+		let mut armors = vec![Vec::new(); ArmorClass::COUNT];
+		for armor in storage.armors.iter() {
+			armors[armor.class as usize].push(Arc::clone(armor));
+		}
+		let decorations = storage.decorations.iter()
+			.filter(|d| d.has_skills(&constraints))
+			.map(Arc::clone)
+			.collect();
 		HillClimbing {
-			storage: Arc::new(storage),
-			constraints: constraints.clone(),
-			start_points: vec![],
+			constraints,
+			objective,
+			armors,
+			charms: storage.charms.iter().map(Arc::clone).collect(),
+			weapons: storage.weapons.iter().map(Arc::clone).collect(),
+			decorations,
+			threads,
 			iterations: 256,
-			random: 0
 		}
 	}
-}
 
-impl Engine for HillClimbing {
-	fn run(&mut self) -> Result<Vec<Equipment>, EngineError> {
-		unimplemented!()
+	fn pool_len(&self, piece: usize) -> usize {
+		match piece {
+			CHARM => self.charms.len(),
+			WEAPON => self.weapons.len(),
+			_ => self.armors[piece].len(),
+		}
+	}
+
+	fn build(&self, state: &State) -> Equipment {
+		let mut equipment = Equipment::new();
+		let mut missing = self.constraints.clone();
+		let mut armors = Vec::with_capacity(ArmorClass::COUNT);
+		for (class, choice) in state.pieces[..CHARM].iter().enumerate() {
+			if let Some(i) = choice {
+				let armor = &self.armors[class][*i];
+				missing.remove_skills(&armor.get_skills());
+				armors.push(AttachedDecorations::new(Arc::clone(armor)));
+			}
+		}
+		if let Some(i) = state.pieces[CHARM] {
+			let charm = &self.charms[i];
+			missing.remove_skills(&charm.get_skills());
+			equipment.try_add_charm(Arc::clone(charm));
+		}
+		let weapon = state.pieces[WEAPON].map(|i| {
+			let weapon = &self.weapons[i];
+			missing.remove_skills(&weapon.get_skills());
+			AttachedDecorations::new(Arc::clone(weapon))
+		});
+		for mut armor in armors.into_iter() {
+			eval_and_assign_slots(&mut armor, &self.decorations, &mut missing);
+			equipment.try_add_armor(armor);
+		}
+		if let Some(mut weapon) = weapon {
+			eval_and_assign_slots(&mut weapon, &self.decorations, &mut missing);
+			equipment.try_add_weapon(weapon);
+		}
+		equipment
+	}
+
+	fn score(&self, state: &State, ctx: &SearchContext) -> Score {
+		let equipment = self.build(state);
+		let reached = equipment.reached_levels(&self.constraints);
+		ctx.add_explored(1);
+		ctx.update_best(reached);
+		(reached, self.objective.value(&equipment))
+	}
+
+	// Start from a random state and move to the best neighbour until no piece change improves the score
+	fn climb(&self, ctx: &SearchContext) -> (State, Score) {
+		let mut rng = rand::thread_rng();
+		let mut state = State { pieces: [None; WEAPON + 1] };
+		for (piece, choice) in state.pieces.iter_mut().enumerate() {
+			let len = self.pool_len(piece);
+			if len > 0 {
+				*choice = Some(rng.gen_range(0..len));
+			}
+		}
+		let mut best = self.score(&state, ctx);
+		let mut order: Vec<usize> = (0..=WEAPON).collect();
+		for _ in 0..self.iterations {
+			let mut improved = false;
+			order.shuffle(&mut rng);
+			for piece in order.iter() {
+				for candidate in 0..self.pool_len(*piece) {
+					// Scoring a large pool takes long, stop as soon as asked
+					if ctx.is_cancelled() {
+						return (state, best);
+					}
+					let mut next = state.clone();
+					next.pieces[*piece] = Some(candidate);
+					let score = self.score(&next, ctx);
+					if score > best {
+						state = next;
+						best = score;
+						improved = true;
+					}
+				}
+			}
+			if improved.not() {
+				break;
+			}
+		}
+		(state, best)
 	}
 }
 
+impl Engine for HillClimbing {
+	// Independent random restarts, spread over the worker threads
+	fn run(&mut self, ctx: &SearchContext) -> Result<Vec<Equipment>, EngineError> {
+		if self.armors.iter().all(|pool| pool.is_empty()) && self.charms.is_empty() {
+			return Err(EngineError::Impossible);
+		}
+		let restarts = self.threads.max(1) * RESTARTS_PER_THREAD;
+		let engine = &*self;
+		let mut climbs = parallel_map((0..restarts).collect(), self.threads, |_| engine.climb(ctx));
+		if ctx.is_cancelled() {
+			return Err(EngineError::Cancelled);
+		}
+		climbs.sort_by(|(_, a), (_, b)| b.cmp(a));
 
+		let mut found: Vec<State> = Vec::new();
+		for (state, _) in climbs.into_iter() {
+			if found.contains(&state).not() {
+				found.push(state);
+			}
+		}
+		found.truncate(MAX_RESULTS);
+		Ok(found.iter().map(|state| self.build(state)).collect())
+	}
+}
@@ -0,0 +1,43 @@
+use std::{
+	cmp::{max, min},
+	sync::Mutex,
+	thread,
+};
+
+// Default number of worker threads: one for each core
+pub fn available_threads() -> usize {
+	thread::available_parallelism().map_or(1, |n| n.get())
+}
+
+/*
+Run job on every input using up to threads worker threads.
+The inputs are consumed from a shared queue so a slow job do not block the others,
+the outputs keep the same order of the inputs.
+*/
+pub(crate) fn parallel_map<I, O, F>(inputs: Vec<I>, threads: usize, job: F) -> Vec<O>
+	where I: Send, O: Send, F: Fn(I) -> O + Sync {
+	let len = inputs.len();
+	let threads = min(max(threads, 1), max(len, 1));
+	if threads == 1 {
+		return inputs.into_iter().map(job).collect();
+	}
+	let queue = Mutex::new(inputs.into_iter().enumerate());
+	let outputs = Mutex::new(Vec::with_capacity(len));
+	thread::scope(|scope| {
+		for _ in 0..threads {
+			scope.spawn(|| loop {
+				let next = queue.lock().unwrap().next();
+				match next {
+					Some((i, input)) => {
+						let output = job(input);
+						outputs.lock().unwrap().push((i, output));
+					}
+					None => break,
+				}
+			});
+		}
+	});
+	let mut outputs = outputs.into_inner().unwrap();
+	outputs.sort_by_key(|(i, _)| *i);
+	outputs.into_iter().map(|(_, output)| output).collect()
+}
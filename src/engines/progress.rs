@@ -0,0 +1,86 @@
+use std::{
+	sync::{
+		Arc,
+		atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering},
+	},
+	time::{Duration, Instant},
+};
+use crate::data::db_types::skill::SkillsLevel;
+
+// Snapshot of a running search
+#[derive(Clone, Copy)]
+pub struct Progress {
+	pub explored: u64,    // Nodes of the search space visited so far
+	pub best_score: u32,  // Constraints levels reached by the best equipment found so far
+	pub max_score: u32,   // Constraints levels requested
+	pub elapsed: Duration,
+}
+
+impl Progress {
+	pub fn fraction(&self) -> f64 {
+		if self.max_score == 0 {
+			0.0
+		} else {
+			self.best_score as f64 / self.max_score as f64
+		}
+	}
+}
+
+/*
+Shared between the engine and whoever started it.
+The engines must call is_cancelled() often and stop with EngineError::Cancelled when it is true,
+add_explored() and update_best() feed the progress reporting.
+*/
+pub struct SearchContext {
+	cancelled: Arc<AtomicBool>,
+	explored: AtomicU64,
+	best_score: AtomicU32,
+	max_score: u32,
+	started: Instant,
+}
+
+impl SearchContext {
+	pub fn new(constraints: &SkillsLevel) -> Self {
+		SearchContext::with_flag(Arc::new(AtomicBool::new(false)), constraints)
+	}
+
+	fn with_flag(cancelled: Arc<AtomicBool>, constraints: &SkillsLevel) -> Self {
+		SearchContext {
+			cancelled,
+			explored: AtomicU64::new(0),
+			best_score: AtomicU32::new(0),
+			max_score: constraints.iter().map(|c| c.get_level() as u32).sum(),
+			started: Instant::now(),
+		}
+	}
+
+	// A new context for a search started by this one, cancelling this cancels the new one too
+	pub fn subsearch(&self, constraints: &SkillsLevel) -> Self {
+		SearchContext::with_flag(Arc::clone(&self.cancelled), constraints)
+	}
+
+	pub fn cancel(&self) {
+		self.cancelled.store(true, Ordering::Relaxed);
+	}
+
+	pub fn is_cancelled(&self) -> bool {
+		self.cancelled.load(Ordering::Relaxed)
+	}
+
+	pub fn add_explored(&self, nodes: u64) {
+		self.explored.fetch_add(nodes, Ordering::Relaxed);
+	}
+
+	pub fn update_best(&self, score: u32) {
+		self.best_score.fetch_max(score, Ordering::Relaxed);
+	}
+
+	pub fn progress(&self) -> Progress {
+		Progress {
+			explored: self.explored.load(Ordering::Relaxed),
+			best_score: self.best_score.load(Ordering::Relaxed),
+			max_score: self.max_score,
+			elapsed: self.started.elapsed(),
+		}
+	}
+}
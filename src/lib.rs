@@ -0,0 +1,12 @@
+/*
+Everything that does not need GTK: the data loading, the engines and the search API.
+The GTK frontend is the binary in main.rs, other tools can link this library and run the engines headless.
+*/
+pub mod batch;
+pub mod data;
+pub mod engines;
+pub mod search;
+pub mod server;
+pub mod settings;
+#[cfg(test)]
+mod tests;
@@ -1,23 +1,40 @@
-use crate::data::{db::DB, db_storage::Storage};
-use crate::settings::Settings;
+use mhwiss::data::{db::DB, db_storage::Storage};
+use mhwiss::settings::Settings;
 use crate::ui::Ui;
 
 mod ui;
-mod data;
-mod engines;
-mod settings;
-#[cfg(test)]
-mod tests;
 
 fn main() {
-	let mut db = DB::new();
-	let settings = Settings::new(&db);
+	let mut settings = Settings::load();
+	let mut db = match DB::locate(settings.get_database().as_deref()) {
+		Ok(db) => db,
+		Err(e) => match ui::ask_database(e) {
+			Some(db) => db,
+			None => return,
+		},
+	};
+	if settings.get_database().as_deref() != Some(db.path()) {
+		settings.set_database(db.path());
+		if let Err(e) = settings.write() {
+			eprintln!("Failed to save the database path: {}", e);
+		}
+	}
 	db.set_language(settings.get_language());
 
 	let mut storage = Storage::new();
-	storage.load_all(&db);
+	match settings.load_languages(&db).and_then(|_| storage.load_cached(&db)) {
+		Ok(report) if report.is_empty() => {}
+		Ok(report) => eprintln!("{}", report),
+		Err(e) => {
+			ui::show_error("Failed to load the database", &e.to_string());
+			return;
+		}
+	}
+	if let Err(e) = storage.set_secondary_language(&db, settings.get_secondary_language().as_deref()) {
+		eprintln!("{}", e);
+	}
 //	let storage = Rc::new(storage);
 
-	let app = Ui::new(settings, storage);
+	let app = Ui::new(settings, db, storage);
 	app.start();
-}
\ No newline at end of file
+}
@@ -0,0 +1,235 @@
+use std::{
+	ops::Not,
+	sync::{
+		Arc,
+		atomic::{AtomicBool, Ordering},
+	},
+	thread::{self, Builder, JoinHandle},
+	time::Duration,
+};
+use crate::data::{
+	db_storage::Storage,
+	db_types::{
+		ArmorRank, Decorations, Gender, Item, Level, WeaponClass,
+		skill::{Skill, SkillsLevel},
+		weapon::WeaponFeature,
+	},
+	mutable::equipment::Equipment,
+	pruning::PruneReport,
+};
+use crate::engines::{
+	EngineError, Engines, Objective,
+	additional_skills::additional_skills,
+	greedy::fill_weapon,
+	new_engine,
+	parallel::available_threads,
+	progress::{Progress, SearchContext},
+};
+
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(250);
+
+// Raises the flag when dropped, also by a panic of the search
+struct DoneGuard<'a>(&'a AtomicBool);
+
+impl Drop for DoneGuard<'_> {
+	fn drop(&mut self) {
+		self.0.store(true, Ordering::Relaxed);
+	}
+}
+
+// Everything a running search can tell to its caller
+pub enum SearchEvent {
+	Pruned(PruneReport),  // Before the engine starts
+	Progress(Progress),
+	Done(Vec<Equipment>),
+	AdditionalSkills(Vec<(Arc<Skill>, Level)>),
+	Impossible,
+	Cancelled,
+}
+
+// Which items of the storage a search can use
+#[derive(Default, Clone)]
+pub struct Filters {
+	pub ranks: Vec<ArmorRank>,  // Empty for every rank
+	pub gender: Option<Gender>,
+	pub weapons: bool,  // Also pick a weapon, implied by a class or a feature
+	pub weapon_classes: Vec<WeaponClass>,  // Empty for every class
+	pub weapon_features: Vec<WeaponFeature>,  // The weapon must have all of them
+}
+
+impl Filters {
+	// A class or a feature asks for a weapon, the search fails without a matching one
+	pub fn requires_weapon(&self) -> bool {
+		self.weapon_classes.is_empty().not() || self.weapon_features.is_empty().not()
+	}
+
+	pub fn apply(&self, storage: &mut Storage) {
+		if self.weapons.not() && self.requires_weapon().not() {
+			storage.weapons.clear();
+		}
+		if self.weapon_classes.is_empty().not() {
+			storage.weapons.retain(|weapon| self.weapon_classes.contains(&weapon.class));
+		}
+		if self.weapon_features.is_empty().not() {
+			storage.weapons.retain(|weapon| self.weapon_features.iter().all(|feature| weapon.class_data.has(feature)));
+		}
+		if self.ranks.is_empty().not() {
+			storage.armors.retain(|armor| self.ranks.contains(&armor.rank));
+		}
+		if let Some(gender) = self.gender {
+			storage.armors.retain(|armor| armor.gender == Gender::All || armor.gender == gender);
+		}
+	}
+}
+
+/*
+A search request, independent from any UI.
+The storage given to the run methods is the pool of usable items, for example DynamicStorage::generate_storage().
+*/
+pub struct Search {
+	pub engine: Engines,
+	pub constraints: SkillsLevel,
+	pub threads: usize,
+	pub additional_skills: bool,  // After the search compute what else fits in the found equipment
+	pub objective: Objective,
+	pub weapon_required: bool,  // Every result has a weapon of the pool
+}
+
+impl Search {
+	pub fn new(engine: Engines, constraints: SkillsLevel) -> Self {
+		Search {
+			engine,
+			constraints,
+			threads: available_threads(),
+			additional_skills: false,
+			objective: Objective::Defence,
+			weapon_required: false,
+		}
+	}
+
+	pub fn with_threads(mut self, threads: usize) -> Self {
+		self.threads = threads.max(1);
+		self
+	}
+
+	pub fn with_additional_skills(mut self, enable: bool) -> Self {
+		self.additional_skills = enable;
+		self
+	}
+
+	pub fn with_objective(mut self, objective: Objective) -> Self {
+		self.objective = objective;
+		self
+	}
+
+	pub fn with_weapon_required(mut self, required: bool) -> Self {
+		self.weapon_required = required;
+		self
+	}
+
+	// The pool run_pruned() searches, without the items dominated for the constraints
+	pub fn prune(&self, storage: &Storage) -> (Storage, PruneReport) {
+		let mut pool = storage.clone();
+		let mut pruned = pool.prune_dominated(&self.objective.pruning_constraints(&self.constraints));
+		// The pruning knows nothing of attack and elements
+		if let Objective::Damage(_) = self.objective {
+			pool.weapons = storage.weapons.clone();
+			pruned.weapons = 0;
+		}
+		(pool, pruned)
+	}
+
+	// Blocking search on a pool from prune(), the best equipment is the first one
+	pub fn run_pruned(&self, pool: Storage, ctx: &SearchContext) -> Result<Vec<Equipment>, EngineError> {
+		if self.weapon_required && pool.weapons.is_empty() {
+			return Err(EngineError::Impossible);
+		}
+		// The engines leave the weapon slot empty when no weapon helps the constraints
+		let weapons = pool.weapons.clone();
+		let decorations: Decorations = pool.decorations.iter()
+			.filter(|d| d.has_skills(&self.constraints))
+			.map(Arc::clone)
+			.collect();
+		let mut engine = new_engine(self.engine, pool, self.constraints.clone(), self.objective.clone(), self.threads);
+		let mut results = engine.run(ctx)?;
+		if self.weapon_required {
+			for equipment in results.iter_mut() {
+				fill_weapon(equipment, weapons.iter(), &decorations, &self.constraints, &self.objective);
+			}
+		}
+		Ok(results)
+	}
+
+	// Blocking search, the best equipment is the first one
+	pub fn run(&self, storage: &Storage, ctx: &SearchContext) -> Result<Vec<Equipment>, EngineError> {
+		self.run_pruned(self.prune(storage).0, ctx)
+	}
+
+	// Blocking search that reports every event, progress included, to events
+	pub fn run_with_events<F>(&self, storage: &Storage, ctx: &SearchContext, events: F) where F: Fn(SearchEvent) + Sync {
+		let done = AtomicBool::new(false);
+		thread::scope(|scope| {
+			// Periodic progress report until the search and the analysis end
+			scope.spawn(|| {
+				while done.load(Ordering::Relaxed).not() {
+					thread::sleep(PROGRESS_INTERVAL);
+					events(SearchEvent::Progress(ctx.progress()));
+				}
+			});
+
+			let _done = DoneGuard(&done);
+			let (pool, pruned) = self.prune(storage);
+			events(SearchEvent::Pruned(pruned));
+			match self.run_pruned(pool, ctx) {
+				Ok(bests) => {
+					events(SearchEvent::Done(bests));
+					if self.additional_skills {
+						// Same pool and settings, what else fits on top of the found set
+						let additional = additional_skills(self, storage, ctx);
+						if ctx.is_cancelled() {
+							events(SearchEvent::Cancelled);
+						} else {
+							events(SearchEvent::AdditionalSkills(additional));
+						}
+					}
+				}
+				Err(EngineError::Impossible) => events(SearchEvent::Impossible),
+				Err(EngineError::Cancelled) => events(SearchEvent::Cancelled),
+			}
+		});
+	}
+
+	// Run the search in a background thread
+	pub fn spawn<F>(self, storage: Storage, events: F) -> SearchHandle where F: Fn(SearchEvent) + Send + Sync + 'static {
+		let ctx = Arc::new(SearchContext::new(&self.constraints));
+		let thread_ctx = Arc::clone(&ctx);
+		let thread = Builder::new().name(self.engine.to_string()).spawn(move || {
+			self.run_with_events(&storage, &thread_ctx, events);
+		}).unwrap();
+		SearchHandle { ctx, thread }
+	}
+}
+
+pub struct SearchHandle {
+	ctx: Arc<SearchContext>,
+	thread: JoinHandle<()>,
+}
+
+impl SearchHandle {
+	// Ask the engine to stop, a SearchEvent::Cancelled is reported
+	pub fn cancel(&self) {
+		self.ctx.cancel();
+	}
+
+	pub fn progress(&self) -> Progress {
+		self.ctx.progress()
+	}
+
+	pub fn is_finished(&self) -> bool {
+		self.thread.is_finished()
+	}
+
+	pub fn join(self) {
+		self.thread.join().expect("Search thread panicked");
+	}
+}
@@ -0,0 +1,338 @@
+use std::{
+	io::{self, Read},
+	net::SocketAddr,
+	ops::Not,
+	str::FromStr,
+	sync::{
+		Arc,
+		atomic::{AtomicBool, Ordering},
+	},
+	thread,
+};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use strum::IntoEnumIterator;
+use tiny_http::{Header, Method, Request, Response};
+use crate::data::{
+	crafting::{Crafted, ShoppingList},
+	damage::Target,
+	db_storage::Storage,
+	db_types::{ArmorRank, Element, Gender, ID, Item, WeaponClass, skill::SkillsLevel, weapon::WeaponFeature},
+	mutable::equipment::Equipment,
+	records::{ConstraintsRecord, EquipmentRecord, SearchRecord},
+};
+use crate::engines::{EngineError, Engines, Objective, parallel::available_threads, progress::SearchContext};
+use crate::search::{Filters, Search};
+
+/*
+JSON over HTTP access to one loaded Storage.
+	GET  /skills                  every skill
+	GET  /items/<kind>            armors, charms, decorations, weapons, tools or monsters
+	POST /search                  SearchRequest -> SearchRecord and satisfied
+	POST /evaluate                EvaluateRequest -> skills, defence, the constraints reached, the damage on a hitzone
+	                              and the shopping list of the materials to craft it
+Every worker thread serves one request at a time, so up to workers searches run together.
+*/
+pub struct Server {
+	http: tiny_http::Server,
+	storage: Storage,
+	workers: usize,
+	stopped: AtomicBool,
+}
+
+#[derive(Deserialize)]
+struct SearchRequest {
+	#[serde(default)]
+	engine: Option<String>,
+	#[serde(default)]
+	constraints: ConstraintsRecord,  // May be empty with a monster
+	#[serde(default)]
+	ranks: Vec<String>,
+	#[serde(default)]
+	gender: Option<String>,
+	#[serde(default)]
+	weapons: bool,
+	#[serde(default)]
+	weapon_classes: Vec<String>,  // Like weapon_features, implies weapons
+	#[serde(default)]
+	weapon_features: Vec<String>,  // "<kind>:<value>" as parsed by WeaponFeature
+	#[serde(default)]
+	monster: Option<ID>,  // Its suggested skills are added to the constraints
+	#[serde(default)]
+	hitzone: Option<ID>,  // Of the monster, implies weapons and maximizes the damage on it
+	#[serde(default)]
+	threads: Option<usize>,  // Engine threads of this search, default 1, at most the available ones
+}
+
+#[derive(Deserialize)]
+struct EvaluateRequest {
+	equipment: EquipmentRecord,
+	#[serde(default)]
+	constraints: ConstraintsRecord,
+	#[serde(default)]
+	monster: Option<ID>,
+	#[serde(default)]
+	hitzone: Option<ID>,  // With monster
+	#[serde(default)]
+	crafted: Crafted,  // Left out of the shopping list
+}
+
+// Status code and body of a response
+type Reply = (u16, Value);
+
+// Larger request bodies are rejected before reading them
+const MAX_BODY: usize = 1 << 20;
+
+fn error(status: u16, message: String) -> Reply {
+	(status, json!({ "error": message }))
+}
+
+impl Server {
+	pub fn bind(addr: &str, storage: Storage, workers: usize) -> io::Result<Self> {
+		let http = tiny_http::Server::http(addr).map_err(io::Error::other)?;
+		Ok(Server {
+			http,
+			storage,
+			workers: workers.max(1),
+			stopped: AtomicBool::new(false),
+		})
+	}
+
+	pub fn local_addr(&self) -> SocketAddr {
+		self.http.server_addr().to_ip().expect("Server not listening on an ip address")
+	}
+
+	// Serve until stop() is called
+	pub fn run(&self) {
+		thread::scope(|scope| {
+			for _ in 0..self.workers {
+				scope.spawn(|| loop {
+					match self.http.recv() {
+						Ok(request) => self.respond(request),
+						Err(_) if self.stopped.load(Ordering::Relaxed) => break,
+						Err(e) => eprintln!("Server: {}", e),
+					}
+				});
+			}
+		});
+	}
+
+	pub fn stop(&self) {
+		self.stopped.store(true, Ordering::Relaxed);
+		for _ in 0..self.workers {
+			self.http.unblock();
+		}
+	}
+
+	fn respond(&self, mut request: Request) {
+		let (status, value) = match Self::body(&mut request) {
+			Ok(body) => self.route(request.method(), request.url(), &body),
+			Err(reply) => reply,
+		};
+		let header = Header::from_bytes(&b"Content-Type"[..], &b"application/json"[..]).unwrap();
+		let response = Response::from_string(value.to_string())
+			.with_status_code(status)
+			.with_header(header);
+		if let Err(e) = request.respond(response) {
+			eprintln!("Server: {}", e);
+		}
+	}
+
+	fn body(request: &mut Request) -> Result<String, Reply> {
+		let too_large = || error(413, format!("Request body over {} bytes", MAX_BODY));
+		if request.body_length().is_some_and(|length| length > MAX_BODY) {
+			return Err(too_large());
+		}
+		let mut body = Vec::new();
+		request.as_reader().take(MAX_BODY as u64 + 1).read_to_end(&mut body).map_err(|e| error(400, e.to_string()))?;
+		if body.len() > MAX_BODY {
+			return Err(too_large());
+		}
+		String::from_utf8(body).map_err(|e| error(400, e.to_string()))
+	}
+
+	fn route(&self, method: &Method, url: &str, body: &str) -> Reply {
+		let path = url.split('?').next().unwrap_or("");
+		match (method, path) {
+			(Method::Get, "/skills") => self.skills(),
+			(Method::Get, p) if p.starts_with("/items/") => self.items(&p["/items/".len()..]),
+			(Method::Post, "/search") => match serde_json::from_str(body) {
+				Ok(request) => self.search(request),
+				Err(e) => error(400, e.to_string()),
+			},
+			(Method::Post, "/evaluate") => match serde_json::from_str(body) {
+				Ok(request) => self.evaluate(request),
+				Err(e) => error(400, e.to_string()),
+			},
+			_ => error(404, format!("No route for {} {}", method, path)),
+		}
+	}
+
+	fn skills(&self) -> Reply {
+		let mut skills: Vec<_> = self.storage.skills.iter().collect();
+		skills.sort_by_key(|s| s.id);
+		let skills: Vec<Value> = skills.iter()
+			.map(|s| json!({ "id": s.id, "name": s.name, "description": s.description, "max_level": s.max_level }))
+			.collect();
+		(200, Value::from(skills))
+	}
+
+	fn items(&self, kind: &str) -> Reply {
+		let mut items: Vec<(u16, Value)> = match kind {
+			"armors" => self.storage.armors.iter().map(|a| (a.id, json!({
+				"id": a.id, "name": a.name, "class": a.class.to_string(), "rank": a.rank.to_string(),
+				"gender": a.gender.to_string(), "slots": a.slots, "defence": a.defence,
+				"skills": a.skills.to_record(), "set_skill": a.set_skill.as_ref().map(|s| s.name.get().to_string()),
+			}))).collect(),
+			"charms" => self.storage.charms.iter().map(|c| (c.id, json!({
+				"id": c.id, "name": c.name, "skills": c.skills.to_record(),
+			}))).collect(),
+			"decorations" => self.storage.decorations.iter().map(|d| (d.id, json!({
+				"id": d.id, "name": d.name, "size": d.size, "skills": d.skills.to_record(),
+			}))).collect(),
+			"weapons" => self.storage.weapons.iter().map(|w| (w.id, json!({
+				"id": w.id, "name": w.name, "class": w.class.to_string(), "attack": w.attack_true,
+				"slots": w.slots, "skills": w.skill.to_record(), "class_data": w.class_data,
+			}))).collect(),
+			"tools" => self.storage.tools.iter().map(|t| (t.id, json!({
+				"id": t.id, "name": t.name, "slots": t.get_slots(),
+			}))).collect(),
+			"monsters" => self.storage.monsters.iter().map(|m| (m.id, json!({
+				"id": m.id, "name": m.name, "large": m.large,
+				"weaknesses": Element::iter().map(|e| (e.to_string(), Value::from(m.weakness(e)))).collect::<serde_json::Map<_, _>>(),
+				"ailments": m.ailments.iter().map(|a| a.to_string()).collect::<Vec<_>>(),
+				"suggested": m.suggested.to_record(),
+				"weapons": m.best_weapons(&self.storage.weapons, 10).iter().map(|w| w.id).collect::<Vec<_>>(),
+				"hitzones": m.hitzones.iter().map(|h| json!({
+					"id": h.id, "name": h.name, "cut": h.cut, "impact": h.impact, "shot": h.shot, "ko": h.ko,
+					"elements": Element::iter_element().map(|e| (e.to_string(), Value::from(h.element(*e)))).collect::<serde_json::Map<_, _>>(),
+				})).collect::<Vec<_>>(),
+			}))).collect(),
+			_ => return error(404, format!("Unknown items {}", kind)),
+		};
+		items.sort_by_key(|(id, _)| *id);
+		(200, Value::from(items.into_iter().map(|(_, item)| item).collect::<Vec<_>>()))
+	}
+
+	// The hitzone of the monster, None without one
+	fn target(&self, monster: Option<ID>, hitzone: Option<ID>) -> Result<Option<Target>, Reply> {
+		let hitzone = match hitzone {
+			Some(hitzone) => hitzone,
+			None => return Ok(None),
+		};
+		let monster = match monster.and_then(|id| self.storage.monsters.get(id)) {
+			Some(monster) => monster,
+			None => return Err(error(400, format!("Hitzone {} without a known monster", hitzone))),
+		};
+		match Target::new(&self.storage, Arc::clone(monster), hitzone) {
+			Some(target) => Ok(Some(target)),
+			None => Err(error(400, format!("Unknown hitzone {} of {}", hitzone, monster.name))),
+		}
+	}
+
+	fn search(&self, request: SearchRequest) -> Reply {
+		let engine = match request.engine.as_deref().map(Engines::from_str).unwrap_or(Ok(Engines::Greedy)) {
+			Ok(engine) => engine,
+			Err(_) => return error(400, format!("Unknown engine {}", request.engine.unwrap())),
+		};
+		let mut constraints = match SkillsLevel::from_record(&request.constraints, &self.storage) {
+			Ok(constraints) => constraints,
+			Err(e) => return error(400, e.to_string()),
+		};
+		if let Some(id) = request.monster {
+			match self.storage.monsters.get(id) {
+				Some(monster) => monster.apply_suggested(&mut constraints),
+				None => return error(400, format!("Unknown monster {}", id)),
+			}
+		}
+		if constraints.len() == 0 {
+			return error(400, String::from("No constraints"));
+		}
+		let objective = match self.target(request.monster, request.hitzone) {
+			Ok(Some(target)) => Objective::Damage(target),
+			Ok(None) => Objective::Defence,
+			Err(reply) => return reply,
+		};
+		let mut filters = Filters { weapons: request.weapons || request.hitzone.is_some(), ..Default::default() };
+		for rank in request.ranks.iter() {
+			match ArmorRank::from_str(rank) {
+				Ok(rank) => filters.ranks.push(rank),
+				Err(_) => return error(400, format!("Unknown rank {}", rank)),
+			}
+		}
+		if let Some(gender) = &request.gender {
+			match Gender::from_str(gender) {
+				Ok(gender) => filters.gender = Some(gender),
+				Err(_) => return error(400, format!("Unknown gender {}", gender)),
+			}
+		}
+		for class in request.weapon_classes.iter() {
+			match WeaponClass::from_str(class) {
+				Ok(class) => filters.weapon_classes.push(class),
+				Err(_) => return error(400, format!("Unknown weapon class {}", class)),
+			}
+		}
+		for feature in request.weapon_features.iter() {
+			match WeaponFeature::from_str(feature) {
+				Ok(feature) => filters.weapon_features.push(feature),
+				Err(e) => return error(400, e),
+			}
+		}
+
+		let threads = match request.threads {
+			Some(0) => return error(400, String::from("No threads")),
+			Some(threads) => threads.min(available_threads()),
+			None => 1,
+		};
+
+		let mut pool = self.storage.clone();
+		filters.apply(&mut pool);
+		let search = Search::new(engine, constraints.clone())
+			.with_threads(threads)
+			.with_objective(objective.clone())
+			.with_weapon_required(filters.requires_weapon());
+		let results = match search.run(&pool, &SearchContext::new(&constraints)) {
+			Ok(results) => results,
+			Err(EngineError::Impossible) => Vec::new(),
+			Err(EngineError::Cancelled) => return error(500, String::from("Search cancelled")),
+		};
+		let satisfied = results.first().is_some_and(|best| best.satisfies(&constraints));
+		let record = SearchRecord::new(&engine.to_string(), &constraints, &results);
+		let damage = match &objective {
+			Objective::Damage(target) => results.first().and_then(|best| target.damage(best)),
+			Objective::Defence => None,
+		};
+		(200, json!({ "satisfied": satisfied, "search": record, "damage": damage }))
+	}
+
+	fn evaluate(&self, request: EvaluateRequest) -> Reply {
+		let equipment = match Equipment::from_record(&request.equipment, &self.storage) {
+			Ok(equipment) => equipment,
+			Err(e) => return error(400, e.to_string()),
+		};
+		let constraints = match SkillsLevel::from_record(&request.constraints, &self.storage) {
+			Ok(constraints) => constraints,
+			Err(e) => return error(400, e.to_string()),
+		};
+		let damage = match self.target(request.monster, request.hitzone) {
+			Ok(target) => target.and_then(|target| target.damage(&equipment)),
+			Err(reply) => return reply,
+		};
+		let list = ShoppingList::new(&equipment, &self.storage, &request.crafted);
+		let shopping_list = json!({
+			"materials": list.materials.iter().map(|(m, quantity)| json!({
+				"id": m.id, "name": m.name, "rarity": m.rarity, "quantity": quantity,
+			})).collect::<Vec<_>>(),
+			"steps": list.steps.iter().map(|(kind, name)| json!({ "kind": kind.to_string(), "name": name })).collect::<Vec<_>>(),
+			"missing": list.missing,
+		});
+		(200, json!({
+			"equipment": equipment.to_record(),
+			"defence": equipment.get_defence(),
+			"satisfied": request.constraints.is_empty().not() && equipment.satisfies(&constraints),
+			"reached_levels": equipment.reached_levels(&constraints),
+			"damage": damage,
+			"shopping_list": shopping_list,
+		}))
+	}
+}
@@ -1,12 +1,15 @@
 use std::io::Write;
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 use std::ops::Not;
 use std::rc::Rc;
 use std::fs::File;
+use std::path::{Path, PathBuf};
 use directories::ProjectDirs;
 use serde::{Deserialize, Serialize};
 use ron::ser::{PrettyConfig};
-use crate::data::db::DB;
+use crate::data::crafting::Crafted;
+use crate::data::db::{DataError, DB};
+use crate::engines::parallel::available_threads;
 
 const CONFIG_FILE: &str = "mhwiss.conf";
 
@@ -15,14 +18,30 @@ pub struct Settings {
 	#[serde(skip_serializing, skip_deserializing)]
 	available_languages: Rc<Vec<(String, String)>> ,
 	language: RefCell<String>,
+	#[serde(default)]
+	secondary_language: RefCell<Option<String>>,  // Names also shown in this language
+	#[serde(default = "default_threads")]
+	threads: Cell<usize>,  // Worker threads used by the engines
+	#[serde(default)]
+	database: RefCell<Option<PathBuf>>,  // Chosen by the user, tried before the default locations
+	#[serde(default)]
+	crafted: RefCell<Crafted>,  // Left out of the shopping list, written by hand in the config file
+}
+
+fn default_threads() -> Cell<usize> {
+	Cell::new(available_threads())
 }
 
 impl Settings {
-	pub fn new(db: &DB) -> Self {
-		let available_languages = Rc::new(db.get_available_languages());
-		let mut ret = Settings {
-			available_languages: Rc::clone(&available_languages),
-			language: RefCell::new(String::from("en"))
+	// The languages are loaded later with load_languages(), the database path is a setting
+	pub fn load() -> Self {
+		let ret = Settings {
+			available_languages: Rc::new(Vec::new()),
+			language: RefCell::new(String::from("en")),
+			secondary_language: RefCell::new(None),
+			threads: default_threads(),
+			database: RefCell::new(None),
+			crafted: Default::default(),
 		};
 
 		if let Some(proj_dirs) = ProjectDirs::from("org", "SilverCorp", "mhwiss") {
@@ -30,9 +49,7 @@ impl Settings {
 			let conf = conf.join(CONFIG_FILE);
 			if conf.exists() {  // TODO add atomic file reader
 				let file = File::open(conf).unwrap();
-				let mut settings: Settings = ron::de::from_reader(file).expect("Failed to load config");
-				settings.available_languages = available_languages;
-				return settings;
+				return ron::de::from_reader(file).expect("Failed to load config");
 			} else {
 				println!("Use default config");
 			}
@@ -40,6 +57,11 @@ impl Settings {
 		ret
 	}
 
+	pub fn load_languages(&mut self, db: &DB) -> Result<(), DataError> {
+		self.available_languages = Rc::new(db.get_available_languages()?);
+		Ok(())
+	}
+
 	pub fn change_language(&self, lang: String) {
 		self.language.replace(lang);
 	}
@@ -48,6 +70,34 @@ impl Settings {
 		self.language.borrow().clone()
 	}
 
+	pub fn change_secondary_language(&self, lang: Option<String>) {
+		self.secondary_language.replace(lang);
+	}
+
+	pub fn get_secondary_language(&self) -> Option<String> {
+		self.secondary_language.borrow().clone()
+	}
+
+	pub fn set_threads(&self, threads: usize) {
+		self.threads.set(threads.max(1));
+	}
+
+	pub fn get_threads(&self) -> usize {
+		self.threads.get()
+	}
+
+	pub fn set_database(&self, path: &Path) {
+		self.database.replace(Some(path.to_path_buf()));
+	}
+
+	pub fn get_database(&self) -> Option<PathBuf> {
+		self.database.borrow().clone()
+	}
+
+	pub fn get_crafted(&self) -> Crafted {
+		self.crafted.borrow().clone()
+	}
+
 	pub fn get_available_languages(&self) -> Rc<Vec<(String, String)>> {
 		Rc::clone(&self.available_languages)
 	}
@@ -0,0 +1,38 @@
+use rand::{SeedableRng, rngs::StdRng};
+use crate::batch::{generate_random_constraints, run_batch, write_csv};
+use crate::engines::Engines;
+use crate::tests::Shared;
+
+#[test]
+fn batch_csv() {
+	let shared = Shared::get();
+	let generate = |seed| {
+		let mut rng = StdRng::seed_from_u64(seed);
+		(1..=4).filter_map(|len| generate_random_constraints(&shared.storage.skills, len, &mut rng)).collect::<Vec<_>>()
+	};
+	let sets = generate(42);
+	assert_eq!(sets.len(), 4);
+	// Same seed, same workload
+	for (a, b) in sets.iter().zip(generate(42).iter()) {
+		assert_eq!(a.to_record(), b.to_record());
+	}
+
+	let mut sets = sets;
+	sets.extend(shared.static_constraints.iter().cloned());
+	let rows = run_batch(Engines::Greedy, &shared.storage, &sets, 1);
+	assert_eq!(rows.len(), sets.len());
+	for row in rows.iter() {
+		assert_eq!(row.satisfied, row.best.as_ref().is_some_and(|best| best.satisfies(&row.constraints)));
+	}
+
+	let mut csv = Vec::new();
+	write_csv(&rows, &mut csv).ok().unwrap();
+	let mut reader = csv::Reader::from_reader(csv.as_slice());
+	let header = reader.headers().unwrap().clone();
+	let records: Vec<_> = reader.records().map(|r| r.unwrap()).collect();
+	assert_eq!(records.len(), rows.len());
+	for (record, row) in records.iter().zip(rows.iter()) {
+		assert_eq!(record.len(), header.len());
+		assert_eq!(&record[1], if row.satisfied { "yes" } else { "no" });
+	}
+}
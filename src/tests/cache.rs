@@ -0,0 +1,51 @@
+use std::sync::Arc;
+use itertools::Itertools;
+use crate::data::{
+	cache::{self, CacheError},
+	db_storage::Storage,
+	db_types::{ID, Item, Level, id_map::{Entity, IdMap}, skill::SkillsLevel},
+};
+use crate::tests::Shared;
+
+// The Display of SkillsLevel does not order the skills with the same level
+fn levels(skills: &SkillsLevel) -> Vec<(ID, Level)> {
+	skills.iter().map(|s| (s.get_id(), s.get_level())).sorted().collect()
+}
+
+fn items<T: Item + Entity>(items: &IdMap<T>) -> Vec<(ID, String, Vec<(ID, Level)>, Vec<u8>)> {
+	items.iter().map(|i| (i.id(), i.name().to_string(), levels(&i.get_skills()), i.get_slots())).collect()
+}
+
+// The cache gives back the same entities, linked again, and only for the same database
+#[test]
+fn cache_round_trip() {
+	let shared = Shared::get();
+	let storage: &Storage = &shared.storage;
+	let path = std::env::temp_dir().join(format!("mhwiss-cache-{}.cache", std::process::id()));
+	cache::write(storage, &path, 42).unwrap_or_else(|e| panic!("{}", e));
+	let cached = cache::read(&path, 42).unwrap_or_else(|e| panic!("{}", e));
+	let stale = cache::read(&path, 43);
+	std::fs::remove_file(&path).unwrap();
+
+	assert!(matches!(stale, Err(CacheError::Stale)));
+	let skills = |s: &Storage| s.skills.iter().map(|s| (s.to_string(), s.max_level, s.unlock.as_ref().map(|u| u.id))).collect_vec();
+	assert_eq!(skills(&cached), skills(storage));
+	let set_skills = |s: &Storage| s.set_skills.iter().map(|s| (s.id, levels(&s.skills))).collect_vec();
+	assert_eq!(set_skills(&cached), set_skills(storage));
+	assert_eq!(items(&cached.armors), items(&storage.armors));
+	assert_eq!(items(&cached.charms), items(&storage.charms));
+	assert_eq!(items(&cached.decorations), items(&storage.decorations));
+	assert_eq!(items(&cached.weapons), items(&storage.weapons));
+	assert_eq!(items(&cached.tools), items(&storage.tools));
+	assert_eq!(cached.sets.len(), storage.sets.len());
+	for (set, original) in cached.sets.iter().zip(storage.sets.iter()) {
+		assert_eq!(set.name, *original.name.get());
+		for (armor, original) in set.armors.iter().zip(original.armors.iter()) {
+			assert_eq!(armor.as_ref().map(|a| a.id), original.as_ref().map(|a| a.id));
+			// Linked to the armor of the cached storage, not to a copy
+			if let Some(armor) = armor {
+				assert!(Arc::ptr_eq(armor, cached.armors.get(armor.id).unwrap()));
+			}
+		}
+	}
+}
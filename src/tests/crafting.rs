@@ -0,0 +1,60 @@
+use std::sync::Arc;
+use crate::data::{
+	crafting::{Crafted, ShoppingList},
+	db_storage::Storage,
+	db_types::{ArmorClass, ArmorRank, WeaponClass, material::RecipeKind},
+	entries::Entries,
+	mutable::{attached_decorations::AttachedDecorations, equipment::Equipment},
+};
+
+// The upgrades start from the crafted weapon, or from the nearest one that can be forged
+#[test]
+fn shopping_list() {
+	let mut entries = Entries::default()
+		.armor(1, "Helm", ArmorClass::Head, ArmorRank::Master, [0; 3], &[])
+		.armor(2, "Mail", ArmorClass::Chest, ArmorRank::Master, [0; 3], &[])
+		.charm(1, "Charm I", &[])
+		.charm(2, "Charm II", &[])
+		.weapon(1, "Sword I", WeaponClass::GreatSword, 100, [0; 3], &[])
+		.weapon(2, "Sword II", WeaponClass::GreatSword, 110, [0; 3], &[])
+		.weapon(3, "Sword III", WeaponClass::GreatSword, 120, [0; 3], &[])
+		.material(1, "Iron Ore")
+		.material(2, "Machalite Ore")
+		.material(3, "Monster Bone")
+		.recipe(RecipeKind::WeaponCreate, 1, &[(1, 2)])
+		.recipe(RecipeKind::WeaponCreate, 2, &[(1, 5)])
+		.recipe(RecipeKind::WeaponUpgrade, 2, &[(2, 1)])
+		.recipe(RecipeKind::WeaponUpgrade, 3, &[(2, 2), (3, 1)])
+		.recipe(RecipeKind::Armor, 1, &[(3, 2)])
+		.recipe(RecipeKind::Charm, 1, &[(1, 1)])
+		.recipe(RecipeKind::Charm, 2, &[(1, 3), (9, 1)]);
+	entries.weapons[1].previous_id = Some(1);
+	entries.weapons[2].previous_id = Some(2);
+	entries.charms[1].previous_id = Some(1);
+	let mut storage = Storage::new();
+	let report = storage.load_all(&entries).unwrap();
+	assert!(report.issues.iter().any(|i| i.to_string() == "recipe_item 2: no item with id 9"));
+
+	let mut equipment = Equipment::new();
+	equipment.try_add_weapon(AttachedDecorations::new(Arc::clone(storage.weapons.get(3).unwrap())));
+	equipment.try_add_armor(AttachedDecorations::new(Arc::clone(storage.armors.get(1).unwrap())));
+	equipment.try_add_armor(AttachedDecorations::new(Arc::clone(storage.armors.get(2).unwrap())));
+	equipment.try_add_charm(Arc::clone(storage.charms.get(2).unwrap()));
+	let quantities = |list: &ShoppingList| list.materials.iter().map(|(m, quantity)| (m.id, *quantity)).collect::<Vec<_>>();
+
+	let list = ShoppingList::new(&equipment, &storage, &Crafted::default());
+	let steps: Vec<String> = list.steps.iter().map(|(kind, name)| format!("{} {}", kind, name)).collect();
+	assert_eq!(steps, ["weapon create Sword II", "weapon upgrade Sword III", "armor Helm", "armor Mail", "charm Charm I", "charm Charm II"]);
+	assert_eq!(quantities(&list), [(1, 9), (2, 2), (3, 3)]);
+	assert_eq!(list.missing, ["Mail"]);
+
+	let mut crafted = Crafted::default();
+	crafted.weapons.insert(1);
+	crafted.armors.insert(2);
+	crafted.charms.insert(1);
+	let list = ShoppingList::new(&equipment, &storage, &crafted);
+	assert_eq!(list.steps.len(), 4);
+	assert_eq!(quantities(&list), [(1, 3), (2, 3), (3, 3)]);
+	assert!(list.missing.is_empty());
+	assert_eq!(list.to_csv().lines().nth(1), Some("1,\"Iron Ore\",0,3"));
+}
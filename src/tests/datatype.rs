@@ -1,14 +1,10 @@
-use std::{
-	borrow::Borrow,
-	sync::Arc,
-};
+use std::sync::Arc;
 use crate::data::{
 	mutable::attached_decorations::AttachedDecorations,
 	db_types::{
 		Item,
 		skill::{SkillLevel, SkillsLevel},
 	},
-	db::{get_armor_by_id, get_decorations_by_id, get_skill_by_id},
 };
 use crate::tests::Shared;
 
@@ -16,25 +12,25 @@ use crate::tests::Shared;
 fn attached_decorations() {
 	let shared = Shared::get();
 	let armors = &shared.storage.armors;
-	let armor = get_armor_by_id(armors, 1545).unwrap();  // Slots 4-4-2 Skill id 16 level 3
+	let armor = armors.get(1545).unwrap();  // Slots 4-4-2 Skill id 16 level 3
 	let mut armdec = AttachedDecorations::new(Arc::clone(armor));
-	let decorations = shared.storage.decorations.borrow();
+	let decorations = &shared.storage.decorations;
 
-	let deco1 = get_decorations_by_id(decorations, 150).unwrap();  // Size 4 Skill id 16  level 3
-	let deco2 = get_decorations_by_id(decorations, 149).unwrap();  // Skill id 73 size <3>
-	let deco3 = get_decorations_by_id(decorations, 143).unwrap();  // Skill id 86 size <4>
-	let deco4 = get_decorations_by_id(decorations, 53).unwrap();   // Skill id 47 size <2>
+	let deco1 = decorations.get(150).unwrap();  // Size 4 Skill id 16  level 3
+	let deco2 = decorations.get(149).unwrap();  // Skill id 73 size <3>
+	let deco3 = decorations.get(143).unwrap();  // Skill id 86 size <4>
+	let deco4 = decorations.get(53).unwrap();   // Skill id 47 size <2>
 
 	assert_eq!(armdec.try_add_deco(deco1), true);
 	assert_eq!(armdec.try_add_deco(deco2), true);
 	assert_eq!(armdec.try_add_deco(deco3), false);
 	assert_eq!(armdec.try_add_deco(deco4), true);
 
-	let skills = shared.storage.skills.borrow();
+	let skills = &shared.storage.skills;
 	let mut skill_list = SkillsLevel::new();
-	skill_list.insert(SkillLevel::new(Arc::clone(get_skill_by_id(skills,16).unwrap()), 6));
-	skill_list.insert(SkillLevel::new(Arc::clone(get_skill_by_id(skills,73).unwrap()), 3));
-	skill_list.insert(SkillLevel::new(Arc::clone(get_skill_by_id(skills,47).unwrap()), 1));
+	skill_list.insert(SkillLevel::new(Arc::clone(skills.get(16).unwrap()), 6));
+	skill_list.insert(SkillLevel::new(Arc::clone(skills.get(73).unwrap()), 3));
+	skill_list.insert(SkillLevel::new(Arc::clone(skills.get(47).unwrap()), 1));
 
 	let skills_level = armdec.get_skills();
 	assert_eq!(skills_level.len(), skill_list.len());
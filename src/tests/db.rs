@@ -0,0 +1,273 @@
+use std::{
+	path::Path,
+	str::FromStr,
+	sync::Arc,
+};
+use itertools::Itertools;
+use crate::data::{
+	db::{DataError, DB, DbError, LoadReport},
+	db_storage::Storage,
+	source::DataSource,
+	db_types::{Armors, ArmorSets, Charms, Decorations, Item, SetSkills, Skills, Weapons, skill::{SkillLevel, SkillsLevel}, weapon::WeaponFeature},
+};
+use crate::tests::{Shared, temp_db};
+
+#[test]
+fn database_location() {
+	// A configured path that no longer exists falls back to the default locations
+	let missing = Path::new("missing/mhw.db");
+	let db = DB::locate(Some(missing)).unwrap_or_else(|e| panic!("{}", e));
+	assert_ne!(db.path(), missing);
+	assert!(DB::candidates(Some(missing)).first().is_some_and(|p| p == missing));
+
+	// An explicit file must be a MHWorldData database
+	assert!(DB::open(db.path()).is_ok());
+	assert!(matches!(DB::open(Path::new("Cargo.toml")), Err(DbError::Open(..))));
+	assert!(matches!(DB::open(missing), Err(DbError::Open(..))));
+}
+
+const BROKEN_DB: &str = "
+CREATE TABLE language(id TEXT PRIMARY KEY, name TEXT);
+INSERT INTO language VALUES ('en', 'English');
+CREATE TABLE skilltree(id INTEGER PRIMARY KEY, max_level INTEGER, secret INTEGER, unlocks_id INTEGER);
+CREATE TABLE skilltree_text(id INTEGER, lang_id TEXT, name TEXT, description TEXT);
+INSERT INTO skilltree VALUES (1, 3, 0, NULL), (2, 1, 1, 99), (3, 2, 0, NULL);
+INSERT INTO skilltree_text VALUES (1, 'en', 'One', ''), (1, 'it', 'Uno', ''), (2, 'en', 'Two', '');
+CREATE TABLE armorset_bonus_skill(setbonus_id INTEGER, skilltree_id INTEGER, required INTEGER);
+CREATE TABLE armorset_bonus_text(id INTEGER, lang_id TEXT, name TEXT);
+CREATE TABLE armorset(id INTEGER PRIMARY KEY, rank TEXT, armorset_bonus_id INTEGER);
+CREATE TABLE armorset_text(id INTEGER, lang_id TEXT, name TEXT);
+CREATE TABLE armor(id INTEGER PRIMARY KEY, rank TEXT, armor_type TEXT, armorset_id INTEGER, armorset_bonus_id INTEGER, male INTEGER, female INTEGER,
+	slot_1 INTEGER, slot_2 INTEGER, slot_3 INTEGER, defense_base INTEGER, defense_max INTEGER, defense_augment_max INTEGER,
+	fire INTEGER, water INTEGER, thunder INTEGER, ice INTEGER, dragon INTEGER);
+CREATE TABLE armor_text(id INTEGER, lang_id TEXT, name TEXT);
+CREATE TABLE armor_skill(armor_id INTEGER, skilltree_id INTEGER, level INTEGER);
+INSERT INTO armor VALUES (1, 'HR', 'head', NULL, NULL, 1, 1, 1, 0, 0, 10, 20, 30, 0, 0, 0, 0, 0),
+	(2, 'XR', 'head', NULL, NULL, 1, 1, 1, 0, 0, 10, 20, 30, 0, 0, 0, 0, 0);
+INSERT INTO armor_text VALUES (1, 'en', 'Good'), (2, 'en', 'Bad rank');
+INSERT INTO armor_skill VALUES (1, 1, 1), (1, 42, 1);
+CREATE TABLE decoration(id INTEGER PRIMARY KEY, slot INTEGER, skilltree_id INTEGER, skilltree_level INTEGER, skilltree2_id INTEGER, skilltree2_level INTEGER);
+CREATE TABLE decoration_text(id INTEGER, lang_id TEXT, name TEXT);
+INSERT INTO decoration VALUES (1, 1, 1, 1, 42, 1);
+INSERT INTO decoration_text VALUES (1, 'it', 'Mista');
+CREATE TABLE charm(id INTEGER PRIMARY KEY, previous_id INTEGER);
+CREATE TABLE charm_skill(charm_id INTEGER, skilltree_id INTEGER, level INTEGER);
+CREATE TABLE charm_text(id INTEGER, lang_id TEXT, name TEXT);
+";
+
+/*
+Broken rows are reported and skipped, empty tables do not panic, a missing table stops the loading.
+Missing translations fall back to English, or to a placeholder, and are reported.
+*/
+#[test]
+fn load_report() {
+	let mut db = temp_db("broken", BROKEN_DB);
+	db.set_language(String::from("it"));
+	let mut storage = Storage::new();
+	let result = storage.load_all(&db);
+	assert!(matches!(result, Err(DataError::MissingTable(ref table)) if table == "weapon"), "{:?}", result.err());
+
+	let mut report = LoadReport::default();
+	let mut skills = Skills::new();
+	db.load_skills(&mut skills, &mut report).unwrap();
+	let mut armors = Armors::new();
+	db.load_armors(&mut armors, &skills, &SetSkills::new(), &mut report).unwrap();
+	let mut decorations = Decorations::new();
+	db.load_decorations(&mut decorations, &skills, &mut report).unwrap();
+	let mut charms = Charms::new();
+	db.load_charms(&mut charms, &skills, &mut report).unwrap();
+
+	let names: Vec<_> = skills.iter().sorted_by_key(|s| s.id).map(|s| s.name.to_string()).collect();
+	assert_eq!(names, ["Uno", "Two", "skilltree 3"]);
+	assert_eq!(armors.len(), 1);
+	assert_eq!(armors.iter().next().unwrap().name, "Good");
+	assert_eq!(armors.iter().next().unwrap().skills.len(), 1);
+	// Every item with a dangling skill is kept without it
+	assert_eq!(decorations.iter().map(|d| d.skills.len()).collect::<Vec<_>>(), [1]);
+	assert_eq!(report.fallbacks().collect::<Vec<_>>(), [("skilltree", 3), ("skilltree", 2), ("armor", 1)]);
+	let issues: Vec<String> = report.issues.iter().map(|i| i.to_string()).collect();
+	assert_eq!(issues, [
+		"skilltree 3: no name, placeholder used",
+		"skilltree 2: no name in language it, English used",
+		"skilltree 2: no skilltree with id 99",
+		"armor 1: no name in language it, English used",
+		"armor 1: no skilltree with id 42",
+		"armor 2: invalid rank \"XR\"",
+		"decoration 1: no skilltree with id 42",
+		"Nothing loaded from charm",
+	]);
+}
+
+const WEAPONS_DB: &str = "
+CREATE TABLE language(id TEXT PRIMARY KEY, name TEXT);
+INSERT INTO language VALUES ('en', 'English');
+CREATE TABLE skilltree(id INTEGER PRIMARY KEY, max_level INTEGER, secret INTEGER, unlocks_id INTEGER);
+CREATE TABLE skilltree_text(id INTEGER, lang_id TEXT, name TEXT, description TEXT);
+INSERT INTO skilltree VALUES (1, 7, 0, NULL), (2, 3, 0, NULL);
+INSERT INTO skilltree_text VALUES (1, 'en', 'Attack Boost', ''), (2, 'en', 'Free Elem', '');
+CREATE TABLE weapon(id INTEGER PRIMARY KEY, weapon_type TEXT, previous_weapon_id INTEGER, attack_true INTEGER, affinity INTEGER, sharpness TEXT, defense INTEGER,
+	slot_1 INTEGER, slot_2 INTEGER, slot_3 INTEGER, element1 TEXT, element1_attack INTEGER, element2 TEXT, element2_attack INTEGER, element_hidden INTEGER,
+	elderseal TEXT, armorset_bonus_id INTEGER, phial TEXT, phial_power INTEGER, shelling TEXT, shelling_level INTEGER, kinsect_bonus TEXT, notes TEXT,
+	coating_close INTEGER, coating_power INTEGER, coating_paralysis INTEGER, coating_poison INTEGER, coating_sleep INTEGER, coating_blast INTEGER, ammo_id INTEGER);
+CREATE TABLE weapon_text(id INTEGER, lang_id TEXT, name TEXT);
+CREATE TABLE weapon_skill(weapon_id INTEGER, skilltree_id INTEGER, level INTEGER);
+CREATE TABLE weapon_ammo(id INTEGER PRIMARY KEY, deviation TEXT, special_ammo TEXT,
+	normal1_clip INTEGER, normal1_rapid INTEGER, normal1_recoil INTEGER, normal1_reload TEXT,
+	spread2_clip INTEGER, spread2_rapid INTEGER, spread2_recoil INTEGER, spread2_reload TEXT,
+	sticky3_clip INTEGER, sticky3_rapid INTEGER, sticky3_recoil INTEGER, sticky3_reload TEXT);
+INSERT INTO weapon(id, weapon_type, previous_weapon_id, attack_true, affinity, sharpness, defense, slot_1, slot_2, slot_3,
+		element1, element1_attack, element_hidden, elderseal, phial, phial_power, shelling, shelling_level, coating_close, coating_poison, ammo_id)
+	VALUES (1, 'great-sword', NULL, 100, 0, '50,50', 0, 1, 0, 0, NULL, NULL, 0, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL),
+	(2, 'great-sword', 1, 120, 10, '50,50,50', 0, 2, 0, 0, 'Fire', 150, 1, 'low', NULL, NULL, NULL, NULL, NULL, NULL, NULL),
+	(3, 'bow', NULL, 90, 0, NULL, 10, 0, 0, 0, NULL, NULL, 0, NULL, NULL, NULL, NULL, NULL, 1, 1, NULL),
+	(4, 'charge-blade', NULL, 110, 0, '100', 0, 0, 0, 0, NULL, NULL, 0, NULL, 'power element', NULL, NULL, NULL, NULL, NULL, NULL),
+	(5, 'switch-axe', NULL, 110, 0, '100', 0, 0, 0, 0, NULL, NULL, 0, NULL, 'dragon', 300, NULL, NULL, NULL, NULL, NULL),
+	(6, 'gunlance', NULL, 110, 0, '100', 0, 0, 0, 0, NULL, NULL, 0, NULL, NULL, NULL, 'wide', 4, NULL, NULL, NULL),
+	(7, 'heavy-bowgun', NULL, 200, 0, NULL, 0, 0, 0, 0, NULL, NULL, 0, NULL, NULL, NULL, NULL, NULL, NULL, NULL, 1);
+INSERT INTO weapon_text VALUES (1, 'en', 'Buster Sword'), (2, 'en', 'Buster Blade'), (3, 'en', 'Hunter Bow'), (4, 'en', 'Chalice'),
+	(5, 'en', 'Axe'), (6, 'en', 'Gunlance'), (7, 'en', 'Bowgun');
+INSERT INTO weapon_skill VALUES (2, 1, 2), (2, 2, 1), (3, 1, 3);
+INSERT INTO weapon_ammo VALUES (1, 'none', 'wyvernheart', 6, 0, 2, 'normal', 3, 1, 3, 'slow', 0, 0, NULL, NULL);
+";
+
+fn weapons_db(name: &str) -> (Weapons, LoadReport) {
+	let mut db = temp_db(name, WEAPONS_DB);
+	db.set_language(String::from("en"));
+	let mut report = LoadReport::default();
+	let mut skills = Skills::new();
+	db.load_skills(&mut skills, &mut report).unwrap();
+	let mut weapons = Weapons::new();
+	db.load_weapons(&mut weapons, &skills, &SetSkills::new(), &mut report).unwrap();
+	(weapons, report)
+}
+
+// One weapon for each id, with every skill of weapon_skill at its level
+#[test]
+fn weapon_skills() {
+	let (weapons, report) = weapons_db("weapon-skills");
+	assert!(report.is_empty(), "{}", report);
+	let levels = |id| weapons.get(id).unwrap().skill.iter().map(|s| (s.get_id(), s.get_level())).sorted().collect_vec();
+	assert_eq!(weapons.len(), 7);
+	assert_eq!(levels(1), []);
+	assert_eq!(levels(2), [(1, 2), (2, 1)]);
+	assert_eq!(levels(3), [(1, 3)]);
+	let blade = weapons.get(2).unwrap();
+	assert_eq!((blade.attack_true, blade.previous_id, blade.get_slots()), (120, Some(1), vec![2, 0, 0]));
+}
+
+// The mechanics of each class, and the filters on them
+#[test]
+fn weapon_class_data() {
+	let (weapons, _) = weapons_db("weapon-class");
+	let data = |id| weapons.get(id).unwrap().class_data.to_string();
+	assert_eq!(data(1), "");
+	assert_eq!(data(3), "coatings close, poison");
+	assert_eq!(data(4), "power element phial");
+	assert_eq!(data(5), "dragon 300 phial");
+	assert_eq!(data(6), "wide shelling lv4");
+	assert_eq!(data(7), "deviation none, special wyvernheart, ammo normal1 x6, spread2 x3 rapid");
+
+	let matching = |feature: &str| {
+		let feature = WeaponFeature::from_str(feature).unwrap();
+		weapons.iter().filter(|w| w.class_data.has(&feature)).map(|w| w.id).sorted().collect_vec()
+	};
+	assert_eq!(matching("phial:Power Element"), [4]);
+	assert_eq!(matching("shelling:wide:3"), [6]);
+	assert!(matching("shelling:wide:5").is_empty());
+	assert_eq!(matching("coating:poison"), [3]);
+	assert_eq!(matching("ammo:spread2"), [7]);
+	assert!(matching("ammo:sticky3").is_empty());
+	assert!(WeaponFeature::from_str("sharpness:white").is_err());
+}
+
+// The validator finds what the loaders skip or do not survive, a missing table fails only its own checks
+#[test]
+fn validation() {
+	let db = temp_db("validate", &format!("{}
+		INSERT INTO armorset VALUES (1, 'HR', 7);
+		INSERT INTO armor VALUES (3, 'HR', 'head', 1, NULL, 1, 1, 0, 0, 0, 10, 20, 30, 0, 0, 0, 0, 0),
+			(4, 'HR', 'head', 1, NULL, 1, 1, 0, 0, 0, 10, 20, 30, 0, 0, 0, 0, 0);", BROKEN_DB));
+	let validation = db.validate();
+	let mut report = LoadReport::default();
+	let mut armors = Armors::new();
+	db.load_armors(&mut armors, &Skills::new(), &SetSkills::new(), &mut report).unwrap();
+	let mut sets = ArmorSets::new();
+	db.load_sets(&mut sets, &armors, &SetSkills::new(), &mut report).unwrap();
+
+	assert!(!validation.is_valid());
+	let issues: Vec<String> = validation.issues().map(|i| i.to_string()).collect();
+	for expected in [
+		"armor 2: invalid rank \"XR\"",
+		"armor 1: no skilltree with id 42",
+		"skilltree 2: no skilltree with id 99",
+		"armorset 1: no armorset_bonus with id 7",
+		"armorset 1: armors 3, 4 all in the head slot",
+	] {
+		assert!(issues.iter().any(|i| i == expected), "{} not in {:?}", expected, issues);
+	}
+	let check = |name: &str| validation.checks.iter().find(|c| c.name == name).unwrap();
+	assert!(check("armor types").issues.is_empty());
+	assert!(matches!(check("weapon types").issues[..], [DataError::MissingTable(ref table)] if table == "weapon"));
+	// The loader keeps the first armor of the slot
+	assert_eq!(sets.get(1).unwrap().armors[0].as_ref().map(|a| a.id), Some(3));
+	assert!(report.issues.iter().any(|i| matches!(i, DataError::SlotTaken { set: 1, armors, .. } if armors[..] == [3, 4])));
+}
+
+// A language change rewrites the names of the same Arcs
+#[test]
+fn reload_names() {
+	let mut db = DB::with_language(String::from("it")).unwrap_or_else(|e| panic!("{}", e));
+	let mut storage = Storage::new();
+	storage.load_all(&db).unwrap_or_else(|e| panic!("{}", e));
+	let skill = storage.get_skill_from_name("Bonus attacco").unwrap();
+	let mut constraints = SkillsLevel::new();
+	constraints.insert(SkillLevel::new(Arc::clone(&skill), 1));
+	let copy = storage.clone();
+
+	db.set_language(String::from("en"));
+	storage.reload_names(&db).unwrap_or_else(|e| panic!("{}", e));
+	assert!(Arc::ptr_eq(&skill, storage.get_skill_from_name("Attack Boost").as_ref().unwrap()));
+	// The copies made before the reload find the new names too
+	assert!(Arc::ptr_eq(&skill, copy.get_skill_from_name("Attack Boost").as_ref().unwrap()));
+	assert!(copy.get_skill_from_name("Bonus attacco").is_none());
+	assert_eq!(constraints.to_string(), "<Attack Boost 1>");
+	assert!(storage.get_skill_from_name("Bonus attacco").is_none());
+}
+
+// Names in a second language are shown next to the current ones and found by name
+#[test]
+fn secondary_names() {
+	let db = DB::with_language(String::from("it")).unwrap_or_else(|e| panic!("{}", e));
+	let mut storage = Storage::new();
+	storage.load_all(&db).unwrap_or_else(|e| panic!("{}", e));
+	storage.set_secondary_language(&db, Some("en")).unwrap_or_else(|e| panic!("{}", e));
+
+	let skill = storage.get_skill_from_name("Attack Boost").unwrap();
+	assert_eq!(skill.name.to_string(), "Bonus attacco (Attack Boost)");
+	assert!(Arc::ptr_eq(&skill, &storage.find_skill("bonus attacco").unwrap()));
+	assert!(Arc::ptr_eq(&skill, &storage.find_skill("attack boost").unwrap()));
+
+	storage.set_secondary_language(&db, None).unwrap_or_else(|e| panic!("{}", e));
+	assert_eq!(skill.name.to_string(), "Bonus attacco");
+	assert!(storage.find_skill("attack boost").is_none());
+}
+
+// The id and name indexes agree with a scan, also after items are dropped
+#[test]
+fn id_index() {
+	let shared = Shared::get();
+	let mut skills = shared.storage.skills.clone();
+	for skill in shared.storage.skills.iter() {
+		assert!(Arc::ptr_eq(skill, skills.get(skill.id).unwrap()));
+		assert!(Arc::ptr_eq(skill, skills.get_by_name(&skill.name.get().to_uppercase()).unwrap()));
+	}
+	let first = Arc::clone(skills.iter().next().unwrap());
+	skills.retain(|skill| skill.id != first.id);
+	assert_eq!(skills.len(), shared.storage.skills.len() - 1);
+	assert!(skills.get(first.id).is_none() && skills.get_by_name(&first.name.get()).is_none());
+	for skill in skills.iter() {
+		assert!(Arc::ptr_eq(skill, skills.get(skill.id).unwrap()));
+	}
+	assert!(skills.insert(Arc::clone(&first)).is_none());
+	assert!(Arc::ptr_eq(&first, skills.get(first.id).unwrap()));
+}
@@ -0,0 +1,45 @@
+use crate::data::{
+	db_storage::Storage,
+	db_types::{ArmorClass, ArmorRank},
+	diff::StorageDiff,
+	entries::Entries,
+};
+
+fn storage(entries: Entries) -> Storage {
+	let mut storage = Storage::new();
+	storage.load_all(&entries).unwrap_or_else(|e| panic!("{}", e));
+	storage
+}
+
+// Entities matched by id, only the fields that differ are listed
+#[test]
+fn storage_diff() {
+	let old = storage(Entries::default()
+		.skill(1, "Attack Boost", 7)
+		.skill(2, "Critical Eye", 7)
+		.armor(1, "Helm", ArmorClass::Head, ArmorRank::Master, [1, 0, 0], &[(1, 2)])
+		.charm(1, "Attack Charm", &[(1, 1)]));
+	let new = storage(Entries::default()
+		.skill(1, "Attack Boost", 8)
+		.skill(2, "Critical Eye", 7)
+		.armor(1, "Helm", ArmorClass::Head, ArmorRank::Master, [2, 0, 0], &[(1, 2), (2, 1)])
+		.decoration(1, "Attack Jewel", 1, &[(1, 1)]));
+	let diff = StorageDiff::new(&old, &new);
+
+	assert!(StorageDiff::new(&old, &old).is_empty());
+	assert_eq!(diff.to_string(), "\
+Skills: 0 added, 0 removed, 1 changed
+	~ 1 Attack Boost
+		max_level: 7 -> 8
+Armors: 0 added, 0 removed, 1 changed
+	~ 1 Helm
+		slots: 1, 0, 0 -> 2, 0, 0
+		skills: Attack Boost 2 -> Attack Boost 2, Critical Eye 1
+Decorations: 1 added, 0 removed, 0 changed
+	+ 1 Attack Jewel
+Charms: 0 added, 1 removed, 0 changed
+	- 1 Attack Charm");
+	let json = serde_json::to_value(&diff).unwrap();
+	assert_eq!(json["armors"]["changed"][0]["changes"][0]["new"], "2, 0, 0");
+	assert_eq!(json["charms"]["removed"][0]["name"], "Attack Charm");
+}
@@ -7,7 +7,13 @@ use crate::data::{
 	}
 };
 use crate::data::dyn_storage::DynamicStorage;
-use crate::engines::{EngineError::Impossible, Engine, greedy::Greedy};
+use crate::engines::{
+	EngineError::{Cancelled, Impossible},
+	Engine, Objective,
+	greedy::Greedy,
+	parallel::available_threads,
+	progress::SearchContext,
+};
 use crate::tests::Shared;
 
 
@@ -38,8 +44,9 @@ fn run(storage: &Arc<Storage>, constraints: SkillsLevel) {
 	println!("Requirements:\n{}", constraints.to_string());
 		let tmp = DynamicStorage::new(storage);
 		let clone = tmp.generate_storage();
-		let mut engine = Greedy::new(clone, constraints);
-	match engine.run() {
+		let ctx = SearchContext::new(&constraints);
+		let mut engine = Greedy::new(clone, constraints, Objective::Defence, available_threads());
+	match engine.run(&ctx) {
 		Ok(result) => {
 			let best = result.first().unwrap();
 			println!("Set Skills:\n{}", best.get_skills());
@@ -47,6 +54,7 @@ fn run(storage: &Arc<Storage>, constraints: SkillsLevel) {
 		},
 		Err(e) => match e {
 			Impossible => println!("Impossible"),
+			Cancelled => unreachable!(),
 		}
 	}
 	println!("--------------------------------");
@@ -1,24 +1,30 @@
 use std::{
 	mem,
 	sync::{Arc, Once},
-	ops::Range,
 };
-use glib::random_int_range;
 use rand::prelude::*;
+use crate::batch::generate_random_constraints;
 use crate::data::{
 	db_storage::Storage,
-	db::{DB, get_skill_by_id},
-	db_types::{
-		ID, Skills,
-		skill::{Skill, SkillLevel, SkillsLevel}
-	},
+	db::DB,
+	db_types::skill::{SkillLevel, SkillsLevel},
 };
 
 const RANDOM_LEN: usize = 10;
 
+mod batch;
+mod cache;
+mod crafting;
 mod datatype;
+mod db;
+mod diff;
 mod greedy;
 mod genetic;
+mod monster;
+mod pruning;
+mod records;
+mod server;
+mod source;
 
 
 struct Shared {
@@ -44,16 +50,27 @@ fn static_constraints(storage: &Storage) -> Vec<SkillsLevel> {
 	static_constraints
 }
 
+// A database made by sql in a temporary file, the file is removed once the database is open
+fn temp_db(name: &str, sql: &str) -> DB {
+	let path = std::env::temp_dir().join(format!("mhwiss-{}-{}.db", name, std::process::id()));
+	let _ = std::fs::remove_file(&path);
+	rusqlite::Connection::open(&path).unwrap().execute_batch(sql).unwrap();
+	let db = DB::open(&path).unwrap_or_else(|e| panic!("{}", e));
+	std::fs::remove_file(&path).unwrap();
+	db
+}
+
 impl Shared {
 	fn new() -> Arc<Self> {
 		let mut storage = Storage::new();
-		let db = DB::with_language(String::from("it"));
-		storage.load_all(&db);
+		let db = DB::with_language(String::from("it")).unwrap_or_else(|e| panic!("{}", e));
+		storage.load_all(&db).unwrap_or_else(|e| panic!("{}", e));
 		storage.print_stat();
 
 		let mut rand = Vec::new();
+		let mut rng = thread_rng();
 		for _ in 0..RANDOM_LEN {
-			if let Some(c) = generate_random_constraints(&storage.skills, random_int_range(1,10) as usize) {
+			if let Some(c) = generate_random_constraints(&storage.skills, rng.gen_range(1..10), &mut rng) {
 				rand.push(c);
 			}
 		}
@@ -77,38 +94,3 @@ impl Shared {
 		}
 	}
 }
-
-impl Storage {
-	pub fn get_skill_from_name(&self, name: &str) -> Option<Arc<Skill>> {
-		for skill in self.skills.iter() {
-			if skill.name == name {
-				return Some(Arc::clone(skill));
-			}
-		}
-		None
-	}
-}
-
-fn generate_random_constraints(skills: &Skills, len: usize) -> Option<SkillsLevel> {
-	let mut constraints = SkillsLevel::new();
-	let mut len = len;
-	if skills.len() < len {
-		return None;
-	}
-	let mut rng = rand::thread_rng();
-	while len > 0 {
-		let skill_id : ID = rng.gen_range::<ID, Range<ID>>(1..skills.len() as ID);
-		if constraints.contains_id(skill_id) {
-			continue;
-		}
-		if let Some(skill) = get_skill_by_id(skills, skill_id) {
-			let range = 1..=skill.max_level;
-			let level = rng.gen_range(range);
-			constraints.insert(SkillLevel::new(Arc::clone(skill), level));
-		} else {
-			continue;
-		}
-		len -= 1;
-	}
-	Some(constraints)
-}
\ No newline at end of file
@@ -0,0 +1,127 @@
+use std::sync::Arc;
+use itertools::Itertools;
+use crate::data::{
+	damage::{DamageSkills, Target, hit},
+	db::LoadReport,
+	db_storage::Storage,
+	db_types::{ElderSeal, Element, Monsters, Skills, WeaponClass, Weapons, skill::{SkillLevel, SkillsLevel}, weapon::Weapon},
+	entries::Entries,
+	source::DataSource,
+};
+use crate::engines::{Engines, Objective, progress::SearchContext};
+use crate::search::Search;
+use crate::tests::temp_db;
+
+const MONSTERS_DB: &str = "
+CREATE TABLE language(id TEXT PRIMARY KEY, name TEXT);
+INSERT INTO language VALUES ('en', 'English'), ('it', 'Italiano');
+CREATE TABLE skilltree(id INTEGER PRIMARY KEY, max_level INTEGER, secret INTEGER, unlocks_id INTEGER);
+CREATE TABLE skilltree_text(id INTEGER, lang_id TEXT, name TEXT, description TEXT);
+INSERT INTO skilltree VALUES (1, 5, 0, NULL), (2, 3, 0, NULL), (3, 5, 0, NULL), (4, 3, 0, NULL), (5, 6, 0, NULL);
+INSERT INTO skilltree_text VALUES (1, 'en', 'Earplugs', ''), (1, 'it', 'Tappi', ''), (2, 'en', 'Fire Resistance', ''),
+	(2, 'it', 'Resistenza al fuoco', ''), (3, 'en', 'Windproof', ''), (3, 'it', 'Antivento', ''),
+	(4, 'en', 'Weakness Exploit', ''), (4, 'it', 'Sfruttare punti deboli', ''), (5, 'en', 'Water Attack', ''), (5, 'it', 'Attacco acqua', '');
+CREATE TABLE monster(id INTEGER PRIMARY KEY, size TEXT,
+	weakness_fire INTEGER, weakness_water INTEGER, weakness_thunder INTEGER, weakness_ice INTEGER, weakness_dragon INTEGER,
+	weakness_poison INTEGER, weakness_sleep INTEGER, weakness_paralysis INTEGER, weakness_blast INTEGER, weakness_stun INTEGER,
+	ailment_roar TEXT, ailment_wind TEXT, ailment_tremor TEXT, ailment_defensedown INTEGER, ailment_fireblight INTEGER,
+	ailment_waterblight INTEGER, ailment_thunderblight INTEGER, ailment_iceblight INTEGER, ailment_dragonblight INTEGER,
+	ailment_blastblight INTEGER, ailment_regional INTEGER, ailment_poison INTEGER, ailment_sleep INTEGER, ailment_paralysis INTEGER,
+	ailment_bleed INTEGER, ailment_stun INTEGER, ailment_mud INTEGER, ailment_effluvia INTEGER);
+CREATE TABLE monster_text(id INTEGER, lang_id TEXT, name TEXT);
+INSERT INTO monster VALUES (1, 'large', 0, 3, 2, 1, 0, 1, 0, 0, 0, 0, 'large', 'small', NULL, 0, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 0),
+	(2, 'small', 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, NULL, NULL, NULL, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0);
+INSERT INTO monster_text VALUES (1, 'en', 'Anjanath'), (1, 'it', 'Anjanath'), (2, 'en', 'Jagras');
+CREATE TABLE monster_hitzone(id INTEGER PRIMARY KEY, monster_id INTEGER, cut INTEGER, impact INTEGER, shot INTEGER,
+	fire INTEGER, water INTEGER, ice INTEGER, thunder INTEGER, dragon INTEGER, ko INTEGER);
+CREATE TABLE monster_hitzone_text(id INTEGER, lang_id TEXT, name TEXT);
+INSERT INTO monster_hitzone VALUES (1, 1, 70, 60, 50, 0, 25, 15, 20, 5, 100), (2, 1, 40, 40, 35, 0, 50, 10, 10, 5, 0);
+INSERT INTO monster_hitzone_text VALUES (1, 'en', 'Head'), (1, 'it', 'Testa'), (2, 'en', 'Belly'), (2, 'it', 'Ventre');
+";
+
+fn weapon(id: u16, attack: u16, elements: Vec<(Element, u16)>) -> Arc<Weapon> {
+	Arc::new(Weapon::new(id, None, WeaponClass::GreatSword, format!("Sword {}", id), attack, 0, None, 0, [0; 3],
+		elements, false, ElderSeal::Empty, None, SkillsLevel::new()))
+}
+
+// MONSTERS_DB loaded in Italian
+fn load(name: &str) -> (Skills, Monsters, DamageSkills, LoadReport) {
+	let mut db = temp_db(name, MONSTERS_DB);
+	db.set_language(String::from("it"));
+	let mut report = LoadReport::default();
+	let mut skills = Skills::new();
+	db.load_skills(&mut skills, &mut report).unwrap();
+	let mut monsters = Monsters::new();
+	db.load_monsters(&mut monsters, &skills, &mut report).unwrap();
+	let mut damage_skills = DamageSkills::new();
+	db.load_damage_skills(&mut damage_skills, &skills, &mut report).unwrap();
+	(skills, monsters, damage_skills, report)
+}
+
+// Weaknesses, ailments and the skills against them by English name in any language, the weapons by weakness
+#[test]
+fn monster_suggestions() {
+	let (_, monsters, _, report) = load("monsters");
+
+	let issues: Vec<String> = report.issues.iter().map(|i| i.to_string()).collect();
+	assert_eq!(issues, ["monster 2: no name in language it, English used"]);
+	let anjanath = monsters.get(1).unwrap();
+	assert_eq!(anjanath.to_string(), "Anjanath [1], weak to water ★★★, thunder ★★, ice ★, poison ★, inflicts large roar, wind, fireblight, mud");
+	let suggested = anjanath.suggested.iter().map(|s| (s.get_id(), s.get_level())).sorted().collect_vec();
+	assert_eq!(suggested, [(1, 5), (2, 3), (3, 3)]);
+	let jagras = monsters.get(2).unwrap();
+	assert!(!jagras.large && jagras.suggested.len() == 0);
+
+	let mut weapons = Weapons::new();
+	weapons.insert(weapon(1, 200, Vec::new()));
+	weapons.insert(weapon(2, 100, vec![(Element::Thunder, 400)]));
+	weapons.insert(weapon(3, 100, vec![(Element::Water, 300)]));
+	weapons.insert(weapon(4, 100, vec![(Element::Fire, 500)]));
+	assert_eq!(anjanath.rank_weapons(&weapons).iter().map(|w| w.id).collect_vec(), [3, 2, 1, 4]);
+}
+
+// The damage on each hitzone, Weakness Exploit only on the weak spots, and the search that maximizes it
+#[test]
+fn hitzone_damage() {
+	let (skills, monsters, damage_skills, _) = load("hitzones");
+	let anjanath = monsters.get(1).unwrap();
+	assert_eq!(anjanath.hitzones.iter().map(|h| h.name.to_string()).collect_vec(), ["Testa", "Ventre"]);
+	let head = anjanath.find_hitzone("testa").unwrap();
+	assert_eq!((head.raw(WeaponClass::GreatSword), head.raw(WeaponClass::Hammer), head.element(Element::Water)), (70, 60, 25));
+	let belly = anjanath.find_hitzone("2").unwrap();
+
+	let mut with_skills = SkillsLevel::new();
+	with_skills.insert(SkillLevel::new(Arc::clone(skills.get(4).unwrap()), 3));
+	with_skills.insert(SkillLevel::new(Arc::clone(skills.get(5).unwrap()), 3));
+	let water = weapon(3, 100, vec![(Element::Water, 300)]);
+	let damage = hit(&water, &with_skills, &damage_skills, head);
+	assert_eq!(damage.affinity, 50);
+	assert!((damage.raw - 100. * 1.125 * 0.7).abs() < 0.01 && (damage.element - 40. * 0.25).abs() < 0.01);
+	let damage = hit(&water, &with_skills, &damage_skills, belly);
+	assert_eq!(damage.affinity, 0);
+	assert!((damage.total() - (40. + 40. * 0.5)).abs() < 0.01);
+
+	// Raw on the head, water on the belly
+	let mut entries = Entries::default()
+		.skill(4, "Weakness Exploit", 3)
+		.charm(1, "Charm", &[(4, 3)])
+		.weapon(1, "Raw", WeaponClass::GreatSword, 150, [0; 3], &[])
+		.weapon(2, "Water", WeaponClass::GreatSword, 100, [0; 3], &[]);
+	entries.weapons[1].elements = vec![(Element::Water, 600)];
+	let mut storage = Storage::new();
+	storage.load_all(&entries).unwrap();
+	storage.monsters.insert(Arc::clone(anjanath));
+	let mut constraints = SkillsLevel::new();
+	constraints.insert(SkillLevel::new(Arc::clone(storage.skills.get(4).unwrap()), 3));
+	for engine in [Engines::Greedy, Engines::HillClimbing] {
+		for (hitzone, expected) in [(1, 1), (2, 2)] {
+			let target = Target::new(&storage, Arc::clone(anjanath), hitzone).unwrap();
+			let bests = Search::new(engine, constraints.clone())
+				.with_threads(1)
+				.with_objective(Objective::Damage(target))
+				.run(&storage, &SearchContext::new(&constraints))
+				.unwrap_or_else(|_| panic!("{} found no equipment against hitzone {}", engine, hitzone));
+			assert_eq!(bests[0].weapon.as_ref().map(|w| w.item.id), Some(expected), "{}", engine);
+		}
+	}
+}
@@ -0,0 +1,37 @@
+use crate::data::{
+	db_storage::Storage,
+	db_types::{Item, skill::SkillsLevel},
+	mutable::equipment::Equipment,
+};
+use crate::engines::{Engine, EngineError, Objective, greedy::Greedy, progress::SearchContext};
+use crate::tests::Shared;
+
+#[test]
+fn pruning() {
+	let shared = Shared::get();
+	for constraints in shared.static_constraints.iter().chain(shared.random_constraints.iter()) {
+		let mut pruned: Storage = (*shared.storage).clone();
+		let report = pruned.prune_dominated(constraints);
+		assert_eq!(shared.storage.armors.len() - pruned.armors.len(), report.armors);
+		assert_eq!(shared.storage.charms.len() - pruned.charms.len(), report.charms);
+		assert_eq!(shared.storage.weapons.len() - pruned.weapons.len(), report.weapons);
+		assert_eq!(pruned.prune_dominated(constraints).total(), 0);
+
+		// Every skill still obtainable before the pruning is still obtainable after it
+		for skill in constraints.iter() {
+			let mut query = SkillsLevel::new();
+			query.insert(skill.clone());
+			let before = shared.storage.armors.iter().any(|a| a.has_skills(&query));
+			let after = pruned.armors.iter().any(|a| a.has_skills(&query));
+			assert_eq!(before, after);
+		}
+
+		let ctx = SearchContext::new(constraints);
+		let full = Greedy::new((*shared.storage).clone(), constraints.clone(), Objective::Defence, 1).run(&ctx);
+		let reduced = Greedy::new(pruned, constraints.clone(), Objective::Defence, 1).run(&ctx);
+		// Same outcome and same best defence on both pools
+		let best = |results: &Result<Vec<Equipment>, EngineError>| results.as_ref().ok()
+			.map(|r| (r[0].satisfies(constraints), Objective::Defence.value(&r[0])));
+		assert_eq!(best(&full), best(&reduced));
+	}
+}
@@ -0,0 +1,56 @@
+use crate::data::{
+	db_types::{ArmorClass, skill::SkillsLevel},
+	mutable::equipment::Equipment,
+	records::{ImportError, ItemRecord, SearchRecord, SkillLevelRecord},
+};
+use crate::engines::{Engine, Objective, greedy::Greedy, progress::SearchContext};
+use crate::tests::Shared;
+
+#[test]
+fn json_round_trip() {
+	let shared = Shared::get();
+	for constraints in shared.static_constraints.iter().chain(shared.random_constraints.iter()) {
+		let ctx = SearchContext::new(constraints);
+		let results = match Greedy::new((*shared.storage).clone(), constraints.clone(), Objective::Defence, 1).run(&ctx) {
+			Ok(results) => results,
+			Err(_) => continue,
+		};
+		let json = SearchRecord::new("Greedy", constraints, &results).to_json();
+		let record = SearchRecord::from_json(&json).ok().unwrap();
+
+		let loaded: SkillsLevel = record.constraints(&shared.storage).ok().unwrap();
+		assert_eq!(loaded.to_record(), constraints.to_record());
+		let loaded: Vec<Equipment> = record.results(&shared.storage).ok().unwrap();
+		assert!(loaded == results);
+		// Same equipment, same file
+		assert_eq!(SearchRecord::new("Greedy", constraints, &loaded).to_json(), json);
+	}
+}
+
+#[test]
+fn json_wrong_decoration() {
+	let shared = Shared::get();
+	let head = shared.storage.armors.iter()
+		.find(|a| a.class as usize == ArmorClass::Head as usize && a.slots[2] == 0)
+		.unwrap();
+	let decoration = shared.storage.decorations.iter().next().unwrap();
+	let mut record = Equipment::new().to_record();
+	record.head = Some(ItemRecord { id: head.id, name: String::new(), decorations: vec![None, None, Some(decoration.id)] });
+	assert!(matches!(Equipment::from_record(&record, &shared.storage), Err(ImportError::WrongDecoration { slot: 2, .. })));
+
+	// The same piece with empty slots loads back as it is
+	record.head.as_mut().unwrap().decorations = vec![None; 3];
+	let loaded = Equipment::from_record(&record, &shared.storage).ok().unwrap();
+	assert_eq!(loaded.to_record().head, record.head.map(|h| ItemRecord { name: head.name.get().to_string(), ..h }));
+}
+
+#[test]
+fn json_wrong_level() {
+	let shared = Shared::get();
+	let skill = shared.storage.skills.iter().next().unwrap();
+	let record = |level| vec![SkillLevelRecord { id: skill.id, name: String::new(), level }];
+	assert!(SkillsLevel::from_record(&record(skill.max_level), &shared.storage).is_ok());
+	for level in [0, skill.max_level + 1] {
+		assert!(matches!(SkillsLevel::from_record(&record(level), &shared.storage), Err(ImportError::WrongLevel { .. })));
+	}
+}
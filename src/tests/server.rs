@@ -0,0 +1,69 @@
+use std::{
+	io::{Read, Write},
+	net::{SocketAddr, TcpStream},
+	thread,
+};
+use serde_json::{json, Value};
+use crate::data::records::SearchRecord;
+use crate::server::Server;
+use crate::tests::Shared;
+
+// Minimal HTTP client, returns the status code and the JSON body
+fn request(addr: SocketAddr, method: &str, path: &str, body: &str) -> (u16, Value) {
+	let mut stream = TcpStream::connect(addr).unwrap();
+	write!(stream, "{} {} HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\nContent-Length: {}\r\n\r\n{}",
+		method, path, body.len(), body).unwrap();
+	let mut response = String::new();
+	stream.read_to_string(&mut response).unwrap();
+	let status = response.split(' ').nth(1).unwrap().parse().unwrap();
+	let body = response.split("\r\n\r\n").nth(1).unwrap();
+	(status, serde_json::from_str(body).unwrap())
+}
+
+#[test]
+fn server() {
+	let shared = Shared::get();
+	let server = Server::bind("127.0.0.1:0", (*shared.storage).clone(), 4).ok().unwrap();
+	let addr = server.local_addr();
+	thread::scope(|scope| {
+		scope.spawn(|| server.run());
+
+		let (status, skills) = request(addr, "GET", "/skills", "");
+		assert_eq!(status, 200);
+		assert_eq!(skills.as_array().unwrap().len(), shared.storage.skills.len());
+		let (status, armors) = request(addr, "GET", "/items/armors", "");
+		assert_eq!(status, 200);
+		assert_eq!(armors.as_array().unwrap().len(), shared.storage.armors.len());
+		let (status, monsters) = request(addr, "GET", "/items/monsters", "");
+		assert_eq!(status, 200);
+		assert_eq!(monsters.as_array().unwrap().len(), shared.storage.monsters.len());
+		assert_eq!(request(addr, "GET", "/items/quests", "").0, 404);
+		assert_eq!(request(addr, "POST", "/search", "{").0, 400);
+		let constraints = shared.static_constraints[0].to_record();
+		assert_eq!(request(addr, "POST", "/search", &json!({ "constraints": constraints, "threads": 0 }).to_string()).0, 400);
+		assert_eq!(request(addr, "POST", "/search", &json!({ "constraints": constraints, "threads": 10000 }).to_string()).0, 200);
+
+		// Several searches at once on the same storage
+		let searches: Vec<_> = shared.static_constraints.iter().map(|constraints| {
+			let body = json!({ "constraints": constraints.to_record() }).to_string();
+			scope.spawn(move || request(addr, "POST", "/search", &body))
+		}).collect();
+		for search in searches {
+			let (status, response) = search.join().unwrap();
+			assert_eq!(status, 200);
+			let record: SearchRecord = serde_json::from_value(response["search"].clone()).unwrap();
+			let results = record.results(&shared.storage).ok().unwrap();
+			let satisfied = results.first().is_some_and(|best| best.satisfies(&record.constraints(&shared.storage).ok().unwrap()));
+			assert_eq!(response["satisfied"].as_bool().unwrap(), satisfied);
+
+			if let Some(best) = record.results.first() {
+				let body = json!({ "equipment": best, "constraints": record.constraints }).to_string();
+				let (status, evaluation) = request(addr, "POST", "/evaluate", &body);
+				assert_eq!(status, 200);
+				assert_eq!(evaluation["satisfied"].as_bool().unwrap(), satisfied);
+				assert!(evaluation["shopping_list"]["materials"].is_array());
+			}
+		}
+		server.stop();
+	});
+}
@@ -0,0 +1,165 @@
+use std::{
+	fs,
+	panic::{self, AssertUnwindSafe},
+	str::FromStr,
+};
+use strum::IntoEnumIterator;
+use crate::data::{
+	db_storage::Storage,
+	db_types::{ArmorClass, ArmorRank, Item, WeaponClass, skill::{SkillLevel, SkillsLevel}, weapon::{ClassData, WeaponFeature}},
+	csv_source::CsvSource,
+	entries::Entries,
+	source::FileSource,
+};
+use crate::engines::{EngineError, Engines, progress::SearchContext};
+use crate::search::{Filters, Search, SearchEvent};
+
+// A storage made in the test, without any database, is enough for an engine
+#[test]
+fn memory_source() {
+	let entries = Entries::default()
+		.skill(1, "Attack Boost", 7)
+		.skill(2, "Critical Eye", 7)
+		.armor(1, "Helm", ArmorClass::Head, ArmorRank::Master, [1, 0, 0], &[(1, 2)])
+		.armor(2, "Mail", ArmorClass::Chest, ArmorRank::Master, [0, 0, 0], &[(1, 1), (2, 1)])
+		.charm(1, "Broken Charm", &[(9, 1)])
+		.decoration(1, "Attack Jewel", 1, &[(1, 1)]);
+	let mut storage = Storage::new();
+	let report = storage.load_all(&entries).unwrap_or_else(|e| panic!("{}", e));
+	let issues: Vec<String> = report.issues.iter().map(|i| i.to_string()).collect();
+	assert!(issues.contains(&String::from("charm 1: no skilltree with id 9")), "{:?}", issues);
+	assert_eq!(storage.charms.get(1).unwrap().skills.len(), 0);
+
+	let mut constraints = SkillsLevel::new();
+	constraints.insert(SkillLevel::new(storage.find_skill("attack boost").unwrap(), 4));
+	let bests = Search::new(Engines::Greedy, constraints.clone())
+		.run(&storage, &SearchContext::new(&constraints));
+	assert!(bests.is_ok_and(|bests| bests[0].satisfies(&constraints)));
+}
+
+// RON and JSON files, the missing ones are empty collections
+#[test]
+fn file_source() {
+	let dir = std::env::temp_dir().join(format!("mhwiss-files-{}", std::process::id()));
+	fs::create_dir_all(&dir).unwrap();
+	fs::write(dir.join("skills.ron"), r#"[(id: 1, name: "Attack Boost", max_level: 7)]"#).unwrap();
+	fs::write(dir.join("armors.json"), r#"[{"id": 5, "name": "Helm", "class": "Head", "rank": "Master", "slots": [2, 0, 0], "skills": [[1, 3]]}]"#).unwrap();
+	fs::write(dir.join("charms.json"), r#"[{"id": 1, "name": 3}]"#).unwrap();
+	let mut storage = Storage::new();
+	let error = storage.load_all(&FileSource::new(&dir)).err().map(|e| e.to_string());
+	fs::remove_file(dir.join("charms.json")).unwrap();
+	let mut storage = Storage::new();
+	let report = storage.load_all(&FileSource::new(&dir)).unwrap_or_else(|e| panic!("{}", e));
+	fs::remove_dir_all(&dir).unwrap();
+
+	assert!(error.is_some_and(|e| e.contains("charms.json")));
+	let helm = storage.armors.get(5).unwrap();
+	assert_eq!(helm.name, "Helm");
+	assert_eq!(helm.get_slots(), [2, 0, 0]);
+	assert_eq!(helm.skills.to_string(), "<Attack Boost 3>");
+	assert!(storage.weapons.is_empty());
+	assert!(report.issues.iter().any(|i| i.to_string() == "Nothing loaded from weapon"));
+}
+
+// The MHWorldData source files, linked by English name and translated from a name_<lang> column or a translations file
+#[test]
+fn csv_source() {
+	let dir = std::env::temp_dir().join(format!("mhwiss-csv-{}", std::process::id()));
+	let files = [
+		("skills/skill_base.csv", "name_en,name_it,max_level,unlocks\nAttack Boost,Bonus attacco,7,\nAgitator Secret,,1,Agitator\nAgitator,Provocatore,5,\n"),
+		("armors/armorset_bonus_base.csv", "name_en,skill1_name,skill1_required\nNergigante Hunter,Agitator Secret,4\n"),
+		("armors/armor_base.csv", "name_en,rank,type,armorset,male,female,slot_1,defense_base,skill1_name,skill1_level,skill2_name,skill2_level\n\
+			Nergigante Helm,HR,head,Nergigante,1,1,1,64,Attack Boost,2,,\n\
+			Nergigante Mail,HR,chest,Nergigante,1,1,0,64,Agitator,1,Unknown Skill,1\n"),
+		("armors/armor_base_translations.csv", "name_en,name_it\nNergigante Helm,Elmo Nergigante\n"),
+		("armors/armorset_base.csv", "name_en,rank,head,chest,bonus\nNergigante,HR,Nergigante Helm,Nergigante Mail,Nergigante Hunter\n"),
+		("charms/charm_base.csv", "name_en,skill1_name,skill1_level\nAttack Charm,Attack Boost,1\n"),
+		("decorations/decoration_base.csv", "name_en,slot,skill1_name,skill1_level\nAttack Jewel,1,Attack Boost,1\n"),
+		("weapons/weapon_base.csv", "id,name_en,weapon_type,previous_en,attack,affinity,slot_1,element1,element1_attack,elderseal,ammo_config\n\
+			10,Buster Sword I,great-sword,,384,0,0,,,,\n\
+			11,Buster Sword II,great-sword,Buster Sword I,432,-10,1,Fire,120,high,\n\
+			12,Hunter's Shot I,heavy-bowgun,,240,0,0,,,,Hunter's Shot\n"),
+		("weapons/weapon_ammo.csv", "key,deviation,special_ammo,normal1_clip,normal1_rapid,pierce1_clip\nHunter's Shot,none,wyvernheart,6,,0\n"),
+		("weapons/weapon_sharpness.csv", "name_en,red,orange,yellow,green\nBuster Sword II,100,50,50,50\n"),
+	];
+	for (file, text) in files {
+		let path = dir.join(file);
+		fs::create_dir_all(path.parent().unwrap()).unwrap();
+		fs::write(path, text).unwrap();
+	}
+	let mut storage = Storage::new();
+	let report = storage.load_all(&CsvSource::new(&dir, Some(String::from("it")))).unwrap_or_else(|e| panic!("{}", e));
+	fs::remove_dir_all(&dir).unwrap();
+
+	let issues: Vec<String> = report.issues.iter().map(|i| i.to_string()).collect();
+	assert!(issues.contains(&String::from("armor 2: invalid skill2_name \"Unknown Skill\"")), "{:?}", issues);
+	assert!(issues.contains(&String::from("armor 2: no name in language it, English used")), "{:?}", issues);
+	assert_eq!(storage.skills.get(2).unwrap().unlock.as_ref().map(|s| s.id), Some(3));
+	let helm = storage.armors.get(1).unwrap();
+	assert_eq!(helm.name, "Elmo Nergigante");
+	assert_eq!(helm.get_slots(), [1, 0, 0]);
+	assert_eq!(helm.skills.to_string(), "<Bonus attacco 2>");
+	assert_eq!(helm.set_skill.as_ref().map(|s| s.id), Some(1));
+	let set = storage.sets.get(1).unwrap();
+	assert_eq!(set.armors.iter().flatten().map(|a| a.id).collect::<Vec<_>>(), [1, 2]);
+	let sword = storage.weapons.get(11).unwrap();
+	assert_eq!(sword.previous_id, Some(10));
+	assert_eq!(sword.attack_true, 90);
+	assert_eq!(sword.sharpness, Some([100, 50, 50, 50, 0, 0, 0]));
+	assert_eq!(storage.weapons.get(12).unwrap().class_data.to_string(), "deviation none, special wyvernheart, ammo normal1 x6");
+	assert_eq!(storage.decorations.get(1).unwrap().skills.to_string(), "<Bonus attacco 1>");
+}
+
+// A weapon filter forces a matching weapon, also when no weapon helps the constraints
+#[test]
+fn weapon_filters() {
+	let mut entries = Entries::default()
+		.skill(1, "Attack Boost", 7)
+		.armor(1, "Helm", ArmorClass::Head, ArmorRank::Master, [0, 0, 0], &[(1, 2)])
+		.weapon(1, "Buster Sword", WeaponClass::GreatSword, 100, [0, 0, 0], &[])
+		.weapon(2, "Gunlance", WeaponClass::Gunlance, 110, [0, 0, 0], &[]);
+	entries.weapons[1].class_data = ClassData::Shelling { kind: String::from("wide"), level: 4 };
+	let mut storage = Storage::new();
+	storage.load_all(&entries).unwrap_or_else(|e| panic!("{}", e));
+	let mut constraints = SkillsLevel::new();
+	constraints.insert(SkillLevel::new(storage.find_skill("attack boost").unwrap(), 2));
+
+	let search = |engine, filters: Filters| {
+		let mut pool = storage.clone();
+		filters.apply(&mut pool);
+		Search::new(engine, constraints.clone())
+			.with_weapon_required(filters.requires_weapon())
+			.run(&pool, &SearchContext::new(&constraints))
+			.map(|bests| bests[0].weapon.as_ref().map(|w| w.item.id))
+	};
+	for engine in Engines::iter() {
+		assert!(search(engine, Filters::default()).is_ok_and(|weapon| weapon.is_none()));
+		let gunlance = Filters { weapon_features: vec![WeaponFeature::from_str("shelling:wide:3").unwrap()], ..Default::default() };
+		assert!(search(engine, gunlance).is_ok_and(|weapon| weapon == Some(2)));
+		let sword = Filters { weapon_classes: vec![WeaponClass::GreatSword], ..Default::default() };
+		assert!(search(engine, sword).is_ok_and(|weapon| weapon == Some(1)));
+		let bow = Filters { weapon_classes: vec![WeaponClass::Bow], ..Default::default() };
+		assert!(matches!(search(engine, bow), Err(EngineError::Impossible)));
+	}
+}
+
+// A panic in the search ends the progress reports instead of waiting for them forever
+#[test]
+fn search_panic() {
+	let entries = Entries::default()
+		.skill(1, "Attack Boost", 7)
+		.armor(1, "Helm", ArmorClass::Head, ArmorRank::Master, [0, 0, 0], &[(1, 2)]);
+	let mut storage = Storage::new();
+	storage.load_all(&entries).unwrap_or_else(|e| panic!("{}", e));
+	let mut constraints = SkillsLevel::new();
+	constraints.insert(SkillLevel::new(storage.find_skill("attack boost").unwrap(), 2));
+
+	let search = Search::new(Engines::Greedy, constraints.clone());
+	let ctx = SearchContext::new(&constraints);
+	let result = panic::catch_unwind(AssertUnwindSafe(|| search.run_with_events(&storage, &ctx, |event| {
+		if let SearchEvent::Pruned(_) = event {
+			panic!("Search failed");
+		}
+	})));
+	assert!(result.is_err());
+}
@@ -1,40 +1,46 @@
 use std::{
+	cell::RefCell,
 	env,
+	ops::Not,
 	rc::Rc,
 	str::FromStr,
+	sync::Mutex,
 };
 use gio::prelude::*;
 use glib::Receiver;
 use gtk::{Application, ComboBoxText, prelude::*};
 use strum::IntoEnumIterator;
 use crate::ui::pages::Pages;
-use crate::engines::{Engines, EnginesManager, EnginesManagerError};
-use crate::settings::Settings;
-use crate::data::{
-	mutable::equipment::Equipment,
-	db_storage::Storage,
-};
-use crate::data::dyn_storage::DynamicStorage;
+use mhwiss::engines::{Engines, EnginesManager, EnginesManagerError, progress::Progress};
+use mhwiss::search::SearchEvent;
+use mhwiss::settings::Settings;
+use mhwiss::data::crafting::ShoppingList;
+use mhwiss::data::db::{DB, DbError};
+use mhwiss::data::db_storage::Storage;
+use mhwiss::data::dyn_storage::DynamicStorage;
 
 pub(crate) mod pages;
 pub(crate) mod items;
 
-pub enum Callback {
-	Done(Vec<Equipment>),
-	Impossible,
-}
+// Id of the secondary language combo entry without a language
+const NO_LANGUAGE: &str = "none";
 
 pub struct Ui {
 	application: gtk::Application,
 	window: gtk::ApplicationWindow,
 	find_btn: gtk::Button,
+	cancel_btn: gtk::Button,
+	progress_bar: gtk::ProgressBar,
 	lang_combo: gtk::ComboBoxText,
+	secondary_lang_combo: gtk::ComboBoxText,
 	engines_combo: gtk::ComboBoxText,
+	threads_spin: gtk::SpinButton,
 
 	notebook: gtk::Notebook,
 	pages: Pages,
 
 	settings: Settings,
+	db: RefCell<DB>,  // Names are reloaded from here on a language change
 
 	pub(crate) storage: Rc<Storage>,
 	pub(crate) dynamic_storage: Rc<DynamicStorage>,
@@ -52,8 +58,67 @@ pub fn get_builder(path_from_root: String) -> gtk::Builder {
 	gtk::Builder::from_file(glade)  // ToDo: Use new_from_resources with some cargo tricks
 }
 
+/*
+First run: tell why the database was not opened and let the user choose the mhw.db file.
+Ask again until a valid database is chosen, None if the user gives up.
+*/
+pub fn ask_database(error: DbError) -> Option<DB> {
+	gtk::init().unwrap_or_else(|_| panic!("Failed to initialize GTK."));
+	let mut error = error;
+	loop {
+		let message = gtk::MessageDialog::new(
+			None::<&gtk::Window>,
+			gtk::DialogFlags::MODAL,
+			gtk::MessageType::Warning,
+			gtk::ButtonsType::OkCancel,
+			"MHWorldData database not found",
+		);
+		message.set_secondary_text(Some(&format!("{}\n\nChoose the mhw.db file.", error)));
+		let response = message.run();
+		message.close();
+		if response != gtk::ResponseType::Ok {
+			return None;
+		}
+
+		let chooser = gtk::FileChooserDialog::with_buttons(
+			Some("Choose mhw.db"),
+			None::<&gtk::Window>,
+			gtk::FileChooserAction::Open,
+			&[("_Cancel", gtk::ResponseType::Cancel), ("_Open", gtk::ResponseType::Accept)],
+		);
+		let filter = gtk::FileFilter::new();
+		filter.set_name(Some("SQLite database"));
+		filter.add_pattern("*.db");
+		chooser.add_filter(&filter);
+		let response = chooser.run();
+		let path = chooser.filename();
+		chooser.close();
+		match (response, path) {
+			(gtk::ResponseType::Accept, Some(path)) => match DB::open(&path) {
+				Ok(db) => return Some(db),
+				Err(e) => error = e,
+			},
+			_ => return None,
+		}
+	}
+}
+
+pub fn show_error(message: &str, details: &str) {
+	gtk::init().unwrap_or_else(|_| panic!("Failed to initialize GTK."));
+	let dialog = gtk::MessageDialog::new(
+		None::<&gtk::Window>,
+		gtk::DialogFlags::MODAL,
+		gtk::MessageType::Error,
+		gtk::ButtonsType::Close,
+		message,
+	);
+	dialog.set_secondary_text(Some(details));
+	dialog.run();
+	dialog.close();
+}
+
 impl Ui {
-	pub fn new(settings: Settings, storage: Storage) -> Rc<Self> {
+	pub fn new(settings: Settings, db: DB, storage: Storage) -> Rc<Self> {
 		gtk::init().unwrap_or_else(|_| panic!("Failed to initialize GTK."));
 		let builder = get_builder("res/gui/main.glade".to_string());
 
@@ -64,12 +129,20 @@ impl Ui {
 
 		let window = builder.object("main window").unwrap();
 		let find_btn = builder.object("find btn").unwrap();
+		let cancel_btn = builder.object("cancel btn").unwrap();
+		let progress_bar = builder.object("search progress").unwrap();
 		let lang_combo: ComboBoxText = builder.object("languages combo").unwrap();
+		let secondary_lang_combo: ComboBoxText = builder.object("secondary languages combo").unwrap();
 		let engines_combo: ComboBoxText = builder.object("engines combo").unwrap();
+		let threads_spin: gtk::SpinButton = builder.object("threads spin").unwrap();
 
 		let (sender, receiver) = glib::MainContext::channel(glib::PRIORITY_DEFAULT);
 
-		let engine_manager = Rc::new(EnginesManager::new(sender));
+		// The events come from the search thread, forward them to the main loop
+		let sender = Mutex::new(sender);
+		let engine_manager = Rc::new(EnginesManager::new(move |event| {
+			sender.lock().unwrap().send(event).expect("Error sending callback");
+		}));
 
 		let dynamic_storage =  Rc::new(DynamicStorage::new(&storage));
 		let pages = Pages::new(&builder, &dynamic_storage);
@@ -84,6 +157,14 @@ impl Ui {
 				lang_combo.insert(i as i32, Some(id), name.as_str())
 			}
 			lang_combo.set_active_id(Some(settings.get_language().as_ref()));
+
+			secondary_lang_combo.insert(0, Some(NO_LANGUAGE), "-");
+			for (i, (id, name)) in settings.get_available_languages().iter().enumerate() {
+				secondary_lang_combo.insert(i as i32 + 1, Some(id), name.as_str())
+			}
+			secondary_lang_combo.set_active_id(Some(settings.get_secondary_language().as_deref().unwrap_or(NO_LANGUAGE)));
+
+			threads_spin.set_value(settings.get_threads() as f64);
 		}
 
 
@@ -91,12 +172,17 @@ impl Ui {
 			application,
 			window,
 			find_btn,
+			cancel_btn,
+			progress_bar,
 			lang_combo,
+			secondary_lang_combo,
 			engines_combo,
+			threads_spin,
 
 			notebook: builder.object("notebook").unwrap(),
 			pages,
 			settings,
+			db: RefCell::new(db),
 
 			storage: Rc::new(storage),
 			dynamic_storage,
@@ -106,27 +192,93 @@ impl Ui {
 		app
 	}
 
-	fn connect_signals(self: &Rc<Self>, receiver: Receiver<Callback>) {
+	fn connect_signals(self: &Rc<Self>, receiver: Receiver<SearchEvent>) {
 		// Button for starting the background engine thread
 		{
 			let app = Rc::clone(self);
 			self.find_btn.connect_clicked(move |_btn| {
 				let engine = Engines::from_str(app.engines_combo.active_text().unwrap().as_str()).unwrap();
-				let result = app.engine_manager.spawn(engine, &app.dynamic_storage);
-				if let Err(e) = result {
-					match e {
-						EnginesManagerError::AlreadyRunning => { println!("UI: Engine already running")}
-						EnginesManagerError::NoConstraints => { println!("UI: No constraints") }
-					}
+				let filters = match app.pages.skills_page.weapon_filters() {
+					Ok(filters) => filters,
+					Err(e) => return show_error("Invalid weapon filter", &e),
+				};
+				let result = app.engine_manager.spawn(engine, &app.dynamic_storage, &filters, app.settings.get_threads());
+				match result {
+					Ok(()) => app.search_started(),
+					Err(EnginesManagerError::AlreadyRunning) => { println!("UI: Engine already running")}
+					Err(EnginesManagerError::NoConstraints) => { println!("UI: No constraints") }
 				}
 			});
 		}
-		// Language selector and change the language in the settings
+		// Stop the running engine
+		{
+			let app = Rc::clone(self);
+			self.cancel_btn.connect_clicked(move |_btn| {
+				app.engine_manager.cancel();
+			});
+		}
+		// Language selector, reload the names and show them everywhere
 		{
 			let app = Rc::clone(self);
 			self.lang_combo.connect_changed(move |new_lang| {
-				let language = new_lang.active_id().unwrap();
-				app.settings.change_language(language.parse().unwrap());
+				let language = new_lang.active_id().unwrap().to_string();
+				app.settings.change_language(language.clone());
+				let mut db = app.db.borrow_mut();
+				db.set_language(language);
+				match app.storage.reload_names(&db) {
+					Ok(report) => {
+						if report.is_empty().not() {
+							eprintln!("{}", report);
+						}
+						app.pages.refresh();
+					}
+					Err(e) => show_error("Failed to change the language", &e.to_string()),
+				}
+			});
+		}
+		// Names also in a second language, next to the current ones
+		{
+			let app = Rc::clone(self);
+			self.secondary_lang_combo.connect_changed(move |combo| {
+				let language = combo.active_id().map(|id| id.to_string()).filter(|id| id != NO_LANGUAGE);
+				app.settings.change_secondary_language(language.clone());
+				match app.storage.set_secondary_language(&app.db.borrow(), language.as_deref()) {
+					Ok(()) => app.pages.refresh(),
+					Err(e) => show_error("Failed to change the language", &e.to_string()),
+				}
+			});
+		}
+		// Clicking an additional skill adds it as a constraint and search again
+		{
+			let app = Rc::clone(self);
+			self.pages.found_page.connect_additional_skill(move |skill_id, extra| {
+				let current = app.dynamic_storage.get_constraints().iter()
+					.find(|skill| skill.get_id() == skill_id)
+					.map_or(0, |skill| skill.get_level());
+				app.pages.skills_page.set_level(skill_id, current + extra);
+				app.find_btn.clicked();
+			});
+		}
+		// A target monster raises the skills against its ailments, never lowers them
+		{
+			let app = Rc::clone(self);
+			self.pages.skills_page.connect_monster(move |monster_id| {
+				let monster = monster_id.and_then(|id| app.storage.monsters.get(id));
+				if let Some(monster) = monster {
+					let mut constraints = app.dynamic_storage.get_constraints();
+					monster.apply_suggested(&mut constraints);
+					for skill in constraints.iter() {
+						app.pages.skills_page.set_level(skill.get_id(), skill.get_level());
+					}
+				}
+				app.pages.skills_page.show_monster(monster.map(|m| m.as_ref()), &app.storage.weapons);
+			});
+		}
+		// Number of threads used by the engines
+		{
+			let app = Rc::clone(self);
+			self.threads_spin.connect_value_changed(move |spin| {
+				app.settings.set_threads(spin.value() as usize);
 			});
 		}
 		// Callback for the UI update when the running engine return the best equipment.
@@ -134,15 +286,32 @@ impl Ui {
 			let app = Rc::clone(self);
 			receiver.attach(None, move |action| {
 				match action {
-					Callback::Done(results) => {
-						app.engine_manager.ended();
+					SearchEvent::Pruned(pruned) => println!("Pruned {}", pruned),
+					SearchEvent::Progress(progress) => {
+						if app.engine_manager.is_running() {
+							app.show_progress(&progress);
+						}
+					}
+					SearchEvent::Done(results) => {
+						if let Some(best) = results.first() {
+							app.pages.found_page.update_shopping_list(ShoppingList::new(best, &app.storage, &app.settings.get_crafted()));
+						}
 						app.pages.found_page.update(results);
 						app.notebook.set_current_page(Some(app.notebook.n_pages() - 1));
 					}
-					Callback::Impossible => {
+					SearchEvent::AdditionalSkills(skills) => {
+						app.search_ended("Done");
+						app.pages.found_page.update_additional_skills(skills);
+					}
+					SearchEvent::Impossible => {
+						app.search_ended("Impossible");
 						println!("Engine: Impossible to find");
 						// TODO add gui message
 					}
+					SearchEvent::Cancelled => {
+						app.search_ended("Cancelled");
+						println!("Engine: Cancelled");
+					}
 				}
 				glib::Continue(true)
 			});
@@ -167,6 +336,26 @@ impl Ui {
 		}
 	}
 
+	fn search_started(&self) {
+		self.find_btn.set_sensitive(false);
+		self.cancel_btn.set_sensitive(true);
+		self.progress_bar.set_fraction(0.0);
+		self.progress_bar.set_text(Some("Searching..."));
+	}
+
+	fn show_progress(&self, progress: &Progress) {
+		self.progress_bar.set_fraction(progress.fraction());
+		self.progress_bar.set_text(Some(format!("{} nodes, {}/{} levels, {:.1}s",
+			progress.explored, progress.best_score, progress.max_score, progress.elapsed.as_secs_f32()).as_str()));
+	}
+
+	fn search_ended(&self, message: &str) {
+		self.engine_manager.ended();
+		self.find_btn.set_sensitive(true);
+		self.cancel_btn.set_sensitive(false);
+		self.progress_bar.set_text(Some(message));
+	}
+
 	pub fn start(self: &Rc<Self>) {
 		self.pages.insert_widgets_tabs(Rc::clone(self));
 		self.window.show_all();
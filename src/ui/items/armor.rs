@@ -3,7 +3,7 @@ use std::rc::Rc;
 use gdk_pixbuf::Pixbuf;
 use gtk::Builder;
 use gtk::prelude::{BuilderExtManual, ImageExt, LabelExt, WidgetExt};
-use crate::data::{
+use mhwiss::data::{
 	mutable::attached_decorations::AttachedDecorations,
 	db_types::{
 		*,
@@ -85,7 +85,7 @@ impl UI<AttachedDecorations<Armor>> for GtkArmour {
 	fn show(&self, item: &AttachedDecorations<Armor>) {
 		let piece = item.get_item();
 		self.image.set_from_pixbuf(self.images.get(format!("{}", self.class.to_string()).as_str()));
-		self.name.set_text(piece.name.as_str());
+		self.name.set_text(&piece.name.to_string());
 		for (i, armor_skill) in piece.skills.iter().enumerate() {
 			self.skill[i].set_text(format!("{} {}", armor_skill.get_skill().name, armor_skill.get_level()).as_str());
 			self.skill[i].show();
@@ -6,7 +6,7 @@ use std::{
 use gdk_pixbuf::Pixbuf;
 use gtk::Builder;
 use gtk::prelude::{BuilderExtManual, ImageExt, LabelExt, WidgetExt};
-use crate::data::db_types::charm::Charm;
+use mhwiss::data::db_types::charm::Charm;
 use crate::ui::items::UI;
 
 
@@ -46,7 +46,7 @@ impl UI<Arc<Charm>> for GtkCharm {
 
 	fn show(&self, item: &Arc<Charm>) {
 		self.image.set_from_pixbuf(self.images.get("charm"));
-		self.name.set_text(item.name.as_str());
+		self.name.set_text(&item.name.to_string());
 		for (i, charm_skill) in item.skills.iter().enumerate() {
 			self.skill[i].set_text(format!("{} {}", charm_skill.get_skill().name, charm_skill.get_level()).as_str());
 			self.skill[i].show();
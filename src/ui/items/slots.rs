@@ -6,7 +6,7 @@ use gdk_pixbuf::Pixbuf;
 use gtk::Builder;
 use gtk::prelude::{BuilderExtManual, ImageExt, LabelExt};
 
-use crate::data::db_types::decoration::Decoration;
+use mhwiss::data::db_types::decoration::Decoration;
 
 pub struct GtkSlot {
 	pub(crate) label: gtk::Label,
@@ -41,7 +41,7 @@ impl GtkSlot {
 	}
 
 	pub fn show(&self, item: &Arc<Decoration>, size: u8) {
-		self.label.set_text(item.name.as_str());
+		self.label.set_text(&item.name.to_string());
 		self.image.set_from_pixbuf(self.images.get(format!("slot {} {}", size, item.size).as_str()));
 	}
 }
\ No newline at end of file
@@ -5,12 +5,12 @@ use gdk_pixbuf::Pixbuf;
 use gtk::Builder;
 use gtk::prelude::{BuilderExtManual, ImageExt, LabelExt};
 
-use crate::data::db_types::{
+use mhwiss::data::db_types::{
 	tool::Tool,
 };
 use crate::ui::items::slots::GtkSlot;
 use crate::ui::items::UI;
-use crate::data::mutable::attached_decorations::AttachedDecorations;
+use mhwiss::data::mutable::attached_decorations::AttachedDecorations;
 
 pub struct GtkTool {
 	name: gtk::Label,
@@ -55,6 +55,6 @@ impl UI<AttachedDecorations<Tool>> for GtkTool {
 	fn show(&self, item: &AttachedDecorations<Tool>) {
 		let tool = item.get_item();
 		self.image.set_from_pixbuf(self.images.get("booster empty"));
-		self.name.set_text(tool.name.as_str());
+		self.name.set_text(&tool.name.to_string());
 	}
 }
\ No newline at end of file
@@ -5,8 +5,8 @@ use gdk_pixbuf::Pixbuf;
 use gtk::Builder;
 use gtk::prelude::{BuilderExtManual, ImageExt, LabelExt, WidgetExt};
 
-use crate::data::db_types::weapon::Weapon;
-use crate::data::mutable::attached_decorations::AttachedDecorations;
+use mhwiss::data::db_types::weapon::Weapon;
+use mhwiss::data::mutable::attached_decorations::AttachedDecorations;
 use crate::ui::items::slots::GtkSlot;
 use crate::ui::items::UI;
 
@@ -17,6 +17,7 @@ pub struct GtkWeapon {
 	affinity: gtk::Label,
 	element: [gtk::Label; 2],
 	skill: gtk::Label,
+	class_data: gtk::Label,
 	slots: Vec<GtkSlot>,
 	images: Rc<HashMap<String, Pixbuf>>,
 }
@@ -36,6 +37,7 @@ impl GtkWeapon {
 			affinity: builder.object("weapon affinity").unwrap(),
 			element: [builder.object("weapon element 1").unwrap(), builder.object("weapon element 2").unwrap()],
 			skill: builder.object("weapon skill").unwrap(),
+			class_data: builder.object("weapon class data").unwrap(),
 			slots,
 			images,
 		}
@@ -57,6 +59,7 @@ impl UI<AttachedDecorations<Weapon>> for GtkWeapon {
 		self.attack.set_text("-");
 		self.affinity.set_text("-");
 		self.skill.set_text("-");
+		self.class_data.set_text("");
 		self.element[0].set_text("");
 		self.element[1].set_text("");
 		for slot in self.slots.iter() {
@@ -67,11 +70,12 @@ impl UI<AttachedDecorations<Weapon>> for GtkWeapon {
 	fn show(&self, item: &AttachedDecorations<Weapon>) {
 		let weapon = item.get_item();
 		self.image.set_from_pixbuf(self.images.get(format!("{}", weapon.class.to_string()).as_str()));
-		self.name.set_text(weapon.name.as_str());
+		self.name.set_text(&weapon.name.to_string());
 		for skill_level in weapon.skill.iter() {
-			self.skill.set_text(skill_level.get_skill().name.as_str());
+			self.skill.set_text(&skill_level.get_skill().name.to_string());
 			self.skill.show();
 		}
+		self.class_data.set_text(&weapon.class_data.to_string());
 		for (i, _size) in weapon.slots.iter().enumerate() {
 			self.slots[i].empty(0);
 		}
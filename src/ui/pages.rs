@@ -1,4 +1,5 @@
 use std::{
+	cell::RefCell,
 	collections::HashMap,
 	rc::Rc,
 };
@@ -15,7 +16,7 @@ use crate::ui::{
 		result::ResultPage,
 	}
 };
-use crate::data::{
+use mhwiss::data::{
 	db_types::{ArmorClass, Element},
 	dyn_storage::DynamicStorage,
 };
@@ -30,7 +31,7 @@ pub const NORMAL_SIZE_ICON: i32 = 60;
 pub const SMALL_SIZE_ICON: i32 = 25;
 
 pub(crate) struct Pages {
-	skills_page: SkillsPage,
+	pub(crate) skills_page: SkillsPage,
 	armors_page: Rc<ArmorsPage>,
 	decos_page: DecorationsPage,
 	charms_page: CharmsPage,
@@ -50,6 +51,14 @@ impl Pages {
 		}
 	}
 
+	// Show the names again after a language change
+	pub fn refresh(&self) {
+		self.skills_page.refresh();
+		self.armors_page.refresh();
+		self.decos_page.refresh();
+		self.found_page.refresh();
+	}
+
 	// TOOD: This methods do not show anything instead this method create the UI widgets,
 	// TOOD: we should implement some dynamic loading for better performance
 	pub fn insert_widgets_tabs(&self, app: Rc<Ui>) {
@@ -60,6 +69,24 @@ impl Pages {
 	}
 }
 
+// Widgets showing localized names, each one with the closure that writes its text
+#[derive(Default)]
+pub(crate) struct Refresher(RefCell<Vec<Box<dyn Fn()>>>);
+
+impl Refresher {
+	// Run update now and again on every refresh()
+	pub fn bind<F: Fn() + 'static>(&self, update: F) {
+		update();
+		self.0.borrow_mut().push(Box::new(update));
+	}
+
+	pub fn refresh(&self) {
+		for update in self.0.borrow().iter() {
+			update();
+		}
+	}
+}
+
 /* TODO how the hell i can return a closure ??
 fn get_search_function(search_bar: &SearchEntry) -> Box<(Fn(& FlowBoxChild) -> bool)> {
 	let search_bar = search_bar.clone();
@@ -6,14 +6,14 @@ use gdk_pixbuf::Pixbuf;
 use gtk::{Builder, ListBoxRow};
 use gtk::prelude::*;
 use itertools::Itertools;
-use crate::data::{
+use mhwiss::data::{
 	db_storage::Storage,
 	db_types::ArmorClass,
 };
-use crate::data::db_types::ArmorRank;
+use mhwiss::data::db_types::ArmorRank;
 use crate::ui::{
 	*,
-	pages::{set_image_scaled, SMALL_SIZE_ICON},
+	pages::{set_image_scaled, Refresher, SMALL_SIZE_ICON},
 };
 use strum::EnumCount;
 
@@ -21,6 +21,7 @@ pub(crate) struct ArmorsPage {
 	rank_tabs: [gtk::ListBox; ArmorRank::COUNT],
 	rank_switches: [gtk::Switch; ArmorRank::COUNT],
 	images: Rc<HashMap<String, Pixbuf>>,
+	names: Refresher,
 }
 
 impl ArmorsPage {
@@ -39,6 +40,7 @@ impl ArmorsPage {
 			rank_tabs,
 			rank_switches,
 			images,
+			names: Default::default(),
 		}
 	}
 
@@ -62,7 +64,8 @@ impl ArmorsPage {
 			let builder = get_builder("res/gui/set box.glade".to_string());
 			let set_row: gtk::ListBoxRow = builder.object("row").unwrap();
 			let name: gtk::Label = builder.object("name").unwrap();
-			name.set_text(&set.name);
+			let set_copy = Arc::clone(set);
+			self.names.bind(move || name.set_text(&set_copy.name.to_string()));
 			for piece in ArmorClass::iter() {
 				let image: gtk::Image = builder.object(piece.to_string().as_str()).unwrap();
 				if set.get_armor(piece).is_some() {
@@ -82,4 +85,8 @@ impl ArmorsPage {
 			});
 		}
 	}
-}
\ No newline at end of file
+
+	pub fn refresh(&self) {
+		self.names.refresh();
+	}
+}
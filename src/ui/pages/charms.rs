@@ -1,7 +1,7 @@
 use std::rc::Rc;
 
-use crate::data::db_storage::Storage;
-use crate::data::dyn_storage::DynamicStorage;
+use mhwiss::data::db_storage::Storage;
+use mhwiss::data::dyn_storage::DynamicStorage;
 
 pub(crate) struct CharmsPage {
 
@@ -1,18 +1,20 @@
 use std::rc::Rc;
+use std::sync::Arc;
 use gtk::{Builder, FlowBoxChild, SizeGroupMode};
 use gtk::prelude::*;
 use itertools::Itertools;
-use crate::data::{
+use mhwiss::data::{
 	db_storage::Storage,
 	dyn_storage::DynamicStorage,
 };
-use crate::ui::get_builder;
+use crate::ui::{get_builder, pages::Refresher};
 
 pub(crate) struct DecorationsPage {
 	deco_list: [gtk::FlowBox; 4],
 	search_bar: gtk::SearchEntry,
 	quantity_btn: gtk::SpinButton,
 	set_quantity_btn: gtk::Button,
+	names: Refresher,
 }
 
 impl DecorationsPage {
@@ -28,6 +30,7 @@ impl DecorationsPage {
 			search_bar: builder.object("decoration search bar").unwrap(),
 			quantity_btn: builder.object("quantity deco").unwrap(),
 			set_quantity_btn: builder.object("set quantity deco").unwrap(),
+			names: Default::default(),
 		};
 		page.connect_signals();
 		page
@@ -89,9 +92,14 @@ impl DecorationsPage {
 			style.add_provider(&provider, gtk::STYLE_PROVIDER_PRIORITY_USER);
 			style.add_class("FlowBoxSkill");  // TODO: Better implementation using glades => Add this feature in glade
 
-			name.set_text(deco.name.as_str());
+			let deco_copy = Arc::clone(deco);
+			self.names.bind(move || name.set_text(&deco_copy.name.to_string()));
 			size_group.add_widget(&deco_flowbox_child);
 			self.deco_list[deco.size as usize - 1].insert(&deco_flowbox_child, -1);
 		}
 	}
+
+	pub fn refresh(&self) {
+		self.names.refresh();
+	}
 }
\ No newline at end of file
@@ -1,13 +1,18 @@
 use std::{
+	cell::RefCell,
 	collections::HashMap,
+	fs,
+	ops::Not,
 	rc::Rc,
+	sync::Arc,
 };
 use gdk_pixbuf::Pixbuf;
 use gtk::prelude::*;
 use itertools::Itertools;
 use strum::IntoEnumIterator;
-use crate::data::{
-	db_types::{Item, ArmorClass, Element},
+use mhwiss::data::{
+	crafting::ShoppingList,
+	db_types::{ID, Item, ArmorClass, Element, Level, skill::Skill},
 	mutable::equipment::Equipment,
 };
 use crate::ui::{
@@ -24,6 +29,7 @@ use crate::ui::{
 		set_image
 	},
 	get_builder,
+	show_error,
 };
 
 pub struct ResultPage {
@@ -35,7 +41,14 @@ pub struct ResultPage {
 	skills_summary: gtk::ListBox,
 	decorations_summary: gtk::ListBox,
 	defences_summary: Vec<gtk::Label>,
+	additional_skills: gtk::TreeView,
+	additional_skills_store: gtk::ListStore,
+	shopping_list: gtk::ListBox,
+	export_shopping_btn: gtk::Button,
 	images: Rc<HashMap<String, Pixbuf>>,
+	results: RefCell<Vec<Equipment>>,  // Shown now, kept for refresh()
+	additional: RefCell<Vec<(Arc<Skill>, Level)>>,
+	shopping: Rc<RefCell<Option<ShoppingList>>>,  // Of the best result, shared with the export button
 }
 
 impl ResultPage {
@@ -60,8 +73,16 @@ impl ResultPage {
 			skills_summary: builder.object("skills summary").unwrap(),
 			decorations_summary: builder.object("decorations summary").unwrap(),
 			defences_summary,
+			additional_skills: builder.object("additional skills").unwrap(),
+			additional_skills_store: builder.object("additional skills store").unwrap(),
+			shopping_list: builder.object("shopping list").unwrap(),
+			export_shopping_btn: builder.object("export shopping list btn").unwrap(),
 			images,
+			results: Default::default(),
+			additional: Default::default(),
+			shopping: Default::default(),
 		};
+		f.connect_export();
 		f.set_fixed_images(builder);
 		f.empty();
 		f
@@ -102,10 +123,65 @@ impl ResultPage {
 		self.defences_summary.get(3).unwrap().set_text("");
 		self.defences_summary.get(4).unwrap().set_text("");
 		self.defences_summary.get(5).unwrap().set_text("");
+		self.additional_skills_store.clear();
+	}
+
+	// Save the shopping list as CSV where the user chooses
+	fn connect_export(&self) {
+		let shopping = Rc::clone(&self.shopping);
+		self.export_shopping_btn.connect_clicked(move |_btn| {
+			let shopping = shopping.borrow();
+			let list = match shopping.as_ref() {
+				Some(list) => list,
+				None => return,
+			};
+			let chooser = gtk::FileChooserDialog::with_buttons(
+				Some("Export the shopping list"),
+				None::<&gtk::Window>,
+				gtk::FileChooserAction::Save,
+				&[("_Cancel", gtk::ResponseType::Cancel), ("_Save", gtk::ResponseType::Accept)],
+			);
+			chooser.set_do_overwrite_confirmation(true);
+			chooser.set_current_name("shopping list.csv");
+			let response = chooser.run();
+			let path = chooser.filename();
+			chooser.close();
+			if let (gtk::ResponseType::Accept, Some(path)) = (response, path) {
+				if let Err(e) = fs::write(&path, list.to_csv()) {
+					show_error("Failed to export the shopping list", &e.to_string());
+				}
+			}
+		});
+	}
+
+	// f receives the skill id and the extra levels of the clicked row
+	pub fn connect_additional_skill<F: Fn(ID, Level) + 'static>(&self, f: F) {
+		self.additional_skills.connect_row_activated(move |view, path, _column| {
+			let model = view.model().unwrap();
+			if let Some(iter) = model.iter(path) {
+				let extra = model.value(&iter, 1).get::<u32>().unwrap();
+				let id = model.value(&iter, 2).get::<u32>().unwrap();
+				f(id as ID, extra as Level);
+			}
+		});
+	}
+
+	pub fn update_additional_skills(&self, skills: Vec<(Arc<Skill>, Level)>) {
+		self.additional_skills_store.clear();
+		for (skill, extra) in skills.iter() {
+			self.additional_skills_store.insert_with_values(None, &[
+				(0, &skill.name.to_string()),
+				(1, &(*extra as u32)),
+				(2, &(skill.id as u32)),
+			]);
+		}
+		self.additional.replace(skills);
 	}
 
 	pub fn update(&self, best_list: Vec<Equipment>) {
 		let best = best_list.first().unwrap();
+		self.additional_skills_store.clear();
+		self.additional.borrow_mut().clear();
 		self.weapon.update(&best.weapon);
 		for (i, piece) in self.armors.iter().enumerate() {
 			piece.update(&best.set[i]);
@@ -114,6 +190,7 @@ impl ResultPage {
 		for (i, tool) in self.tools.iter().enumerate() {  // Tools
 			tool.update(&best.tools[i]);
 		}
+		self.results_list.forall(|i| { self.results_list.remove(i) });
 		for (i, _equip) in best_list.iter().enumerate() {
 			let label = gtk::LabelBuilder::new().build();
 			label.set_text(format!("{}", i).as_str());
@@ -138,7 +215,7 @@ impl ResultPage {
 			let image: gtk::Image = builder.object("decoration image").unwrap();
 			set_image(&image, format!("slot {} {}", decoration.size, decoration.size).as_str(), &self.images);
 			let name: gtk::Label = builder.object("decoration name").unwrap();
-			name.set_text(decoration.name.as_str());
+			name.set_text(&decoration.name.to_string());
 			let quantity_label: gtk::Label = builder.object("decoration quantity").unwrap();
 			quantity_label.set_text(format!("x{}", quantity).as_str());
 			let row: gtk::ListBoxRow = builder.object("decoration row").unwrap();
@@ -151,5 +228,50 @@ impl ResultPage {
 		self.defences_summary.get(3).unwrap().set_text(best.get_thunder_defence().to_string().as_str());
 		self.defences_summary.get(4).unwrap().set_text(best.get_ice_defence().to_string().as_str());
 		self.defences_summary.get(5).unwrap().set_text(best.get_dragon_defence().to_string().as_str());
+		self.results.replace(best_list);
+	}
+
+	pub fn update_shopping_list(&self, list: ShoppingList) {
+		self.shopping.replace(Some(list));
+		self.show_shopping_list();
+	}
+
+	// One row for each material, the material names are the current ones
+	fn show_shopping_list(&self) {
+		self.shopping_list.forall(|i| { self.shopping_list.remove(i) });
+		let shopping = self.shopping.borrow();
+		let list = match shopping.as_ref() {
+			Some(list) => list,
+			None => return,
+		};
+		let mut lines: Vec<String> = list.materials.iter()
+			.map(|(material, quantity)| format!("{} x{}", material.name, quantity))
+			.collect();
+		if list.is_empty() {
+			lines.push(String::from("Nothing to craft"));
+		}
+		if list.missing.is_empty().not() {
+			lines.push(format!("No recipe for: {}", list.missing.join(", ")));
+		}
+		for line in lines {
+			let label = gtk::LabelBuilder::new().halign(gtk::Align::Start).build();
+			label.set_text(&line);
+			let child = gtk::ListBoxRowBuilder::new().build();
+			child.add(&label);
+			self.shopping_list.add(&child);
+		}
+		self.shopping_list.show_all();
+		self.export_shopping_btn.set_sensitive(list.materials.is_empty().not());
+	}
+
+	// Show the same results again, with the names in the current language
+	pub fn refresh(&self) {
+		self.show_shopping_list();
+		let results = self.results.take();
+		let additional = self.additional.take();
+		if results.is_empty().not() {
+			self.update(results);
+			self.update_additional_skills(additional);
+		}
 	}
 }
@@ -1,22 +1,42 @@
-use std::rc::Rc;
+use std::{
+	cell::{Cell, RefCell},
+	collections::HashMap,
+	rc::Rc,
+	str::FromStr,
+};
 
 use gio::prelude::*;
 use gtk::prelude::*;
 use gtk::prelude::BuilderExtManual;
 use itertools::Itertools;
+use strum::IntoEnumIterator;
 
-use crate::ui::get_builder;
+use crate::ui::{get_builder, pages::Refresher};
 use std::sync::Arc;
 use gtk::{Builder, FlowBoxChild, SizeGroupMode};
-use crate::data::db_storage::Storage;
-use crate::data::db_types::skill::SkillLevel;
-use crate::data::dyn_storage::DynamicStorage;
+use mhwiss::data::db_storage::Storage;
+use mhwiss::data::db_types::{ID, Level, WeaponClass, Weapons, monster::Monster, skill::SkillLevel, weapon::WeaponFeature};
+use mhwiss::data::dyn_storage::DynamicStorage;
+use mhwiss::search::Filters;
+
+// Id of the monster combo entry without a monster
+const NO_MONSTER: &str = "none";
+// Ids of the weapon class combo entries without a class
+const NO_WEAPON: &str = "none";
+const ANY_WEAPON: &str = "any";
 
 pub(crate) struct SkillsPage {
 	skill_list: gtk::FlowBox,
 	armor_set_skill_list: gtk::FlowBox,
 	search_bar: gtk::SearchEntry,
 	reset_btn: gtk::Button,
+	monster_combo: gtk::ComboBoxText,
+	monster_info: gtk::Label,
+	refilling: Rc<Cell<bool>>,  // The monster combo is being refilled, its changes are not the user's
+	weapon_class_combo: gtk::ComboBoxText,
+	weapon_feature_entry: gtk::Entry,
+	levels: RefCell<HashMap<ID, gtk::SpinButton>>,
+	names: Refresher,
 }
 
 impl SkillsPage {
@@ -25,11 +45,28 @@ impl SkillsPage {
 		let armor_set_skill_list = builder.object("skill set list").expect("UI do not contains \"skill set list\"");
 		let search_bar = builder.object("skill search bar").expect("UI do not contains \"skill search bar\"");
 		let reset_btn = builder.object("reset constraints btn").expect("UI do not contains \"reset constraints btn\"");
+		let monster_combo = builder.object("monster combo").expect("UI do not contains \"monster combo\"");
+		let monster_info = builder.object("monster info").expect("UI do not contains \"monster info\"");
+		let weapon_class_combo: gtk::ComboBoxText = builder.object("weapon class combo").expect("UI do not contains \"weapon class combo\"");
+		let weapon_feature_entry = builder.object("weapon feature entry").expect("UI do not contains \"weapon feature entry\"");
+		weapon_class_combo.append(Some(NO_WEAPON), "No weapon");
+		weapon_class_combo.append(Some(ANY_WEAPON), "Any weapon");
+		for class in WeaponClass::iter() {
+			weapon_class_combo.append(Some(class.to_string().as_str()), &class.to_string());
+		}
+		weapon_class_combo.set_active_id(Some(NO_WEAPON));
 		let page = SkillsPage {
 			skill_list,
 			armor_set_skill_list: armor_set_skill_list,
 			search_bar,
 			reset_btn,
+			monster_combo,
+			monster_info,
+			refilling: Default::default(),
+			weapon_class_combo,
+			weapon_feature_entry,
+			levels: Default::default(),
+			names: Default::default(),
 		};
 		page.connect_signals(dynamic_storage);
 		page
@@ -78,6 +115,26 @@ impl SkillsPage {
 	}
 
 	pub fn show(&self, storage: &Rc<Storage>, dynamic_storage: &Rc<DynamicStorage>) {
+		// The monsters by name in the current language, the chosen one stays chosen
+		let monster_combo = self.monster_combo.clone();
+		let storage_copy = Rc::clone(storage);
+		let refilling = Rc::clone(&self.refilling);
+		let monster_info = self.monster_info.clone();
+		self.names.bind(move || {
+			refilling.set(true);
+			let active = monster_combo.active_id();
+			monster_combo.remove_all();
+			monster_combo.append(Some(NO_MONSTER), "-");
+			for monster in storage_copy.monsters.iter().sorted_by(|a, b| a.name.cmp(&b.name)) {
+				monster_combo.append(Some(monster.id.to_string().as_str()), &monster.name.to_string());
+			}
+			monster_combo.set_active_id(Some(active.as_deref().unwrap_or(NO_MONSTER)));
+			refilling.set(false);
+			// Same monster, only the names change
+			let monster = active.and_then(|id| id.parse().ok()).and_then(|id| storage_copy.monsters.get(id));
+			set_monster_info(&monster_info, monster.map(|m| m.as_ref()), &storage_copy.weapons);
+		});
+
 		let size_group: gtk::SizeGroup = gtk::SizeGroup::new(SizeGroupMode::Both);
 		for skill in storage.skills.iter().sorted_by(|a, b| { a.name.cmp(&b.name) }) {
 			let builder = get_builder("res/gui/skill box.glade".to_string());
@@ -92,8 +149,11 @@ impl SkillsPage {
 			style.add_provider(&provider, gtk::STYLE_PROVIDER_PRIORITY_USER);
 			style.add_class("FlowBoxSkill");  // TODO: Better implementation using glades => Add this feature in glade
 
-			name.set_text(skill.name.as_str());
-			name.set_tooltip_text(Some(skill.description.as_str()));
+			let skill_copy = Arc::clone(skill);
+			self.names.bind(move || {
+				name.set_text(&skill_copy.name.to_string());
+				name.set_tooltip_text(Some(&skill_copy.description.get()));
+			});
 			adjustment.set_upper(skill.max_level as f64);
 
 			let dynamic_storage_copy = Rc::clone(dynamic_storage);
@@ -103,6 +163,7 @@ impl SkillsPage {
 				dynamic_storage_copy.set_constraint(skill_level);
 			});
 			size_group.add_widget(&skill_flowbox);
+			self.levels.borrow_mut().insert(skill.id, level);
 
 			self.skill_list.insert(&skill_flowbox, -1);
 		}
@@ -120,7 +181,8 @@ impl SkillsPage {
 			style.add_provider(&provider, gtk::STYLE_PROVIDER_PRIORITY_USER);
 			style.add_class("FlowBoxSkill");
 
-			name.set_text(skill.name.as_str());
+			let skill_copy = Arc::clone(skill);
+			self.names.bind(move || name.set_text(&skill_copy.name.to_string()));
 			adjustment.set_upper(skill.get_max() as f64);
 			/*
 			let app = Rc::clone(&application);
@@ -136,4 +198,56 @@ impl SkillsPage {
 			self.armor_set_skill_list.insert(&skill_flowbox, -1);
 		}
 	}
-}
\ No newline at end of file
+
+	pub fn refresh(&self) {
+		self.names.refresh();
+	}
+
+	// Called with the id of the chosen target monster, None without a monster. Not called on a refill of the names
+	pub fn connect_monster<F: Fn(Option<ID>) + 'static>(&self, f: F) {
+		let refilling = Rc::clone(&self.refilling);
+		self.monster_combo.connect_changed(move |combo| {
+			if refilling.get() {
+				return;
+			}
+			f(combo.active_id().and_then(|id| id.parse().ok()));
+		});
+	}
+
+	// Weaknesses and ailments of monster, with the weapons that hit its weaknesses
+	pub fn show_monster(&self, monster: Option<&Monster>, weapons: &Weapons) {
+		set_monster_info(&self.monster_info, monster, weapons);
+	}
+
+	// The weapon the search must pick, Err with the message of an unknown feature
+	pub fn weapon_filters(&self) -> Result<Filters, String> {
+		let mut filters = Filters::default();
+		match self.weapon_class_combo.active_id().as_deref() {
+			None | Some(NO_WEAPON) => {}
+			Some(ANY_WEAPON) => filters.weapons = true,
+			Some(class) => filters.weapon_classes.push(WeaponClass::from_str(class).map_err(|_| format!("Unknown weapon class {}", class))?),
+		}
+		for feature in self.weapon_feature_entry.text().split_whitespace() {
+			filters.weapon_features.push(WeaponFeature::from_str(feature)?);
+		}
+		Ok(filters)
+	}
+
+	// Change the level from outside the page, the spin button signal updates the constraints
+	pub fn set_level(&self, skill_id: ID, level: Level) {
+		if let Some(spin) = self.levels.borrow().get(&skill_id) {
+			spin.set_value(level as f64);
+		}
+	}
+}
+
+fn set_monster_info(label: &gtk::Label, monster: Option<&Monster>, weapons: &Weapons) {
+	let text = monster.map_or_else(String::new, |monster| {
+		let best = monster.best_weapons(weapons, 3).into_iter()
+			.map(|w| w.name.to_string())
+			.join(", ");
+		if best.is_empty() { monster.to_string() } else { format!("{}, best weapons: {}", monster, best) }
+	});
+	label.set_text(&text);
+	label.set_tooltip_text(Some(&text));
+}